@@ -0,0 +1,6 @@
+DROP TRIGGER likes_record_event ON likes;
+DROP FUNCTION record_like_event();
+DROP TRIGGER comments_record_event ON comments;
+DROP FUNCTION record_comment_event();
+DROP TABLE blog_events;
+DROP FUNCTION notify_blog_event();
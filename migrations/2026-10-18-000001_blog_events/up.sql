@@ -0,0 +1,58 @@
+CREATE TABLE blog_events (
+    id BIGSERIAL PRIMARY KEY,
+    blog_id UUID NOT NULL,
+    event_type VARCHAR NOT NULL,
+    payload JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX blog_events_blog_id_id_idx ON blog_events (blog_id, id);
+
+-- Only the event id is sent over NOTIFY: payloads are capped at 8000 bytes
+-- and listeners load the full row from blog_events anyway.
+CREATE FUNCTION notify_blog_event() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_notify('blog_events', NEW.id::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER blog_events_notify
+    AFTER INSERT ON blog_events
+    FOR EACH ROW EXECUTE FUNCTION notify_blog_event();
+
+CREATE FUNCTION record_comment_event() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        INSERT INTO blog_events (blog_id, event_type, payload)
+        VALUES (NEW.blog_id, 'comment_created', to_jsonb(NEW));
+    ELSIF TG_OP = 'UPDATE' THEN
+        INSERT INTO blog_events (blog_id, event_type, payload)
+        VALUES (NEW.blog_id, 'comment_updated', to_jsonb(NEW));
+    ELSE
+        INSERT INTO blog_events (blog_id, event_type, payload)
+        VALUES (OLD.blog_id, 'comment_deleted',
+                jsonb_build_object('id', OLD.id, 'parent_comment_id', OLD.parent_comment_id));
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER comments_record_event
+    AFTER INSERT OR UPDATE OR DELETE ON comments
+    FOR EACH ROW EXECUTE FUNCTION record_comment_event();
+
+CREATE FUNCTION record_like_event() RETURNS TRIGGER AS $$
+DECLARE
+    target UUID := COALESCE(NEW.blog_id, OLD.blog_id);
+BEGIN
+    INSERT INTO blog_events (blog_id, event_type, payload)
+    VALUES (target, 'like_count',
+            jsonb_build_object('like_count', (SELECT COUNT(*) FROM likes WHERE blog_id = target)));
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER likes_record_event
+    AFTER INSERT OR DELETE ON likes
+    FOR EACH ROW EXECUTE FUNCTION record_like_event();
@@ -0,0 +1,2 @@
+DROP TRIGGER blogs_record_event ON blogs;
+DROP FUNCTION record_blog_event();
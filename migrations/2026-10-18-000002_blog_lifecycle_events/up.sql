@@ -0,0 +1,19 @@
+CREATE FUNCTION record_blog_event() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        INSERT INTO blog_events (blog_id, event_type, payload)
+        VALUES (NEW.id, 'blog_created', to_jsonb(NEW));
+    ELSIF TG_OP = 'UPDATE' THEN
+        INSERT INTO blog_events (blog_id, event_type, payload)
+        VALUES (NEW.id, 'blog_updated', to_jsonb(NEW));
+    ELSE
+        INSERT INTO blog_events (blog_id, event_type, payload)
+        VALUES (OLD.id, 'blog_deleted', jsonb_build_object('id', OLD.id, 'author_id', OLD.author_id));
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER blogs_record_event
+    AFTER INSERT OR UPDATE OR DELETE ON blogs
+    FOR EACH ROW EXECUTE FUNCTION record_blog_event();
@@ -0,0 +1 @@
+ALTER TABLE likes DROP CONSTRAINT likes_blog_id_user_id_key;
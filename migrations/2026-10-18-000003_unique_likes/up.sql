@@ -0,0 +1,8 @@
+-- Keep the earliest like of every duplicated (blog_id, user_id) pair.
+DELETE FROM likes a
+    USING likes b
+    WHERE a.blog_id = b.blog_id
+      AND a.user_id = b.user_id
+      AND (a.created_at, a.id) > (b.created_at, b.id);
+
+ALTER TABLE likes ADD CONSTRAINT likes_blog_id_user_id_key UNIQUE (blog_id, user_id);
@@ -0,0 +1,21 @@
+DROP TRIGGER blogs_record_update_event ON blogs;
+DROP TRIGGER blogs_record_event ON blogs;
+
+CREATE TRIGGER blogs_record_event
+    AFTER INSERT OR UPDATE OR DELETE ON blogs
+    FOR EACH ROW EXECUTE FUNCTION record_blog_event();
+
+DROP TRIGGER comments_maintain_counters ON comments;
+DROP FUNCTION maintain_blog_comment_count();
+DROP TRIGGER likes_maintain_counters ON likes;
+DROP FUNCTION maintain_blog_like_count();
+
+DROP INDEX blogs_view_count_idx;
+DROP INDEX blogs_comment_count_idx;
+DROP INDEX blogs_like_count_idx;
+DROP INDEX blogs_created_at_idx;
+
+ALTER TABLE blogs
+    DROP COLUMN view_count,
+    DROP COLUMN comment_count,
+    DROP COLUMN like_count;
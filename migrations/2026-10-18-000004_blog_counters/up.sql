@@ -0,0 +1,57 @@
+ALTER TABLE blogs
+    ADD COLUMN like_count BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN comment_count BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN view_count BIGINT NOT NULL DEFAULT 0;
+
+UPDATE blogs SET
+    like_count = (SELECT COUNT(*) FROM likes WHERE likes.blog_id = blogs.id),
+    comment_count = (SELECT COUNT(*) FROM comments WHERE comments.blog_id = blogs.id);
+
+CREATE INDEX blogs_created_at_idx ON blogs (created_at DESC);
+CREATE INDEX blogs_like_count_idx ON blogs (like_count DESC);
+CREATE INDEX blogs_comment_count_idx ON blogs (comment_count DESC);
+CREATE INDEX blogs_view_count_idx ON blogs (view_count DESC);
+
+CREATE FUNCTION maintain_blog_like_count() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        UPDATE blogs SET like_count = like_count + 1 WHERE id = NEW.blog_id;
+    ELSE
+        UPDATE blogs SET like_count = like_count - 1 WHERE id = OLD.blog_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER likes_maintain_counters
+    AFTER INSERT OR DELETE ON likes
+    FOR EACH ROW EXECUTE FUNCTION maintain_blog_like_count();
+
+CREATE FUNCTION maintain_blog_comment_count() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        UPDATE blogs SET comment_count = comment_count + 1 WHERE id = NEW.blog_id;
+    ELSE
+        UPDATE blogs SET comment_count = comment_count - 1 WHERE id = OLD.blog_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER comments_maintain_counters
+    AFTER INSERT OR DELETE ON comments
+    FOR EACH ROW EXECUTE FUNCTION maintain_blog_comment_count();
+
+-- Counter and view updates must not be broadcast as edits, so `blog_updated`
+-- is only recorded when the post itself changed.
+DROP TRIGGER blogs_record_event ON blogs;
+
+CREATE TRIGGER blogs_record_event
+    AFTER INSERT OR DELETE ON blogs
+    FOR EACH ROW EXECUTE FUNCTION record_blog_event();
+
+CREATE TRIGGER blogs_record_update_event
+    AFTER UPDATE ON blogs
+    FOR EACH ROW
+    WHEN (OLD.updated_at IS DISTINCT FROM NEW.updated_at)
+    EXECUTE FUNCTION record_blog_event();
@@ -0,0 +1,48 @@
+DROP TRIGGER comments_delete_reactions ON comments;
+DROP TRIGGER blogs_delete_reactions ON blogs;
+DROP FUNCTION delete_target_reactions();
+
+CREATE TABLE likes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    blog_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT likes_blog_id_user_id_key UNIQUE (blog_id, user_id)
+);
+
+INSERT INTO likes (id, blog_id, user_id, created_at)
+SELECT id, target_id, user_id, created_at FROM reactions
+WHERE target_type = 'blog' AND kind = 'like';
+
+DROP TABLE reactions;
+DROP FUNCTION record_reaction_change();
+
+CREATE FUNCTION record_like_event() RETURNS TRIGGER AS $$
+DECLARE
+    target UUID := COALESCE(NEW.blog_id, OLD.blog_id);
+BEGIN
+    INSERT INTO blog_events (blog_id, event_type, payload)
+    VALUES (target, 'like_count',
+            jsonb_build_object('like_count', (SELECT COUNT(*) FROM likes WHERE blog_id = target)));
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER likes_record_event
+    AFTER INSERT OR DELETE ON likes
+    FOR EACH ROW EXECUTE FUNCTION record_like_event();
+
+CREATE FUNCTION maintain_blog_like_count() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        UPDATE blogs SET like_count = like_count + 1 WHERE id = NEW.blog_id;
+    ELSE
+        UPDATE blogs SET like_count = like_count - 1 WHERE id = OLD.blog_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER likes_maintain_counters
+    AFTER INSERT OR DELETE ON likes
+    FOR EACH ROW EXECUTE FUNCTION maintain_blog_like_count();
@@ -0,0 +1,86 @@
+CREATE TABLE reactions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    target_type VARCHAR NOT NULL CHECK (target_type IN ('blog', 'comment')),
+    target_id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    kind VARCHAR NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT reactions_target_user_kind_key UNIQUE (target_type, target_id, user_id, kind)
+);
+
+CREATE INDEX reactions_user_id_idx ON reactions (user_id);
+
+-- Existing likes become "like" reactions on blogs and keep their ids, so
+-- `/likes/{id}` URLs stay valid.
+INSERT INTO reactions (id, target_type, target_id, user_id, kind, created_at)
+SELECT id, 'blog', blog_id, user_id, 'like', created_at FROM likes;
+
+DROP TABLE likes;
+DROP FUNCTION record_like_event();
+DROP FUNCTION maintain_blog_like_count();
+
+CREATE FUNCTION record_reaction_change() RETURNS TRIGGER AS $$
+DECLARE
+    changed reactions%ROWTYPE;
+    delta BIGINT;
+    event_blog_id UUID;
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        changed := NEW;
+        delta := 1;
+    ELSE
+        changed := OLD;
+        delta := -1;
+    END IF;
+
+    IF changed.target_type = 'blog' THEN
+        event_blog_id := changed.target_id;
+        IF changed.kind = 'like' THEN
+            UPDATE blogs SET like_count = like_count + delta WHERE id = changed.target_id;
+        END IF;
+    ELSE
+        SELECT blog_id INTO event_blog_id FROM comments WHERE id = changed.target_id;
+    END IF;
+
+    IF event_blog_id IS NOT NULL THEN
+        INSERT INTO blog_events (blog_id, event_type, payload)
+        VALUES (event_blog_id, 'reaction_count', jsonb_build_object(
+            'target_type', changed.target_type,
+            'target_id', changed.target_id,
+            'kind', changed.kind,
+            'count', (SELECT COUNT(*) FROM reactions
+                      WHERE target_type = changed.target_type
+                        AND target_id = changed.target_id
+                        AND kind = changed.kind)));
+
+        IF changed.target_type = 'blog' AND changed.kind = 'like' THEN
+            INSERT INTO blog_events (blog_id, event_type, payload)
+            VALUES (event_blog_id, 'like_count', jsonb_build_object(
+                'like_count', (SELECT like_count FROM blogs WHERE id = event_blog_id)));
+        END IF;
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER reactions_record_change
+    AFTER INSERT OR DELETE ON reactions
+    FOR EACH ROW EXECUTE FUNCTION record_reaction_change();
+
+-- Reactions point at blogs and comments without a foreign key, so they are
+-- removed together with their target here.
+CREATE FUNCTION delete_target_reactions() RETURNS TRIGGER AS $$
+BEGIN
+    DELETE FROM reactions WHERE target_type = TG_ARGV[0] AND target_id = OLD.id;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER blogs_delete_reactions
+    AFTER DELETE ON blogs
+    FOR EACH ROW EXECUTE FUNCTION delete_target_reactions('blog');
+
+CREATE TRIGGER comments_delete_reactions
+    AFTER DELETE ON comments
+    FOR EACH ROW EXECUTE FUNCTION delete_target_reactions('comment');
@@ -0,0 +1,16 @@
+DROP TRIGGER comments_record_update_event ON comments;
+DROP TRIGGER comments_record_event ON comments;
+
+CREATE TRIGGER comments_record_event
+    AFTER INSERT OR UPDATE OR DELETE ON comments
+    FOR EACH ROW EXECUTE FUNCTION record_comment_event();
+
+DROP TABLE comment_votes;
+DROP FUNCTION maintain_comment_score();
+
+DROP INDEX comments_blog_id_idx;
+
+ALTER TABLE comments
+    DROP COLUMN score,
+    DROP COLUMN downvotes,
+    DROP COLUMN upvotes;
@@ -0,0 +1,69 @@
+ALTER TABLE comments
+    ADD COLUMN upvotes BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN downvotes BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN score BIGINT NOT NULL DEFAULT 0;
+
+CREATE INDEX comments_blog_id_idx ON comments (blog_id);
+
+CREATE TABLE comment_votes (
+    comment_id UUID NOT NULL REFERENCES comments (id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    value SMALLINT NOT NULL CHECK (value IN (-1, 1)),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (comment_id, user_id)
+);
+
+CREATE INDEX comment_votes_user_id_idx ON comment_votes (user_id);
+
+CREATE FUNCTION maintain_comment_score() RETURNS TRIGGER AS $$
+DECLARE
+    target UUID := COALESCE(NEW.comment_id, OLD.comment_id);
+    up_delta BIGINT := 0;
+    down_delta BIGINT := 0;
+    updated comments%ROWTYPE;
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        IF OLD.value = 1 THEN up_delta := up_delta - 1; ELSE down_delta := down_delta - 1; END IF;
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        IF NEW.value = 1 THEN up_delta := up_delta + 1; ELSE down_delta := down_delta + 1; END IF;
+    END IF;
+
+    UPDATE comments SET
+        upvotes = upvotes + up_delta,
+        downvotes = downvotes + down_delta,
+        score = score + up_delta - down_delta
+    WHERE id = target
+    RETURNING * INTO updated;
+
+    IF FOUND THEN
+        INSERT INTO blog_events (blog_id, event_type, payload)
+        VALUES (updated.blog_id, 'comment_score', jsonb_build_object(
+            'id', updated.id,
+            'upvotes', updated.upvotes,
+            'downvotes', updated.downvotes,
+            'score', updated.score));
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER comment_votes_maintain_score
+    AFTER INSERT OR UPDATE OR DELETE ON comment_votes
+    FOR EACH ROW EXECUTE FUNCTION maintain_comment_score();
+
+-- Score updates are broadcast as `comment_score`, so `comment_updated` is
+-- only recorded when the comment text changed.
+DROP TRIGGER comments_record_event ON comments;
+
+CREATE TRIGGER comments_record_event
+    AFTER INSERT OR DELETE ON comments
+    FOR EACH ROW EXECUTE FUNCTION record_comment_event();
+
+CREATE TRIGGER comments_record_update_event
+    AFTER UPDATE ON comments
+    FOR EACH ROW
+    WHEN (OLD.content IS DISTINCT FROM NEW.content)
+    EXECUTE FUNCTION record_comment_event();
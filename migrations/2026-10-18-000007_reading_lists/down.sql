@@ -0,0 +1,2 @@
+DROP TABLE reading_list_items;
+DROP TABLE reading_lists;
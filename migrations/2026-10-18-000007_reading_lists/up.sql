@@ -0,0 +1,26 @@
+CREATE TABLE reading_lists (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    name VARCHAR NOT NULL,
+    description TEXT,
+    is_public BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX reading_lists_user_id_idx ON reading_lists (user_id);
+
+-- `blog_id` is cleared when the blog is deleted; `blog_title` keeps a label
+-- for the item, which then shows as no longer available.
+CREATE TABLE reading_list_items (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    list_id UUID NOT NULL REFERENCES reading_lists (id) ON DELETE CASCADE,
+    blog_id UUID REFERENCES blogs (id) ON DELETE SET NULL,
+    blog_title VARCHAR NOT NULL,
+    position INTEGER NOT NULL,
+    added_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT reading_list_items_list_id_blog_id_key UNIQUE (list_id, blog_id)
+);
+
+CREATE INDEX reading_list_items_list_id_position_idx ON reading_list_items (list_id, position);
+CREATE INDEX reading_list_items_blog_id_idx ON reading_list_items (blog_id);
@@ -0,0 +1,2 @@
+DROP INDEX blogs_author_id_created_at_idx;
+DROP TABLE follows;
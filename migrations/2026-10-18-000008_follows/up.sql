@@ -0,0 +1,13 @@
+CREATE TABLE follows (
+    follower_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    followee_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (follower_id, followee_id),
+    CONSTRAINT follows_not_self CHECK (follower_id <> followee_id)
+);
+
+CREATE INDEX follows_followee_id_idx ON follows (followee_id, created_at DESC);
+
+-- The home feed is assembled on read, walking each followed author's posts
+-- newest first.
+CREATE INDEX blogs_author_id_created_at_idx ON blogs (author_id, created_at DESC, id DESC);
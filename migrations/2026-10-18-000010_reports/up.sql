@@ -0,0 +1,40 @@
+ALTER TABLE users
+    ADD COLUMN role VARCHAR NOT NULL DEFAULT 'user'
+        CONSTRAINT users_role_check CHECK (role IN ('user', 'moderator', 'admin'));
+
+CREATE TABLE reports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    reporter_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    target_type VARCHAR NOT NULL CHECK (target_type IN ('blog', 'comment', 'user')),
+    -- Not a foreign key: the target may be removed as the outcome of the report.
+    target_id UUID NOT NULL,
+    reason VARCHAR NOT NULL,
+    details TEXT,
+    status VARCHAR NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'actioned', 'dismissed')),
+    assignee_id UUID REFERENCES users (id) ON DELETE SET NULL,
+    resolution_notes TEXT,
+    resolved_by UUID REFERENCES users (id) ON DELETE SET NULL,
+    resolved_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX reports_status_created_at_idx ON reports (status, created_at);
+CREATE INDEX reports_target_idx ON reports (target_type, target_id);
+CREATE INDEX reports_assignee_id_idx ON reports (assignee_id);
+
+-- Every moderation and administration action, optionally tied to the report
+-- that prompted it.
+CREATE TABLE audit_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    actor_id UUID REFERENCES users (id) ON DELETE SET NULL,
+    action VARCHAR NOT NULL,
+    target_type VARCHAR NOT NULL,
+    target_id UUID NOT NULL,
+    report_id UUID REFERENCES reports (id) ON DELETE SET NULL,
+    notes TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX audit_log_target_idx ON audit_log (target_type, target_id);
+CREATE INDEX audit_log_report_id_idx ON audit_log (report_id);
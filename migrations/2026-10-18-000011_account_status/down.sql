@@ -0,0 +1,6 @@
+DROP INDEX users_shadow_banned_idx;
+ALTER TABLE users
+    DROP CONSTRAINT users_suspension_expires_check,
+    DROP COLUMN status_expires_at,
+    DROP COLUMN status_reason,
+    DROP COLUMN status;
@@ -0,0 +1,10 @@
+-- `status_expires_at` ends a suspension, ban or shadow-ban; once it has passed
+-- the account is treated as active again.
+ALTER TABLE users
+    ADD COLUMN status VARCHAR NOT NULL DEFAULT 'active'
+        CONSTRAINT users_status_check CHECK (status IN ('active', 'suspended', 'banned', 'shadow_banned')),
+    ADD COLUMN status_reason TEXT,
+    ADD COLUMN status_expires_at TIMESTAMPTZ,
+    ADD CONSTRAINT users_suspension_expires_check CHECK (status <> 'suspended' OR status_expires_at IS NOT NULL);
+
+CREATE INDEX users_shadow_banned_idx ON users (id) WHERE status = 'shadow_banned';
@@ -0,0 +1,3 @@
+ALTER TABLE blogs
+    DROP COLUMN comment_auto_close_days,
+    DROP COLUMN comment_mode;
@@ -0,0 +1,8 @@
+-- `comment_auto_close_days` locks comments that many days after the blog was
+-- published (or created, if it never was); it is applied when comments are
+-- read and written, not stored.
+ALTER TABLE blogs
+    ADD COLUMN comment_mode VARCHAR NOT NULL DEFAULT 'open'
+        CONSTRAINT blogs_comment_mode_check CHECK (comment_mode IN ('open', 'closed', 'locked', 'followers_only', 'registered_only')),
+    ADD COLUMN comment_auto_close_days INTEGER
+        CONSTRAINT blogs_comment_auto_close_days_check CHECK (comment_auto_close_days > 0);
@@ -0,0 +1,6 @@
+DROP TABLE blog_shares;
+DROP INDEX blogs_visibility_idx;
+ALTER TABLE blogs
+    DROP CONSTRAINT blogs_password_hash_check,
+    DROP COLUMN password_hash,
+    DROP COLUMN visibility;
@@ -0,0 +1,17 @@
+ALTER TABLE blogs
+    ADD COLUMN visibility VARCHAR NOT NULL DEFAULT 'public'
+        CONSTRAINT blogs_visibility_check CHECK (visibility IN ('public', 'unlisted', 'private', 'shared', 'password')),
+    -- bcrypt hash of the post password, set only for `password` visibility.
+    ADD COLUMN password_hash VARCHAR,
+    ADD CONSTRAINT blogs_password_hash_check CHECK ((visibility = 'password') = (password_hash IS NOT NULL));
+
+CREATE INDEX blogs_visibility_idx ON blogs (visibility);
+
+CREATE TABLE blog_shares (
+    blog_id UUID NOT NULL REFERENCES blogs (id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (blog_id, user_id)
+);
+
+CREATE INDEX blog_shares_user_id_idx ON blog_shares (user_id);
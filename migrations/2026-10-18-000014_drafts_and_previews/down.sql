@@ -0,0 +1,8 @@
+DROP TABLE blog_preview_tokens;
+DROP TRIGGER blogs_record_revision ON blogs;
+DROP FUNCTION record_blog_revision();
+DROP TABLE blog_revisions;
+
+ALTER TABLE blogs
+    DROP COLUMN published_at,
+    DROP COLUMN status;
@@ -0,0 +1,55 @@
+ALTER TABLE blogs
+    ADD COLUMN status VARCHAR NOT NULL DEFAULT 'published'
+        CONSTRAINT blogs_status_check CHECK (status IN ('draft', 'published')),
+    ADD COLUMN published_at TIMESTAMPTZ;
+
+UPDATE blogs SET published_at = created_at;
+
+-- Every saved version of a blog's title and content, numbered per blog.
+CREATE TABLE blog_revisions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    blog_id UUID NOT NULL REFERENCES blogs (id) ON DELETE CASCADE,
+    revision_number INTEGER NOT NULL,
+    title VARCHAR NOT NULL,
+    content TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT blog_revisions_blog_id_revision_number_key UNIQUE (blog_id, revision_number)
+);
+
+INSERT INTO blog_revisions (blog_id, revision_number, title, content, created_at)
+SELECT id, 1, title, content, updated_at FROM blogs;
+
+CREATE FUNCTION record_blog_revision() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'UPDATE' AND NEW.title = OLD.title AND NEW.content = OLD.content THEN
+        RETURN NULL;
+    END IF;
+
+    INSERT INTO blog_revisions (blog_id, revision_number, title, content)
+    VALUES (
+        NEW.id,
+        COALESCE((SELECT MAX(revision_number) FROM blog_revisions WHERE blog_id = NEW.id), 0) + 1,
+        NEW.title,
+        NEW.content);
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER blogs_record_revision
+    AFTER INSERT OR UPDATE OF title, content ON blogs
+    FOR EACH ROW EXECUTE FUNCTION record_blog_revision();
+
+-- Preview links are signed tokens naming one of these rows, so they can be
+-- listed and revoked. `revision_id` pins the link to one saved version.
+CREATE TABLE blog_preview_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    blog_id UUID NOT NULL REFERENCES blogs (id) ON DELETE CASCADE,
+    revision_id UUID REFERENCES blog_revisions (id) ON DELETE CASCADE,
+    created_by UUID REFERENCES users (id) ON DELETE SET NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX blog_preview_tokens_blog_id_idx ON blog_preview_tokens (blog_id);
@@ -0,0 +1,18 @@
+DROP TABLE blog_transitions;
+
+DROP INDEX blogs_review_queue_idx;
+DROP INDEX blogs_scheduled_idx;
+
+UPDATE blogs SET status = 'draft', published_at = NULL
+WHERE status NOT IN ('draft', 'published');
+
+ALTER TABLE blogs
+    DROP COLUMN reviewer_id,
+    DROP CONSTRAINT blogs_status_check,
+    ADD CONSTRAINT blogs_status_check CHECK (status IN ('draft', 'published'));
+
+UPDATE users SET role = 'user' WHERE role = 'editor';
+
+ALTER TABLE users
+    DROP CONSTRAINT users_role_check,
+    ADD CONSTRAINT users_role_check CHECK (role IN ('user', 'moderator', 'admin'));
@@ -0,0 +1,28 @@
+ALTER TABLE users
+    DROP CONSTRAINT users_role_check,
+    ADD CONSTRAINT users_role_check CHECK (role IN ('user', 'editor', 'moderator', 'admin'));
+
+-- Scheduled blogs keep their go-live time in published_at until the
+-- scheduler publishes them.
+ALTER TABLE blogs
+    DROP CONSTRAINT blogs_status_check,
+    ADD CONSTRAINT blogs_status_check
+        CHECK (status IN ('draft', 'in_review', 'changes_requested', 'scheduled', 'published')),
+    ADD COLUMN reviewer_id UUID REFERENCES users (id) ON DELETE SET NULL;
+
+CREATE INDEX blogs_scheduled_idx ON blogs (published_at) WHERE status = 'scheduled';
+CREATE INDEX blogs_review_queue_idx ON blogs (reviewer_id, updated_at) WHERE status = 'in_review';
+
+-- One row per workflow status change; actor_id is NULL for the scheduler.
+CREATE TABLE blog_transitions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    blog_id UUID NOT NULL REFERENCES blogs (id) ON DELETE CASCADE,
+    from_status VARCHAR NOT NULL,
+    to_status VARCHAR NOT NULL,
+    action VARCHAR NOT NULL,
+    actor_id UUID REFERENCES users (id) ON DELETE SET NULL,
+    comment TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX blog_transitions_blog_id_idx ON blog_transitions (blog_id, created_at);
@@ -0,0 +1,5 @@
+DROP INDEX blogs_publication_id_idx;
+ALTER TABLE blogs DROP COLUMN publication_id;
+
+DROP TABLE publication_members;
+DROP TABLE publications;
@@ -0,0 +1,28 @@
+CREATE TABLE publications (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR NOT NULL,
+    slug VARCHAR NOT NULL CONSTRAINT publications_slug_key UNIQUE,
+    description TEXT,
+    logo_url VARCHAR,
+    accent_color VARCHAR,
+    -- Writers must go through the review workflow instead of publishing directly
+    require_review BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE publication_members (
+    publication_id UUID NOT NULL REFERENCES publications (id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    role VARCHAR NOT NULL
+        CONSTRAINT publication_members_role_check CHECK (role IN ('owner', 'editor', 'writer')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (publication_id, user_id)
+);
+
+CREATE INDEX publication_members_user_id_idx ON publication_members (user_id);
+
+-- Deleting a publication leaves its blogs with their authors.
+ALTER TABLE blogs ADD COLUMN publication_id UUID REFERENCES publications (id) ON DELETE SET NULL;
+
+CREATE INDEX blogs_publication_id_idx ON blogs (publication_id, created_at) WHERE publication_id IS NOT NULL;
@@ -0,0 +1,6 @@
+DROP TABLE notifications;
+DROP TABLE blog_author_invitations;
+
+DROP TRIGGER blogs_add_owner_as_author ON blogs;
+DROP FUNCTION add_blog_owner_as_author();
+DROP TABLE blog_authors;
@@ -0,0 +1,59 @@
+-- Everyone credited on a blog, in byline order. The owner in blogs.author_id
+-- always has a row with role 'author'.
+CREATE TABLE blog_authors (
+    blog_id UUID NOT NULL REFERENCES blogs (id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    role VARCHAR NOT NULL
+        CONSTRAINT blog_authors_role_check CHECK (role IN ('author', 'contributor')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (blog_id, user_id)
+);
+
+CREATE INDEX blog_authors_user_id_idx ON blog_authors (user_id);
+
+INSERT INTO blog_authors (blog_id, user_id, position, role, created_at)
+SELECT id, author_id, 0, 'author', created_at FROM blogs;
+
+CREATE FUNCTION add_blog_owner_as_author() RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO blog_authors (blog_id, user_id, position, role)
+    VALUES (NEW.id, NEW.author_id, 0, 'author');
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER blogs_add_owner_as_author
+    AFTER INSERT ON blogs
+    FOR EACH ROW EXECUTE FUNCTION add_blog_owner_as_author();
+
+CREATE TABLE blog_author_invitations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    blog_id UUID NOT NULL REFERENCES blogs (id) ON DELETE CASCADE,
+    invitee_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    inviter_id UUID REFERENCES users (id) ON DELETE SET NULL,
+    role VARCHAR NOT NULL
+        CONSTRAINT blog_author_invitations_role_check CHECK (role IN ('author', 'contributor')),
+    status VARCHAR NOT NULL DEFAULT 'pending'
+        CONSTRAINT blog_author_invitations_status_check CHECK (status IN ('pending', 'accepted', 'declined', 'revoked')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    responded_at TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX blog_author_invitations_pending_key
+    ON blog_author_invitations (blog_id, invitee_id) WHERE status = 'pending';
+CREATE INDEX blog_author_invitations_invitee_idx ON blog_author_invitations (invitee_id, created_at);
+
+CREATE TABLE notifications (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    actor_id UUID REFERENCES users (id) ON DELETE SET NULL,
+    kind VARCHAR NOT NULL,
+    blog_id UUID REFERENCES blogs (id) ON DELETE CASCADE,
+    data JSONB NOT NULL DEFAULT '{}',
+    read_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX notifications_user_id_idx ON notifications (user_id, created_at DESC, id DESC);
+CREATE INDEX notifications_unread_idx ON notifications (user_id) WHERE read_at IS NULL;
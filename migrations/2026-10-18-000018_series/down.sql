@@ -0,0 +1,2 @@
+DROP TABLE series_blogs;
+DROP TABLE series;
@@ -0,0 +1,22 @@
+CREATE TABLE series (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    publication_id UUID REFERENCES publications (id) ON DELETE SET NULL,
+    title VARCHAR NOT NULL,
+    description TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX series_owner_id_idx ON series (owner_id, created_at);
+CREATE INDEX series_publication_id_idx ON series (publication_id, created_at) WHERE publication_id IS NOT NULL;
+
+-- A blog is in at most one series; deleting a series leaves its blogs alone.
+CREATE TABLE series_blogs (
+    series_id UUID NOT NULL REFERENCES series (id) ON DELETE CASCADE,
+    blog_id UUID NOT NULL REFERENCES blogs (id) ON DELETE CASCADE
+        CONSTRAINT series_blogs_blog_id_key UNIQUE,
+    position INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (series_id, blog_id)
+);
@@ -0,0 +1,5 @@
+DROP FUNCTION IF EXISTS blog_search_rank(UUID, TEXT, VARCHAR);
+DROP TABLE IF EXISTS blog_translations;
+DROP INDEX IF EXISTS blogs_search_vector_idx;
+ALTER TABLE blogs DROP COLUMN IF EXISTS search_vector, DROP COLUMN IF EXISTS locale;
+DROP FUNCTION IF EXISTS blog_search_config(VARCHAR);
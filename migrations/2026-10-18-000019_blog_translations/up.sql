@@ -0,0 +1,49 @@
+-- Text-search configuration for each supported locale.
+CREATE FUNCTION blog_search_config(locale VARCHAR) RETURNS regconfig
+LANGUAGE sql IMMUTABLE AS $$
+    SELECT CASE locale
+        WHEN 'fr' THEN 'french'::regconfig
+        WHEN 'de' THEN 'german'::regconfig
+        ELSE 'english'::regconfig
+    END
+$$;
+
+ALTER TABLE blogs
+    ADD COLUMN locale VARCHAR NOT NULL DEFAULT 'en'
+        CONSTRAINT blogs_locale_check CHECK (locale IN ('en', 'fr', 'de')),
+    ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (
+        to_tsvector(blog_search_config(locale), title || ' ' || content)
+    ) STORED;
+
+CREATE INDEX blogs_search_vector_idx ON blogs USING GIN (search_vector);
+
+CREATE TABLE blog_translations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    blog_id UUID NOT NULL REFERENCES blogs (id) ON DELETE CASCADE,
+    locale VARCHAR NOT NULL CONSTRAINT blog_translations_locale_check CHECK (locale IN ('en', 'fr', 'de')),
+    title VARCHAR NOT NULL,
+    content TEXT NOT NULL,
+    slug VARCHAR NOT NULL,
+    search_vector TSVECTOR GENERATED ALWAYS AS (
+        to_tsvector(blog_search_config(locale), title || ' ' || content)
+    ) STORED,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT blog_translations_blog_id_locale_key UNIQUE (blog_id, locale),
+    CONSTRAINT blog_translations_locale_slug_key UNIQUE (locale, slug)
+);
+
+CREATE INDEX blog_translations_search_vector_idx ON blog_translations USING GIN (search_vector);
+
+-- Best match of `q` across a blog's original and its translations, optionally
+-- limited to one locale; 0 when nothing matches.
+CREATE FUNCTION blog_search_rank(target UUID, q TEXT, lang VARCHAR) RETURNS REAL
+LANGUAGE sql STABLE AS $$
+    SELECT COALESCE(MAX(ts_rank(v.search_vector, websearch_to_tsquery(blog_search_config(v.locale), q))), 0)::REAL
+    FROM (
+        SELECT locale, search_vector FROM blogs WHERE id = target
+        UNION ALL
+        SELECT locale, search_vector FROM blog_translations WHERE blog_id = target
+    ) AS v
+    WHERE lang IS NULL OR v.locale = lang
+$$;
@@ -0,0 +1,6 @@
+ALTER TABLE blogs
+    DROP COLUMN IF EXISTS toc,
+    DROP COLUMN IF EXISTS excerpt_is_manual,
+    DROP COLUMN IF EXISTS excerpt,
+    DROP COLUMN IF EXISTS reading_time_minutes,
+    DROP COLUMN IF EXISTS word_count;
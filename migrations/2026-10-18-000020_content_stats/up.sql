@@ -0,0 +1,8 @@
+-- Derived from content by the server on every write. Existing blogs are
+-- filled in by a one-off backfill when the server starts.
+ALTER TABLE blogs
+    ADD COLUMN word_count INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN reading_time_minutes INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN excerpt TEXT NOT NULL DEFAULT '',
+    ADD COLUMN excerpt_is_manual BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN toc JSONB NOT NULL DEFAULT '[]';
@@ -0,0 +1,6 @@
+ALTER TABLE blogs
+    DROP COLUMN IF EXISTS noindex,
+    DROP COLUMN IF EXISTS og_image_url,
+    DROP COLUMN IF EXISTS canonical_url,
+    DROP COLUMN IF EXISTS meta_description,
+    DROP COLUMN IF EXISTS meta_title;
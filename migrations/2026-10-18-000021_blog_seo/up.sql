@@ -0,0 +1,7 @@
+-- Optional overrides; metadata falls back to the title and excerpt.
+ALTER TABLE blogs
+    ADD COLUMN meta_title VARCHAR,
+    ADD COLUMN meta_description TEXT,
+    ADD COLUMN canonical_url VARCHAR,
+    ADD COLUMN og_image_url VARCHAR,
+    ADD COLUMN noindex BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,5 @@
+DROP TRIGGER IF EXISTS users_touch_updated_at ON users;
+ALTER TABLE users DROP COLUMN IF EXISTS updated_at;
+DROP TRIGGER IF EXISTS comments_touch_updated_at ON comments;
+ALTER TABLE comments DROP COLUMN IF EXISTS updated_at;
+DROP FUNCTION IF EXISTS touch_updated_at();
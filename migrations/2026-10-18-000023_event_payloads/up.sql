@@ -0,0 +1,55 @@
+-- Event payloads are pushed to every subscriber that can read the blog, so
+-- they carry an explicit list of columns instead of the whole row: no post
+-- password hashes, and no blog body, which clients load through
+-- GET /blogs/{id} with the usual reader checks.
+CREATE OR REPLACE FUNCTION record_comment_event() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        INSERT INTO blog_events (blog_id, event_type, payload)
+        VALUES (OLD.blog_id, 'comment_deleted',
+                jsonb_build_object('id', OLD.id, 'parent_comment_id', OLD.parent_comment_id));
+    ELSE
+        INSERT INTO blog_events (blog_id, event_type, payload)
+        VALUES (NEW.blog_id,
+                CASE TG_OP WHEN 'INSERT' THEN 'comment_created' ELSE 'comment_updated' END,
+                jsonb_build_object(
+                    'id', NEW.id,
+                    'blog_id', NEW.blog_id,
+                    'user_id', NEW.user_id,
+                    'content', NEW.content,
+                    'parent_comment_id', NEW.parent_comment_id,
+                    'upvotes', NEW.upvotes,
+                    'downvotes', NEW.downvotes,
+                    'score', NEW.score,
+                    'created_at', NEW.created_at,
+                    'updated_at', NEW.updated_at));
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION record_blog_event() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        INSERT INTO blog_events (blog_id, event_type, payload)
+        VALUES (OLD.id, 'blog_deleted', jsonb_build_object('id', OLD.id, 'author_id', OLD.author_id));
+    ELSE
+        INSERT INTO blog_events (blog_id, event_type, payload)
+        VALUES (NEW.id,
+                CASE TG_OP WHEN 'INSERT' THEN 'blog_created' ELSE 'blog_updated' END,
+                jsonb_build_object(
+                    'id', NEW.id,
+                    'author_id', NEW.author_id,
+                    'publication_id', NEW.publication_id,
+                    'title', NEW.title,
+                    'excerpt', NEW.excerpt,
+                    'locale', NEW.locale,
+                    'status', NEW.status,
+                    'visibility', NEW.visibility,
+                    'published_at', NEW.published_at,
+                    'created_at', NEW.created_at,
+                    'updated_at', NEW.updated_at));
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,14 @@
+CREATE OR REPLACE FUNCTION maintain_blog_comment_count() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        UPDATE blogs SET comment_count = comment_count + 1 WHERE id = NEW.blog_id;
+    ELSE
+        UPDATE blogs SET comment_count = comment_count - 1 WHERE id = OLD.blog_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP FUNCTION is_shadow_banned(UUID);
+
+UPDATE blogs SET comment_count = (SELECT COUNT(*) FROM comments WHERE comments.blog_id = blogs.id);
@@ -0,0 +1,30 @@
+-- Comments by shadow-banned users are hidden from everyone but their author,
+-- so they are left out of blogs.comment_count as well. The status in force
+-- when a comment is added or removed decides; the counter reconciliation job
+-- evens out counts after a shadow-ban starts or ends.
+CREATE FUNCTION is_shadow_banned(target UUID) RETURNS BOOLEAN AS $$
+    SELECT EXISTS (
+        SELECT 1 FROM users
+        WHERE id = target
+          AND status = 'shadow_banned'
+          AND (status_expires_at IS NULL OR status_expires_at > NOW())
+    );
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION maintain_blog_comment_count() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        IF NOT is_shadow_banned(NEW.user_id) THEN
+            UPDATE blogs SET comment_count = comment_count + 1 WHERE id = NEW.blog_id;
+        END IF;
+    ELSIF NOT is_shadow_banned(OLD.user_id) THEN
+        UPDATE blogs SET comment_count = comment_count - 1 WHERE id = OLD.blog_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+UPDATE blogs SET comment_count = (
+    SELECT COUNT(*) FROM comments
+    WHERE comments.blog_id = blogs.id AND NOT is_shadow_banned(comments.user_id)
+);
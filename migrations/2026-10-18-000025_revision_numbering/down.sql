@@ -0,0 +1,16 @@
+CREATE OR REPLACE FUNCTION record_blog_revision() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'UPDATE' AND NEW.title = OLD.title AND NEW.content = OLD.content THEN
+        RETURN NULL;
+    END IF;
+
+    INSERT INTO blog_revisions (blog_id, revision_number, title, content)
+    VALUES (
+        NEW.id,
+        COALESCE((SELECT MAX(revision_number) FROM blog_revisions WHERE blog_id = NEW.id), 0) + 1,
+        NEW.title,
+        NEW.content);
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,21 @@
+-- Numbers revisions while holding the blog row, so two saves of the same blog
+-- never compute the same MAX(revision_number) + 1. The
+-- (blog_id, revision_number) unique constraint still backs this up.
+CREATE OR REPLACE FUNCTION record_blog_revision() RETURNS TRIGGER AS $$
+DECLARE
+    next_number INTEGER;
+BEGIN
+    IF TG_OP = 'UPDATE' AND NEW.title = OLD.title AND NEW.content = OLD.content THEN
+        RETURN NULL;
+    END IF;
+
+    PERFORM 1 FROM blogs WHERE id = NEW.id FOR UPDATE;
+    SELECT COALESCE(MAX(revision_number), 0) + 1 INTO next_number
+    FROM blog_revisions WHERE blog_id = NEW.id;
+
+    INSERT INTO blog_revisions (blog_id, revision_number, title, content)
+    VALUES (NEW.id, next_number, NEW.title, NEW.content);
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
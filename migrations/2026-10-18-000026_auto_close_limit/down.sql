@@ -0,0 +1,3 @@
+ALTER TABLE blogs
+    DROP CONSTRAINT blogs_comment_auto_close_days_check,
+    ADD CONSTRAINT blogs_comment_auto_close_days_check CHECK (comment_auto_close_days > 0);
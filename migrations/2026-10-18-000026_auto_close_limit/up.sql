@@ -0,0 +1,6 @@
+-- Longer periods overflow date arithmetic; about ten years is plenty.
+UPDATE blogs SET comment_auto_close_days = 3650 WHERE comment_auto_close_days > 3650;
+
+ALTER TABLE blogs
+    DROP CONSTRAINT blogs_comment_auto_close_days_check,
+    ADD CONSTRAINT blogs_comment_auto_close_days_check CHECK (comment_auto_close_days BETWEEN 1 AND 3650);
@@ -0,0 +1,9 @@
+DROP TRIGGER series_blogs_touch_blogs ON series_blogs;
+DROP FUNCTION touch_series_blogs();
+DROP TRIGGER blog_translations_touch_blog ON blog_translations;
+DROP TRIGGER blog_authors_touch_blog ON blog_authors;
+DROP TRIGGER reactions_touch_blog ON reactions;
+DROP FUNCTION touch_owning_blog();
+DROP TRIGGER blogs_touch_changed_at ON blogs;
+DROP FUNCTION touch_blog_changed_at();
+ALTER TABLE blogs DROP COLUMN changed_at;
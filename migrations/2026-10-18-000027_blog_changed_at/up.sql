@@ -0,0 +1,62 @@
+-- Last-Modified for blogs. `updated_at` marks edits to the post and drives
+-- `blog_updated` events, so `changed_at` follows everything else shown with
+-- a blog too: counters, settings, reactions, credits, series membership and
+-- translations. Views are left out; they change on every read.
+ALTER TABLE blogs ADD COLUMN changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+UPDATE blogs SET changed_at = updated_at;
+
+-- `search_vector` is generated after BEFORE triggers run, so it is compared
+-- through the columns it is generated from instead.
+CREATE FUNCTION touch_blog_changed_at() RETURNS TRIGGER AS $$
+BEGIN
+    IF (to_jsonb(NEW) - ARRAY['view_count', 'changed_at', 'search_vector'])
+        IS DISTINCT FROM (to_jsonb(OLD) - ARRAY['view_count', 'changed_at', 'search_vector']) THEN
+        NEW.changed_at = NOW();
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER blogs_touch_changed_at
+    BEFORE UPDATE ON blogs
+    FOR EACH ROW EXECUTE FUNCTION touch_blog_changed_at();
+
+-- Rows of other tables shown with a blog; TG_ARGV[0] names the blog column.
+CREATE FUNCTION touch_owning_blog() RETURNS TRIGGER AS $$
+DECLARE
+    changed JSONB := to_jsonb(COALESCE(NEW, OLD));
+BEGIN
+    IF TG_TABLE_NAME = 'reactions' AND changed ->> 'target_type' <> 'blog' THEN
+        RETURN NULL;
+    END IF;
+    UPDATE blogs SET changed_at = NOW() WHERE id = (changed ->> TG_ARGV[0])::UUID;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER reactions_touch_blog
+    AFTER INSERT OR DELETE ON reactions
+    FOR EACH ROW EXECUTE FUNCTION touch_owning_blog('target_id');
+
+CREATE TRIGGER blog_authors_touch_blog
+    AFTER INSERT OR UPDATE OR DELETE ON blog_authors
+    FOR EACH ROW EXECUTE FUNCTION touch_owning_blog('blog_id');
+
+CREATE TRIGGER blog_translations_touch_blog
+    AFTER INSERT OR UPDATE OR DELETE ON blog_translations
+    FOR EACH ROW EXECUTE FUNCTION touch_owning_blog('blog_id');
+
+-- Every part of a series shows its neighbours, so joining, leaving or
+-- reordering touches them all.
+CREATE FUNCTION touch_series_blogs() RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE blogs SET changed_at = NOW()
+    WHERE id = COALESCE(NEW.blog_id, OLD.blog_id)
+        OR id IN (SELECT blog_id FROM series_blogs WHERE series_id IN (NEW.series_id, OLD.series_id));
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER series_blogs_touch_blogs
+    AFTER INSERT OR UPDATE OR DELETE ON series_blogs
+    FOR EACH ROW EXECUTE FUNCTION touch_series_blogs();
@@ -0,0 +1,2 @@
+DROP TRIGGER reactions_touch_comment ON reactions;
+DROP FUNCTION touch_reacted_comment();
@@ -0,0 +1,16 @@
+-- Reactions are shown with a comment, so like votes they count as a change
+-- to it for Last-Modified.
+CREATE FUNCTION touch_reacted_comment() RETURNS TRIGGER AS $$
+DECLARE
+    changed reactions%ROWTYPE := COALESCE(NEW, OLD);
+BEGIN
+    IF changed.target_type = 'comment' THEN
+        UPDATE comments SET updated_at = NOW() WHERE id = changed.target_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER reactions_touch_comment
+    AFTER INSERT OR DELETE ON reactions
+    FOR EACH ROW EXECUTE FUNCTION touch_reacted_comment();
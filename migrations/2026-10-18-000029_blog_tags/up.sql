@@ -0,0 +1,14 @@
+-- Tags are lowercase slugs such as `rust` or `web-dev`; a tag exists for as
+-- long as some blog carries it.
+CREATE TABLE blog_tags (
+    blog_id UUID NOT NULL REFERENCES blogs (id) ON DELETE CASCADE,
+    tag VARCHAR(50) NOT NULL CHECK (tag ~ '^[a-z0-9]+(-[a-z0-9]+)*$'),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (blog_id, tag)
+);
+
+CREATE INDEX blog_tags_tag_idx ON blog_tags (tag, blog_id);
+
+CREATE TRIGGER blog_tags_touch_blog
+    AFTER INSERT OR DELETE ON blog_tags
+    FOR EACH ROW EXECUTE FUNCTION touch_owning_blog('blog_id');
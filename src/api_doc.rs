@@ -3,27 +3,168 @@ use utoipa::OpenApi;
 #[derive(OpenApi)]
 #[openapi(
     paths(
+        crate::routes::login_handler,
+        crate::ws::websocket_handler,
         crate::routes::create_user_handler,
         crate::routes::get_user_by_id,
+        crate::routes::list_blogs_handler,
         crate::routes::create_blog_handler,
         crate::routes::get_blog_by_id,
         crate::routes::update_blog_by_id,
         crate::routes::delete_blog_by_id,
+        crate::routes::blog_events_stream,
+        crate::routes::like_blog_handler,
+        crate::routes::unlike_blog_handler,
+        crate::routes::list_blog_comments_handler,
+        crate::routes::update_comment_settings_handler,
+        crate::routes::update_visibility_handler,
+        crate::routes::list_blog_shares_handler,
+        crate::routes::share_blog_handler,
+        crate::routes::unshare_blog_handler,
+        crate::routes::publish_blog_handler,
+        crate::routes::submit_blog_handler,
+        crate::routes::withdraw_blog_handler,
+        crate::routes::request_changes_handler,
+        crate::routes::approve_blog_handler,
+        crate::routes::unschedule_blog_handler,
+        crate::routes::assign_reviewer_handler,
+        crate::routes::list_blog_transitions_handler,
+        crate::routes::review_queue_handler,
+        crate::routes::set_blog_publication_handler,
+        crate::routes::create_publication_handler,
+        crate::routes::get_publication_handler,
+        crate::routes::update_publication_handler,
+        crate::routes::delete_publication_handler,
+        crate::routes::list_publication_blogs_handler,
+        crate::routes::list_publication_members_handler,
+        crate::routes::set_publication_member_handler,
+        crate::routes::remove_publication_member_handler,
+        crate::routes::list_blog_authors_handler,
+        crate::routes::reorder_blog_authors_handler,
+        crate::routes::remove_blog_author_handler,
+        crate::routes::list_blog_invitations_handler,
+        crate::routes::invite_author_handler,
+        crate::routes::revoke_invitation_handler,
+        crate::routes::list_my_invitations_handler,
+        crate::routes::accept_invitation_handler,
+        crate::routes::decline_invitation_handler,
+        crate::routes::transfer_blog_handler,
+        crate::routes::transfer_user_blogs_handler,
+        crate::routes::search_blogs_handler,
+        crate::routes::set_blog_excerpt_handler,
+        crate::routes::set_blog_seo_handler,
+        crate::routes::set_blog_tags_handler,
+        crate::routes::list_tag_blogs_handler,
+        crate::routes::get_blog_meta_handler,
+        crate::routes::robots_txt_handler,
+        crate::routes::sitemap_handler,
+        crate::routes::blog_sitemap_handler,
+        crate::routes::author_sitemap_handler,
+        crate::routes::tag_sitemap_handler,
+        crate::routes::rss_feed_handler,
+        crate::routes::atom_feed_handler,
+        crate::routes::json_feed_handler,
+        crate::routes::user_rss_feed_handler,
+        crate::routes::user_atom_feed_handler,
+        crate::routes::user_json_feed_handler,
+        crate::routes::tag_rss_feed_handler,
+        crate::routes::tag_atom_feed_handler,
+        crate::routes::tag_json_feed_handler,
+        crate::routes::list_blog_translations_handler,
+        crate::routes::upsert_blog_translation_handler,
+        crate::routes::delete_blog_translation_handler,
+        crate::routes::list_series_handler,
+        crate::routes::create_series_handler,
+        crate::routes::get_series_handler,
+        crate::routes::update_series_handler,
+        crate::routes::delete_series_handler,
+        crate::routes::reorder_series_handler,
+        crate::routes::add_series_blog_handler,
+        crate::routes::remove_series_blog_handler,
+        crate::routes::list_notifications_handler,
+        crate::routes::mark_notification_read_handler,
+        crate::routes::mark_all_notifications_read_handler,
+        crate::routes::list_blog_revisions_handler,
+        crate::routes::create_preview_handler,
+        crate::routes::list_previews_handler,
+        crate::routes::revoke_preview_handler,
+        crate::routes::get_preview_handler,
+        crate::routes::add_blog_reaction_handler,
+        crate::routes::remove_blog_reaction_handler,
         crate::routes::create_comment_handler,
         crate::routes::get_comment_by_id,
         crate::routes::update_comment_handler,
         crate::routes::delete_comment_handler,
+        crate::routes::vote_comment_handler,
+        crate::routes::clear_comment_vote_handler,
+        crate::routes::add_comment_reaction_handler,
+        crate::routes::remove_comment_reaction_handler,
+        crate::routes::list_reaction_kinds,
         crate::routes::create_like_handler,
-        crate::routes::get_like_by_id
+        crate::routes::get_like_by_id,
+        crate::routes::delete_like_handler,
+        crate::routes::list_my_reading_lists_handler,
+        crate::routes::list_user_reading_lists_handler,
+        crate::routes::create_reading_list_handler,
+        crate::routes::get_reading_list_handler,
+        crate::routes::update_reading_list_handler,
+        crate::routes::delete_reading_list_handler,
+        crate::routes::add_reading_list_item_handler,
+        crate::routes::remove_reading_list_item_handler,
+        crate::routes::reorder_reading_list_handler,
+        crate::routes::follow_user_handler,
+        crate::routes::unfollow_user_handler,
+        crate::routes::list_followers_handler,
+        crate::routes::list_following_handler,
+        crate::routes::feed_handler,
+        crate::routes::block_user_handler,
+        crate::routes::unblock_user_handler,
+        crate::routes::mute_user_handler,
+        crate::routes::unmute_user_handler,
+        crate::routes::list_blocks_handler,
+        crate::routes::list_mutes_handler,
+        crate::routes::create_report_handler,
+        crate::routes::list_reports_handler,
+        crate::routes::get_report_handler,
+        crate::routes::assign_report_handler,
+        crate::routes::resolve_report_handler,
+        crate::routes::set_user_role_handler,
+        crate::routes::set_account_status_handler
     ),
     components(
-        schemas(crate::models::User, crate::models::Blog, crate::models::Comment, crate::models::Like)
+        schemas(crate::models::User, crate::models::Blog, crate::models::BlogResponse, crate::models::BlogSort, crate::models::BlogStatus, crate::models::BlogRevision, crate::models::BlogTransition, crate::models::SubmitForReviewRequest, crate::models::ReviewCommentRequest, crate::models::ApproveRequest, crate::models::AssignReviewerRequest, crate::models::Publication, crate::models::PublicationRequest, crate::models::PublicationRole, crate::models::PublicationMember, crate::models::MemberRoleRequest, crate::models::BlogPublicationRequest, crate::models::AuthorRole, crate::models::BlogAuthor, crate::models::AuthorOrderRequest, crate::models::InvitationStatus, crate::models::AuthorInvitation, crate::models::InvitationRequest, crate::models::NotificationKind, crate::models::Notification, crate::models::NotificationPage, crate::models::TransferRequest, crate::models::Series, crate::models::SeriesRequest, crate::models::SeriesDetail, crate::models::SeriesOrderRequest, crate::models::SeriesLink, crate::models::SeriesNav, crate::models::Locale, crate::models::BlogTranslation, crate::models::TranslationRequest, crate::models::TocEntry, crate::models::ExcerptRequest, crate::models::SeoRequest, crate::models::TagsRequest, crate::models::BlogMeta, crate::models::OpenGraph, crate::models::TwitterCard, crate::models::FeedContent, crate::models::PreviewToken, crate::models::PreviewTokenRequest, crate::models::PreviewLink, crate::models::BlogPreview, crate::models::BlogVisibility, crate::models::VisibilityRequest, crate::models::CommentMode, crate::models::CommentSettingsRequest, crate::models::Comment, crate::models::CommentResponse, crate::models::CommentThread, crate::models::CommentSort, crate::models::VoteRequest, crate::models::Like,
+            crate::models::Reaction, crate::models::ReactionTarget, crate::models::ReactionSummary, crate::models::BlogEvent,
+            crate::models::LoginRequest, crate::models::TokenResponse,
+            crate::models::ReadingList, crate::models::ReadingListItem, crate::models::ReadingListRequest, crate::models::ReadingListItemRequest,
+            crate::models::ReorderRequest, crate::models::ReadingListEntry, crate::models::ReadingListDetail,
+            crate::models::Follow, crate::models::UserSummary, crate::models::FollowEntry, crate::models::FollowPage, crate::models::FeedPage,
+            crate::models::UserBlock, crate::models::UserMute,
+            crate::models::Role, crate::models::RoleRequest, crate::models::AccountStatus, crate::models::AccountStatusRequest, crate::models::AdminUser, crate::models::ReportTarget, crate::models::ReportReason, crate::models::ReportStatus,
+            crate::models::ModerationAction, crate::models::Report, crate::models::ReportRequest, crate::models::AssignReportRequest,
+            crate::models::ResolveReportRequest, crate::models::AuditLogEntry, crate::models::ReportDetail,
+            crate::ws::Topic, crate::ws::TopicKind, crate::ws::ClientMessage, crate::ws::ServerMessage, crate::ws::TypingUser)
     ),
     tags(
+        (name = "auth", description = "Authentication API"),
+        (name = "realtime", description = "WebSocket gateway for live events and presence"),
         (name = "users", description = "User management API"),
         (name = "blogs", description = "Blog management API"),
         (name = "comments", description = "Comment management API"),
-        (name = "likes", description = "Like management API")
+        (name = "likes", description = "Like management API"),
+        (name = "reactions", description = "Reactions on blogs and comments"),
+        (name = "reading-lists", description = "Bookmarks organized into reading lists"),
+        (name = "follows", description = "Follow graph and personalized feed"),
+        (name = "blocks", description = "Blocking and muting other users"),
+        (name = "seo", description = "Sitemaps and robots.txt for search engines"),
+        (name = "feeds", description = "RSS, Atom and JSON Feed syndication"),
+        (name = "series", description = "Ordered series of blogs"),
+        (name = "co-authors", description = "Co-authorship of blogs and invitations"),
+        (name = "notifications", description = "Notifications for the authenticated user"),
+        (name = "publications", description = "Team publications and their members"),
+        (name = "reviews", description = "Editorial review workflow"),
+        (name = "previews", description = "Shareable preview links for drafts"),
+        (name = "moderation", description = "Abuse reports and the moderation queue"),
+        (name = "admin", description = "User administration")
     )
 )]
 pub struct ApiDoc;
\ No newline at end of file
@@ -0,0 +1,161 @@
+use std::env;
+use std::future::{ready, Ready};
+
+use actix_web::dev::Payload;
+use actix_web::{web, FromRequest, HttpRequest};
+use chrono::{Duration, Utc};
+use futures_util::future::LocalBoxFuture;
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::error_handler::AppError;
+use crate::models::{AccountStatus, PreviewToken, User};
+use crate::orm::get_user;
+
+const TOKEN_TTL_HOURS: i64 = 24;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: Uuid,
+    pub exp: i64,
+}
+
+#[derive(Deserialize)]
+struct TokenQuery {
+    access_token: String,
+}
+
+fn jwt_secret() -> String {
+    env::var("JWT_SECRET").expect("JWT_SECRET must be set")
+}
+
+pub fn issue_token(user_id: Uuid) -> Result<String, jsonwebtoken::errors::Error> {
+    let claims = Claims {
+        sub: user_id,
+        exp: (Utc::now() + Duration::hours(TOKEN_TTL_HOURS)).timestamp(),
+    };
+
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(jwt_secret().as_bytes()))
+}
+
+pub fn verify_token(token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+    decode::<Claims>(token, &DecodingKey::from_secret(jwt_secret().as_bytes()), &Validation::default())
+        .map(|data| data.claims)
+}
+
+/// Claims of a draft preview link; `sub` is the `blog_preview_tokens` row.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PreviewClaims {
+    pub sub: Uuid,
+    pub blog_id: Uuid,
+    pub exp: i64,
+}
+
+/// Preview links are signed with a key derived from the JWT secret so they
+/// can never be used as access tokens, or the other way round.
+fn preview_secret() -> String {
+    format!("{}:preview", jwt_secret())
+}
+
+pub fn issue_preview_token(preview: &PreviewToken) -> Result<String, jsonwebtoken::errors::Error> {
+    let claims = PreviewClaims {
+        sub: preview.id,
+        blog_id: preview.blog_id,
+        exp: preview.expires_at.timestamp(),
+    };
+
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(preview_secret().as_bytes()))
+}
+
+pub fn verify_preview_token(token: &str) -> Result<PreviewClaims, jsonwebtoken::errors::Error> {
+    decode::<PreviewClaims>(token, &DecodingKey::from_secret(preview_secret().as_bytes()), &Validation::default())
+        .map(|data| data.claims)
+}
+
+/// Reads the token from `Authorization: Bearer`, falling back to an
+/// `access_token` query parameter since browsers cannot set headers on
+/// WebSocket and EventSource connections.
+fn request_token(req: &HttpRequest) -> Option<String> {
+    let header = req.headers()
+        .get("Authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::to_string);
+
+    header.or_else(|| {
+        web::Query::<TokenQuery>::from_query(req.query_string())
+            .ok()
+            .map(|query| query.into_inner().access_token)
+    })
+}
+
+/// The user identified by a valid access token on the request.
+#[derive(Debug, Clone, Copy)]
+pub struct AuthUser {
+    pub id: Uuid,
+}
+
+impl FromRequest for AuthUser {
+    type Error = AppError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let user = request_token(req)
+            .and_then(|token| verify_token(&token).ok())
+            .map(|claims| AuthUser { id: claims.sub })
+            .ok_or(AppError::Unauthorized);
+
+        ready(user)
+    }
+}
+
+/// Rejects suspended and banned accounts. Shadow-banned accounts pass so the
+/// restriction stays invisible to them.
+pub fn check_account_status(user: &User) -> Result<(), AppError> {
+    match user.account_status() {
+        AccountStatus::Suspended => Err(AppError::AccountRestricted(match user.status_expires_at {
+            Some(until) => format!("Account suspended until {}", until.to_rfc3339()),
+            None => "Account suspended".to_string(),
+        })),
+        AccountStatus::Banned => Err(AppError::AccountRestricted("Account banned".to_string())),
+        AccountStatus::Active | AccountStatus::ShadowBanned => Ok(()),
+    }
+}
+
+/// An authenticated user whose account may currently write. Every handler
+/// that changes data takes this instead of `AuthUser`.
+#[derive(Debug, Clone, Copy)]
+pub struct ActiveUser {
+    pub id: Uuid,
+}
+
+impl FromRequest for ActiveUser {
+    type Error = AppError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let auth = AuthUser::from_request(req, payload).into_inner();
+        let pool = req.app_data::<web::Data<DbPool>>().cloned();
+
+        Box::pin(async move {
+            let auth = auth?;
+            let pool = pool.ok_or(AppError::InternalServerError)?;
+            let user = web::block(move || {
+                let mut conn = pool.get().expect("couldn't get db connection from pool");
+                get_user(&mut conn, auth.id)
+            })
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .map_err(|err| match err {
+                // The token outlived its account.
+                diesel::result::Error::NotFound => AppError::Unauthorized,
+                err => AppError::from(err),
+            })?;
+
+            check_account_status(&user)?;
+            Ok(ActiveUser { id: user.id })
+        })
+    }
+}
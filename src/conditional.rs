@@ -0,0 +1,186 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use actix_web::http::header::{EntityTag, ETag, HttpDate, IfModifiedSince, IfNoneMatch, LastModified};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, HttpResponseBuilder};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::api_response::ApiResponse;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Cache validators for a GET response, so clients can revalidate with
+/// `If-None-Match` or `If-Modified-Since` and get a 304 instead of the body.
+pub struct Validators {
+    pub etag: EntityTag,
+    /// Left out when the body can change without the timestamp moving,
+    /// e.g. when it carries counters
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+impl Validators {
+    /// Strong validators for `body`: the tag changes whenever a byte of it does.
+    /// The tag is a 64-bit FNV-1a hash, so it stays the same across builds and restarts.
+    pub fn for_body(body: &[u8], last_modified: Option<DateTime<Utc>>) -> Self {
+        let hash = body
+            .iter()
+            .fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME));
+
+        Self {
+            etag: EntityTag::new_strong(format!("{:016x}", hash)),
+            last_modified,
+        }
+    }
+
+    /// Strong validators for the JSON form of `data`; see `for_body`.
+    pub fn for_json<T: Serialize>(data: &T, last_modified: Option<DateTime<Utc>>) -> Self {
+        Self::for_body(&serde_json::to_vec(data).unwrap_or_default(), last_modified)
+    }
+
+    /// Whether the client's cached copy is still current. `If-None-Match`
+    /// takes precedence; `If-Modified-Since` is only consulted without it.
+    pub fn is_fresh(&self, req: &HttpRequest) -> bool {
+        if let Some(if_none_match) = req.get_header::<IfNoneMatch>() {
+            return match if_none_match {
+                IfNoneMatch::Any => true,
+                IfNoneMatch::Items(tags) => tags.iter().any(|tag| tag.weak_eq(&self.etag)),
+            };
+        }
+
+        match (req.get_header::<IfModifiedSince>(), self.http_last_modified()) {
+            (Some(IfModifiedSince(since)), Some(last_modified)) => last_modified <= SystemTime::from(since),
+            _ => false,
+        }
+    }
+
+    /// HTTP dates only carry whole seconds.
+    fn http_last_modified(&self) -> Option<SystemTime> {
+        self.last_modified
+            .map(|last_modified| UNIX_EPOCH + Duration::from_secs(last_modified.timestamp().max(0) as u64))
+    }
+
+    pub fn apply<'a>(&self, builder: &'a mut HttpResponseBuilder) -> &'a mut HttpResponseBuilder {
+        builder.insert_header(ETag(self.etag.clone()));
+        if let Some(last_modified) = self.http_last_modified() {
+            builder.insert_header(LastModified(HttpDate::from(last_modified)));
+        }
+        builder
+    }
+
+    /// `304 Not Modified` if the client's copy is current, otherwise `200 OK`
+    /// with `body`. Both carry the validators.
+    pub fn respond(&self, req: &HttpRequest, content_type: &str, body: Vec<u8>) -> HttpResponse {
+        if self.is_fresh(req) {
+            return self.apply(&mut HttpResponse::NotModified()).finish();
+        }
+
+        self.apply(&mut HttpResponse::Ok())
+            .content_type(content_type)
+            .body(body)
+    }
+
+    /// Like `respond`, for handlers answering with an `ApiResponse`.
+    pub fn respond_json<T: Serialize>(&self, req: &HttpRequest, data: T) -> HttpResponse {
+        if self.is_fresh(req) {
+            return self.apply(&mut HttpResponse::NotModified()).finish();
+        }
+
+        self.apply(&mut HttpResponse::Ok()).json(ApiResponse::success(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn modified_at() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 18, 12, 30, 15).unwrap() + chrono::Duration::milliseconds(750)
+    }
+
+    fn validators() -> Validators {
+        Validators::for_body(b"hello", Some(modified_at()))
+    }
+
+    fn fresh(headers: &[(&str, &str)]) -> bool {
+        let req = headers
+            .iter()
+            .fold(TestRequest::get(), |req, header| req.insert_header(*header))
+            .to_http_request();
+        validators().is_fresh(&req)
+    }
+
+    #[test]
+    fn etag_is_strong_and_follows_the_body() {
+        let etag = validators().etag;
+        assert!(!etag.weak);
+        assert_eq!(etag, Validators::for_body(b"hello", None).etag);
+        assert_ne!(etag, Validators::for_body(b"hello!", None).etag);
+    }
+
+    #[test]
+    fn if_none_match_compares_weakly() {
+        let tag = validators().etag.tag().to_string();
+        assert!(fresh(&[("If-None-Match", &format!("\"{}\"", tag))]));
+        assert!(fresh(&[("If-None-Match", &format!("W/\"{}\"", tag))]));
+        assert!(fresh(&[("If-None-Match", &format!("\"other\", W/\"{}\"", tag))]));
+        assert!(!fresh(&[("If-None-Match", "\"other\"")]));
+    }
+
+    #[test]
+    fn if_none_match_any_is_fresh() {
+        assert!(fresh(&[("If-None-Match", "*")]));
+    }
+
+    #[test]
+    fn if_none_match_takes_precedence_over_if_modified_since() {
+        let later = "Sun, 18 Oct 2026 13:00:00 GMT";
+        assert!(!fresh(&[("If-None-Match", "\"other\""), ("If-Modified-Since", later)]));
+
+        let tag = format!("\"{}\"", validators().etag.tag());
+        let earlier = "Sun, 18 Oct 2026 12:00:00 GMT";
+        assert!(fresh(&[("If-None-Match", &tag), ("If-Modified-Since", earlier)]));
+    }
+
+    #[test]
+    fn if_modified_since_compares_whole_seconds() {
+        // Last modified at 12:30:15.750; the header can only say 12:30:15.
+        assert!(fresh(&[("If-Modified-Since", "Sun, 18 Oct 2026 12:30:15 GMT")]));
+        assert!(fresh(&[("If-Modified-Since", "Sun, 18 Oct 2026 12:30:16 GMT")]));
+        assert!(!fresh(&[("If-Modified-Since", "Sun, 18 Oct 2026 12:30:14 GMT")]));
+    }
+
+    #[test]
+    fn if_modified_since_is_ignored_without_last_modified() {
+        let req = TestRequest::get()
+            .insert_header(("If-Modified-Since", "Sun, 18 Oct 2026 13:00:00 GMT"))
+            .to_http_request();
+        assert!(!Validators::for_body(b"hello", None).is_fresh(&req));
+    }
+
+    #[test]
+    fn no_conditional_headers_is_not_fresh() {
+        assert!(!fresh(&[]));
+    }
+
+    #[test]
+    fn responses_carry_the_validators() {
+        let validators = validators();
+        let req = TestRequest::get().to_http_request();
+        let response = validators.respond(&req, "text/plain", b"hello".to_vec());
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get("Last-Modified").unwrap(), "Sun, 18 Oct 2026 12:30:15 GMT");
+        let etag = response.headers().get("ETag").unwrap().to_str().unwrap().to_string();
+        assert_eq!(etag, format!("\"{}\"", validators.etag.tag()));
+
+        let req = TestRequest::get().insert_header(("If-None-Match", etag.as_str())).to_http_request();
+        let response = validators.respond(&req, "text/plain", b"hello".to_vec());
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get("ETag").unwrap(), etag.as_str());
+        assert!(response.headers().contains_key("Last-Modified"));
+    }
+}
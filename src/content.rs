@@ -0,0 +1,261 @@
+use crate::models::TocEntry;
+
+/// Reading speed behind `reading_time_minutes`.
+const WORDS_PER_MINUTE: usize = 200;
+/// Longest derived excerpt, in characters, before the trailing ellipsis.
+const EXCERPT_CHARS: usize = 280;
+
+/// What the server derives from a blog's Markdown content on every write.
+pub struct ContentStats {
+    pub word_count: i32,
+    pub reading_time_minutes: i32,
+    pub excerpt: String,
+    pub toc: Vec<TocEntry>,
+}
+
+/// Derives the stats for `content`. Headings are ATX-style (`## Title`);
+/// fenced code blocks count towards the words but stay out of the excerpt and
+/// table of contents.
+pub fn analyze(content: &str) -> ContentStats {
+    let mut words = 0;
+    let mut prose = Vec::new();
+    let mut toc = Vec::new();
+    let mut anchors: Vec<String> = Vec::new();
+    let mut fence: Option<(char, usize)> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if let Some((marker, len)) = fence {
+            if fence_marker(trimmed).is_some_and(|(c, n)| c == marker && n >= len && trimmed[n..].trim().is_empty()) {
+                fence = None;
+            } else {
+                words += trimmed.split_whitespace().count();
+            }
+            continue;
+        }
+        if let Some(opening) = fence_marker(trimmed) {
+            fence = Some(opening);
+            continue;
+        }
+
+        if let Some((level, heading)) = heading(line) {
+            let text = plain_text(heading);
+            words += text.split_whitespace().count();
+            if !text.is_empty() {
+                let anchor = unique_anchor(&mut anchors, &text);
+                toc.push(TocEntry { level, text, anchor });
+            }
+            continue;
+        }
+
+        let text = plain_text(strip_block_marker(trimmed));
+        words += text.split_whitespace().count();
+        if !text.is_empty() {
+            prose.push(text);
+        }
+    }
+
+    ContentStats {
+        word_count: words as i32,
+        reading_time_minutes: words.div_ceil(WORDS_PER_MINUTE) as i32,
+        excerpt: truncate(&prose.join(" "), EXCERPT_CHARS),
+        toc,
+    }
+}
+
+/// The character and length of a code fence opening `line`: three or more
+/// backticks or tildes. A fence only closes on a run at least as long.
+fn fence_marker(line: &str) -> Option<(char, usize)> {
+    let marker = line.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = line.chars().take_while(|c| *c == marker).count();
+    (len >= 3).then_some((marker, len))
+}
+
+/// The level and text of an ATX heading line.
+fn heading(line: &str) -> Option<(i32, &str)> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let line = &line[indent..];
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let rest = &line[level..];
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+
+    Some((level as i32, rest.trim().trim_end_matches('#').trim_end()))
+}
+
+/// Drops a leading blockquote or list marker.
+fn strip_block_marker(line: &str) -> &str {
+    let line = line.trim_start_matches('>').trim_start();
+    for marker in ["- ", "* ", "+ "] {
+        if let Some(rest) = line.strip_prefix(marker) {
+            return rest;
+        }
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        if let Some(rest) = line[digits..].strip_prefix(". ").or_else(|| line[digits..].strip_prefix(") ")) {
+            return rest;
+        }
+    }
+    line
+}
+
+/// Inline Markdown reduced to its text: links and images keep their label,
+/// HTML tags and emphasis markers are dropped, whitespace is collapsed.
+fn plain_text(markdown: &str) -> String {
+    let mut text = String::with_capacity(markdown.len());
+    let mut chars = markdown.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '!' if chars.peek() == Some(&'[') => {}
+            '[' | ']' => {
+                if c == ']' && chars.peek() == Some(&'(') {
+                    for skipped in chars.by_ref() {
+                        if skipped == ')' {
+                            break;
+                        }
+                    }
+                }
+            }
+            '<' => {
+                for skipped in chars.by_ref() {
+                    if skipped == '>' {
+                        break;
+                    }
+                }
+            }
+            '*' | '_' | '`' | '~' => {}
+            c => text.push(c),
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// A URL fragment for the heading, made unique among the blog's headings the
+/// way GitHub does it: `setup`, `setup-1`, `setup-2`.
+fn unique_anchor(taken: &mut Vec<String>, text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if (c == ' ' || c == '-') && !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = match slug.trim_end_matches('-') {
+        "" => "section".to_string(),
+        slug => slug.to_string(),
+    };
+
+    let mut anchor = slug.clone();
+    let mut suffix = 0;
+    while taken.contains(&anchor) {
+        suffix += 1;
+        anchor = format!("{}-{}", slug, suffix);
+    }
+    taken.push(anchor.clone());
+    anchor
+}
+
+/// At most `max_chars` of `text`, cut at a word boundary and marked with an ellipsis.
+pub fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+
+    let cut: String = text.chars().take(max_chars).collect();
+    let cut = match cut.rfind(' ') {
+        Some(space) => &cut[..space],
+        None => cut.as_str(),
+    };
+    format!("{}…", cut.trim_end_matches([',', ';', ':', '.', ' ']))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anchors(stats: &ContentStats) -> Vec<&str> {
+        stats.toc.iter().map(|entry| entry.anchor.as_str()).collect()
+    }
+
+    #[test]
+    fn counts_words_and_rounds_reading_time_up() {
+        let stats = analyze("one two three\n\n- four *five*");
+        assert_eq!(stats.word_count, 5);
+        assert_eq!(stats.reading_time_minutes, 1);
+        assert_eq!(analyze("").reading_time_minutes, 0);
+        assert_eq!(analyze(&"word ".repeat(201)).reading_time_minutes, 2);
+    }
+
+    #[test]
+    fn excerpt_is_plain_prose() {
+        let stats = analyze("# Title\n\n> Read [the docs](https://example.com) and <b>**this**</b>.\n\n```\nlet x = 1;\n```\n1. done");
+        assert_eq!(stats.excerpt, "Read the docs and this. done");
+    }
+
+    #[test]
+    fn headings_build_the_toc() {
+        let stats = analyze("# Intro\n####### Not a heading\n#NoSpace\n    # Indented code\n## Setup ##\n###");
+        let toc: Vec<(i32, &str)> = stats.toc.iter().map(|entry| (entry.level, entry.text.as_str())).collect();
+        assert_eq!(toc, [(1, "Intro"), (2, "Setup")]);
+        assert_eq!(anchors(&stats), ["intro", "setup"]);
+    }
+
+    #[test]
+    fn duplicate_anchors_get_suffixes() {
+        let stats = analyze("## Setup\n## Setup\n## Setup-1\n## Setup\n## ???");
+        assert_eq!(anchors(&stats), ["setup", "setup-1", "setup-1-1", "setup-2", "section"]);
+    }
+
+    #[test]
+    fn anchors_keep_non_ascii_letters() {
+        let stats = analyze("## Über Café — Notes");
+        assert_eq!(anchors(&stats), ["über-café-notes"]);
+    }
+
+    #[test]
+    fn shorter_fence_does_not_close_a_longer_one() {
+        let stats = analyze("````\n```\n# not a heading\n```\n````\n# Heading\nafter");
+        assert_eq!(anchors(&stats), ["heading"]);
+        assert_eq!(stats.excerpt, "after");
+    }
+
+    #[test]
+    fn fence_closes_only_on_the_same_marker() {
+        let stats = analyze("~~~\n```\n## inside\n~~~~\n## outside");
+        assert_eq!(anchors(&stats), ["outside"]);
+        let stats = analyze("```rust\n```not a close\n## inside\n```\n## outside");
+        assert_eq!(anchors(&stats), ["outside"]);
+    }
+
+    #[test]
+    fn fenced_code_counts_towards_words() {
+        let stats = analyze("intro\n```\nlet x = 1;\n```");
+        assert_eq!(stats.word_count, 5);
+        assert_eq!(stats.excerpt, "intro");
+    }
+
+    #[test]
+    fn truncate_keeps_short_text() {
+        assert_eq!(truncate("short text", 10), "short text");
+    }
+
+    #[test]
+    fn truncate_cuts_at_a_word_boundary() {
+        assert_eq!(truncate("one two, three four", 12), "one two…");
+        assert_eq!(truncate("unbroken", 4), "unbr…");
+    }
+
+    #[test]
+    fn truncate_counts_characters_not_bytes() {
+        assert_eq!(truncate("ääää öööö üüüü", 11), "ääää öööö…");
+        assert_eq!(truncate("日本語のテキスト", 3), "日本語…");
+        assert_eq!(truncate("😀😀😀", 3), "😀😀😀");
+    }
+}
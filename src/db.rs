@@ -1,4 +1,5 @@
 use diesel::pg::PgConnection;
+use diesel::{Connection, ConnectionResult};
 use diesel::r2d2::{self, ConnectionManager};
 use std::env;
 
@@ -13,4 +14,11 @@ pub fn create_db_pool() -> DbPool {
     r2d2::Pool::builder()
         .build(manager)
         .expect("Failed to create pool.")
+}
+
+pub fn establish_connection() -> ConnectionResult<PgConnection> {
+    let database_url = env::var("DATABASE_URL")
+        .expect("DATABASE_URL must be set");
+
+    PgConnection::establish(&database_url)
 }
\ No newline at end of file
@@ -2,19 +2,35 @@ use actix_web::{HttpResponse, ResponseError};
 use derive_more::Display;
 use serde::Serialize;
 
+use crate::api_response::ApiResponse;
+
 #[derive(Debug, Display, Serialize)]
 pub enum AppError {
     #[display(fmt = "Internal Server Error")]
     InternalServerError,
     #[display(fmt = "Not Found")]
     NotFound,
+    #[display(fmt = "Unauthorized")]
+    Unauthorized,
+    #[display(fmt = "Forbidden")]
+    Forbidden,
+    #[display(fmt = "{}", _0)]
+    BadRequest(String),
+    /// The account is suspended or banned; the message says which and until when.
+    #[display(fmt = "{}", _0)]
+    AccountRestricted(String),
 }
 
 impl ResponseError for AppError {
     fn error_response(&self) -> HttpResponse {
+        let body = ApiResponse::<()>::error(self.to_string());
         match self {
-            AppError::InternalServerError => HttpResponse::InternalServerError().json("Internal Server Error"),
-            AppError::NotFound => HttpResponse::NotFound().json("Not Found"),
+            AppError::InternalServerError => HttpResponse::InternalServerError().json(body),
+            AppError::NotFound => HttpResponse::NotFound().json(body),
+            AppError::Unauthorized => HttpResponse::Unauthorized().json(body),
+            AppError::Forbidden => HttpResponse::Forbidden().json(body),
+            AppError::BadRequest(_) => HttpResponse::BadRequest().json(body),
+            AppError::AccountRestricted(_) => HttpResponse::Forbidden().json(body),
         }
     }
 }
@@ -26,4 +42,4 @@ impl From<diesel::result::Error> for AppError {
             _ => AppError::InternalServerError,
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,311 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use actix_web::web::{self, Bytes};
+use chrono::Utc;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use futures_util::stream::{self, Stream, StreamExt};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+
+use crate::db::{self, DbPool};
+use crate::models::{Blog, BlogEvent};
+use crate::orm::{can_read_blog, get_blog, get_blog_event, get_blog_events_after, delete_blog_events_before, is_shadow_banned, list_hidden_users};
+
+const CHANNEL: &str = "blog_events";
+const BROADCAST_CAPACITY: usize = 1024;
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const RETENTION_HOURS: i64 = 24;
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+const CLIENT_RETRY_MS: u64 = 3000;
+/// How long a subscriber's blocks, mutes and blog access are trusted before
+/// they are looked up again.
+const AUDIENCE_REFRESH: Duration = Duration::from_secs(60);
+
+/// How many missed events are loaded at a time when catching up; catch-up
+/// pages through until nothing is left.
+pub const REPLAY_PAGE_SIZE: i64 = 500;
+
+/// A blog event with what deciding who may receive it needs, loaded once
+/// when it is published instead of once per subscriber.
+#[derive(Clone)]
+pub struct LiveEvent {
+    pub event: BlogEvent,
+    /// The blog as it is now; `None` once it has been deleted
+    pub blog: Option<Arc<Blog>>,
+    /// Whether the event is about a comment by a shadow-banned user
+    pub shadow_banned_commenter: bool,
+}
+
+impl LiveEvent {
+    pub fn load(conn: &mut PgConnection, event: BlogEvent) -> Result<Self, diesel::result::Error> {
+        let blog = match get_blog(conn, event.blog_id) {
+            Ok(blog) => Some(Arc::new(blog)),
+            Err(diesel::result::Error::NotFound) => None,
+            Err(err) => return Err(err),
+        };
+        Self::with_blog(conn, event, blog)
+    }
+
+    /// Like `load`, for an event of a blog the caller already has.
+    pub fn with_blog(conn: &mut PgConnection, event: BlogEvent, blog: Option<Arc<Blog>>) -> Result<Self, diesel::result::Error> {
+        let shadow_banned_commenter = match event.comment_author() {
+            Some(commenter_id) => is_shadow_banned(conn, commenter_id)?,
+            None => false,
+        };
+        Ok(Self { event, blog, shadow_banned_commenter })
+    }
+}
+
+/// The blog fields whether someone can read it depends on.
+#[derive(PartialEq, Eq)]
+struct AccessKey {
+    status: String,
+    visibility: String,
+    password_hash: Option<String>,
+    author_id: Uuid,
+    reviewer_id: Option<Uuid>,
+}
+
+impl AccessKey {
+    fn of(blog: &Blog) -> Self {
+        Self {
+            status: blog.status.clone(),
+            visibility: blog.visibility.clone(),
+            password_hash: blog.password_hash.clone(),
+            author_id: blog.author_id,
+            reviewer_id: blog.reviewer_id,
+        }
+    }
+}
+
+/// What one SSE or WebSocket subscriber may receive. Whether they can read a
+/// blog, which checks a post password with bcrypt, is worked out once and
+/// reused until the blog's status, visibility, password or people change;
+/// their blocks and mutes are loaded once. Both are looked up again every
+/// minute, so most events are checked without touching the database.
+pub struct Audience {
+    viewer_id: Option<Uuid>,
+    password: Option<String>,
+    hidden_users: HashSet<Uuid>,
+    readable: HashMap<Uuid, (AccessKey, bool)>,
+    loaded_at: Instant,
+}
+
+impl Audience {
+    pub fn load(conn: &mut PgConnection, viewer_id: Option<Uuid>, password: Option<String>) -> Result<Self, diesel::result::Error> {
+        let hidden_users = match viewer_id {
+            Some(viewer_id) => list_hidden_users(conn, viewer_id)?.into_iter().collect(),
+            None => HashSet::new(),
+        };
+        Ok(Self { viewer_id, password, hidden_users, readable: HashMap::new(), loaded_at: Instant::now() })
+    }
+
+    /// Records that the subscriber can read `blog`, which the caller has just checked.
+    pub fn admit(&mut self, blog: &Blog) {
+        self.readable.insert(blog.id, (AccessKey::of(blog), true));
+    }
+
+    /// Whether `allows` can answer for `event` from what is already loaded.
+    fn is_current(&self, event: &LiveEvent) -> bool {
+        self.loaded_at.elapsed() < AUDIENCE_REFRESH
+            && event.blog.as_ref().is_none_or(|blog| {
+                self.readable.get(&blog.id).is_some_and(|(key, _)| *key == AccessKey::of(blog))
+            })
+    }
+
+    fn refresh(&mut self, conn: &mut PgConnection, event: &LiveEvent) -> Result<(), diesel::result::Error> {
+        if self.loaded_at.elapsed() >= AUDIENCE_REFRESH {
+            *self = Self::load(conn, self.viewer_id, self.password.clone())?;
+        }
+        if let Some(blog) = &event.blog {
+            let readable = can_read_blog(conn, blog, self.viewer_id, self.password.as_deref())?;
+            self.readable.insert(blog.id, (AccessKey::of(blog), readable));
+        }
+        Ok(())
+    }
+
+    /// Whether the subscriber can read the event's blog, and the event is not
+    /// about a comment by someone they blocked or muted or who is shadow banned.
+    /// Once the blog is gone, its last events, `blog_deleted` among them, only
+    /// reach `blog_subscriber`s, who could read it when they subscribed.
+    fn allows(&self, event: &LiveEvent, blog_subscriber: bool) -> bool {
+        let hidden_commenter = event.event.comment_author()
+            .filter(|commenter_id| self.viewer_id != Some(*commenter_id))
+            .is_some_and(|commenter_id| event.shadow_banned_commenter || self.hidden_users.contains(&commenter_id));
+        if hidden_commenter {
+            return false;
+        }
+
+        match &event.blog {
+            Some(blog) => self.readable.get(&blog.id).is_some_and(|(_, readable)| *readable),
+            None => blog_subscriber,
+        }
+    }
+
+    /// Whether the subscriber may receive `event`, looking things up first if needed.
+    pub fn check(&mut self, conn: &mut PgConnection, event: &LiveEvent, blog_subscriber: bool) -> Result<bool, diesel::result::Error> {
+        if !self.is_current(event) {
+            self.refresh(conn, event)?;
+        }
+        Ok(self.allows(event, blog_subscriber))
+    }
+}
+
+/// Runs `Audience::check` for a streaming subscriber, off the async runtime
+/// when it needs the database. Returns `None` when the lookup itself failed.
+pub async fn admits(audience: &Arc<Mutex<Audience>>, pool: &web::Data<DbPool>, event: &LiveEvent, blog_subscriber: bool) -> Option<bool> {
+    {
+        let audience = audience.lock().expect("audience lock poisoned");
+        if audience.is_current(event) {
+            return Some(audience.allows(event, blog_subscriber));
+        }
+    }
+
+    let audience = audience.clone();
+    let pool = pool.clone();
+    let event = event.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        audience.lock().expect("audience lock poisoned").check(&mut conn, &event, blog_subscriber)
+    }).await;
+
+    match result {
+        Ok(Ok(admitted)) => Some(admitted),
+        _ => None,
+    }
+}
+
+/// Fans out blog events received from Postgres to every subscriber in this process.
+#[derive(Clone)]
+pub struct EventBroker {
+    sender: broadcast::Sender<LiveEvent>,
+}
+
+impl EventBroker {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(BROADCAST_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<LiveEvent> {
+        self.sender.subscribe()
+    }
+
+    fn publish(&self, event: LiveEvent) {
+        // Sending only fails when nobody is subscribed, which is fine.
+        let _ = self.sender.send(event);
+    }
+}
+
+/// Starts a background thread that `LISTEN`s on the `blog_events` channel and
+/// forwards every notified event to the broker, reconnecting on failure.
+pub fn spawn_listener(broker: EventBroker) {
+    thread::spawn(move || {
+        let mut last_event_id = None;
+        loop {
+            if let Err(err) = listen(&broker, &mut last_event_id) {
+                log::error!("blog event listener failed: {}", err);
+            }
+            thread::sleep(RECONNECT_DELAY);
+        }
+    });
+}
+
+fn listen(broker: &EventBroker, last_event_id: &mut Option<i64>) -> Result<(), Box<dyn Error>> {
+    let mut conn = db::establish_connection()?;
+    diesel::sql_query(format!("LISTEN {}", CHANNEL)).execute(&mut conn)?;
+
+    // Catch up on whatever was published while this process was reconnecting.
+    while let Some(after) = *last_event_id {
+        let events = get_blog_events_after(&mut conn, after, REPLAY_PAGE_SIZE)?;
+        let exhausted = (events.len() as i64) < REPLAY_PAGE_SIZE;
+        for event in events {
+            *last_event_id = Some(event.id);
+            broker.publish(LiveEvent::load(&mut conn, event)?);
+        }
+        if exhausted {
+            break;
+        }
+    }
+
+    let mut last_prune: Option<Instant> = None;
+    loop {
+        let payloads = conn
+            .notifications_iter()
+            .map(|notification| notification.map(|n| n.payload))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for payload in payloads {
+            let Ok(event_id) = payload.parse::<i64>() else {
+                continue;
+            };
+            match get_blog_event(&mut conn, event_id) {
+                Ok(event) => {
+                    *last_event_id = Some(last_event_id.map_or(event.id, |id| id.max(event.id)));
+                    broker.publish(LiveEvent::load(&mut conn, event)?);
+                }
+                Err(diesel::result::Error::NotFound) => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        if last_prune.is_none_or(|at| at.elapsed() >= PRUNE_INTERVAL) {
+            delete_blog_events_before(&mut conn, Utc::now() - chrono::Duration::hours(RETENTION_HOURS))?;
+            last_prune = Some(Instant::now());
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Builds a `text/event-stream` body for one blog: the replayed `backlog`
+/// first, then live events from `receiver`, with periodic keep-alive comments.
+/// Each live event is only sent when `audience` may receive it.
+pub fn sse_stream(blog_id: Uuid, audience: Audience, pool: web::Data<DbPool>, backlog: Vec<BlogEvent>, receiver: broadcast::Receiver<LiveEvent>) -> impl Stream<Item = Result<Bytes, Infallible>> {
+    let replayed: HashSet<i64> = backlog.iter().map(|event| event.id).collect();
+    let audience = Arc::new(Mutex::new(audience));
+
+    let mut opening = vec![format!("retry: {}\n\n", CLIENT_RETRY_MS)];
+    opening.extend(backlog.iter().map(format_event));
+
+    let live = stream::unfold((receiver, replayed), move |(mut receiver, replayed)| {
+        let pool = pool.clone();
+        let audience = audience.clone();
+        async move {
+            loop {
+                match tokio::time::timeout(KEEP_ALIVE_INTERVAL, receiver.recv()).await {
+                    Err(_) => return Some((": keep-alive\n\n".to_string(), (receiver, replayed))),
+                    Ok(Ok(live)) if live.event.blog_id == blog_id && !replayed.contains(&live.event.id) => {
+                        match admits(&audience, &pool, &live, true).await {
+                            Some(true) => return Some((format_event(&live.event), (receiver, replayed))),
+                            Some(false) => continue,
+                            // The client reconnects and gets the event replayed.
+                            None => return None,
+                        }
+                    }
+                    Ok(Ok(_)) => continue,
+                    // A lagging client has missed events; closing the stream makes it
+                    // reconnect with `Last-Event-ID` and get them replayed.
+                    Ok(Err(RecvError::Lagged(_))) | Ok(Err(RecvError::Closed)) => return None,
+                }
+            }
+        }
+    });
+
+    stream::iter(opening)
+        .chain(live)
+        .map(|chunk| Ok(Bytes::from(chunk)))
+}
+
+fn format_event(event: &BlogEvent) -> String {
+    format!("id: {}\nevent: {}\ndata: {}\n\n", event.id, event.event_type, event.payload)
+}
@@ -0,0 +1,211 @@
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::models::BlogResponse;
+use crate::site;
+
+/// How many of the most recently published blogs a feed carries.
+pub const FEED_SIZE: i64 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    Rss,
+    Atom,
+    Json,
+}
+
+impl FeedFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "application/rss+xml; charset=utf-8",
+            FeedFormat::Atom => "application/atom+xml; charset=utf-8",
+            FeedFormat::Json => "application/feed+json; charset=utf-8",
+        }
+    }
+
+    fn file_name(&self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "feed.xml",
+            FeedFormat::Atom => "atom.xml",
+            FeedFormat::Json => "feed.json",
+        }
+    }
+}
+
+/// Which published blogs a feed carries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedScope {
+    Site,
+    /// Blogs the user is credited on
+    Author(Uuid),
+    /// Blogs carrying the tag
+    Tag(String),
+}
+
+/// A feed of published blogs, newest first. Content is the blog's Markdown
+/// source sent as plain text, so readers never render markup from it.
+pub struct Feed {
+    pub title: String,
+    /// Path the feed lives under, e.g. `` for the site or `/users/{id}`
+    pub path: String,
+    pub blogs: Vec<BlogResponse>,
+    /// Whole posts rather than excerpts
+    pub full_content: bool,
+}
+
+impl Feed {
+    /// When the most recently changed blog in the feed was updated.
+    pub fn updated(&self) -> DateTime<Utc> {
+        self.blogs
+            .iter()
+            .map(|response| response.blog.updated_at)
+            .max()
+            .unwrap_or(DateTime::UNIX_EPOCH)
+    }
+
+    pub fn render(&self, format: FeedFormat) -> String {
+        match format {
+            FeedFormat::Rss => self.rss(),
+            FeedFormat::Atom => self.atom(),
+            FeedFormat::Json => self.json(),
+        }
+    }
+
+    fn home_url(&self) -> String {
+        format!("{}{}", site::base_url(), self.path)
+    }
+
+    fn feed_url(&self, format: FeedFormat) -> String {
+        format!("{}{}/{}", site::base_url(), self.path, format.file_name())
+    }
+
+    fn body(&self, response: &BlogResponse) -> String {
+        let blog = &response.blog;
+        sanitize(if self.full_content { &blog.content } else { &blog.excerpt })
+    }
+
+    fn rss(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n<channel>\n");
+        xml.push_str(&format!("<title>{}</title>\n", escape(&self.title)));
+        xml.push_str(&format!("<link>{}</link>\n", escape(&self.home_url())));
+        xml.push_str(&format!("<description>{}</description>\n", escape(&self.title)));
+        xml.push_str(&format!("<atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n", escape(&self.feed_url(FeedFormat::Rss))));
+        xml.push_str(&format!("<lastBuildDate>{}</lastBuildDate>\n", self.updated().to_rfc2822()));
+
+        for response in &self.blogs {
+            let blog = &response.blog;
+            xml.push_str("<item>\n");
+            xml.push_str(&format!("<title>{}</title>\n", escape(&blog.title)));
+            xml.push_str(&format!("<link>{}</link>\n", escape(&site::blog_url(blog.id))));
+            xml.push_str(&format!("<guid isPermaLink=\"false\">urn:uuid:{}</guid>\n", blog.id));
+            xml.push_str(&format!("<pubDate>{}</pubDate>\n", published(response).to_rfc2822()));
+            for author in &response.authors {
+                xml.push_str(&format!("<dc:creator>{}</dc:creator>\n", escape(&author.user.username)));
+            }
+            for tag in &response.tags {
+                xml.push_str(&format!("<category>{}</category>\n", escape(tag)));
+            }
+            xml.push_str(&format!("<description>{}</description>\n", escape(&self.body(response))));
+            xml.push_str("</item>\n");
+        }
+
+        xml.push_str("</channel>\n</rss>\n");
+        xml
+    }
+
+    fn atom(&self) -> String {
+        let feed_url = self.feed_url(FeedFormat::Atom);
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+        xml.push_str(&format!("<title>{}</title>\n", escape(&self.title)));
+        xml.push_str(&format!("<id>{}</id>\n", escape(&feed_url)));
+        xml.push_str(&format!("<link rel=\"self\" href=\"{}\"/>\n", escape(&feed_url)));
+        xml.push_str(&format!("<link rel=\"alternate\" href=\"{}\"/>\n", escape(&self.home_url())));
+        xml.push_str(&format!("<updated>{}</updated>\n", self.updated().to_rfc3339()));
+
+        for response in &self.blogs {
+            let blog = &response.blog;
+            xml.push_str("<entry>\n");
+            xml.push_str(&format!("<title>{}</title>\n", escape(&blog.title)));
+            xml.push_str(&format!("<id>urn:uuid:{}</id>\n", blog.id));
+            xml.push_str(&format!("<link rel=\"alternate\" href=\"{}\"/>\n", escape(&site::blog_url(blog.id))));
+            xml.push_str(&format!("<published>{}</published>\n", published(response).to_rfc3339()));
+            xml.push_str(&format!("<updated>{}</updated>\n", blog.updated_at.to_rfc3339()));
+            for author in &response.authors {
+                xml.push_str(&format!("<author><name>{}</name></author>\n", escape(&author.user.username)));
+            }
+            for tag in &response.tags {
+                xml.push_str(&format!("<category term=\"{}\"/>\n", escape(tag)));
+            }
+            xml.push_str(&format!("<summary type=\"text\">{}</summary>\n", escape(&sanitize(&blog.excerpt))));
+            if self.full_content {
+                xml.push_str(&format!("<content type=\"text\">{}</content>\n", escape(&self.body(response))));
+            }
+            xml.push_str("</entry>\n");
+        }
+
+        xml.push_str("</feed>\n");
+        xml
+    }
+
+    fn json(&self) -> String {
+        let items: Vec<serde_json::Value> = self.blogs
+            .iter()
+            .map(|response| {
+                let blog = &response.blog;
+                json!({
+                    "id": blog.id,
+                    "url": site::blog_url(blog.id),
+                    "title": blog.title,
+                    "content_text": self.body(response),
+                    "summary": sanitize(&blog.excerpt),
+                    "date_published": published(response),
+                    "date_modified": blog.updated_at,
+                    "language": blog.locale,
+                    "authors": response.authors
+                        .iter()
+                        .map(|author| json!({ "name": author.user.username }))
+                        .collect::<Vec<_>>(),
+                    "tags": response.tags,
+                })
+            })
+            .collect();
+
+        json!({
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": self.title,
+            "home_page_url": self.home_url(),
+            "feed_url": self.feed_url(FeedFormat::Json),
+            "items": items,
+        })
+        .to_string()
+    }
+}
+
+fn published(response: &BlogResponse) -> DateTime<Utc> {
+    response.blog.published_at.unwrap_or(response.blog.created_at)
+}
+
+/// Drops control characters feed readers choke on; XML 1.0 cannot carry them at all.
+fn sanitize(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in sanitize(text).chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
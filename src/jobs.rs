@@ -0,0 +1,92 @@
+use std::env;
+use std::thread;
+use std::time::Duration;
+
+use crate::db::DbPool;
+use crate::orm::{backfill_content_stats, publish_due_blogs, reconcile_blog_counters, reconcile_comment_scores};
+
+const DEFAULT_RECONCILE_INTERVAL_SECS: u64 = 60 * 60;
+const DEFAULT_SCHEDULED_PUBLISH_INTERVAL_SECS: u64 = 60;
+
+fn interval_from_env(key: &str, default_secs: u64) -> Duration {
+    let secs = env::var(key)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default_secs);
+
+    Duration::from_secs(secs)
+}
+
+/// Periodically repairs drift in the denormalized blog counters and comment
+/// scores. The interval is read from `COUNTER_RECONCILE_INTERVAL_SECS`.
+pub fn spawn_counter_reconciliation(pool: DbPool) {
+    let interval = interval_from_env("COUNTER_RECONCILE_INTERVAL_SECS", DEFAULT_RECONCILE_INTERVAL_SECS);
+
+    thread::spawn(move || loop {
+        thread::sleep(interval);
+
+        let mut conn = match pool.get() {
+            Ok(conn) => conn,
+            Err(err) => {
+                log::error!("counter reconciliation could not get a connection: {}", err);
+                continue;
+            }
+        };
+
+        match reconcile_blog_counters(&mut conn) {
+            Ok(0) => {}
+            Ok(fixed) => log::warn!("counter reconciliation fixed drifted counters on {} blogs", fixed),
+            Err(err) => log::error!("counter reconciliation failed: {}", err),
+        }
+
+        match reconcile_comment_scores(&mut conn) {
+            Ok(0) => {}
+            Ok(fixed) => log::warn!("counter reconciliation fixed drifted scores on {} comments", fixed),
+            Err(err) => log::error!("comment score reconciliation failed: {}", err),
+        }
+    });
+}
+
+/// Publishes approved blogs once their scheduled time has passed. The interval
+/// is read from `SCHEDULED_PUBLISH_INTERVAL_SECS`.
+pub fn spawn_scheduled_publishing(pool: DbPool) {
+    let interval = interval_from_env("SCHEDULED_PUBLISH_INTERVAL_SECS", DEFAULT_SCHEDULED_PUBLISH_INTERVAL_SECS);
+
+    thread::spawn(move || loop {
+        thread::sleep(interval);
+
+        let mut conn = match pool.get() {
+            Ok(conn) => conn,
+            Err(err) => {
+                log::error!("scheduled publishing could not get a connection: {}", err);
+                continue;
+            }
+        };
+
+        match publish_due_blogs(&mut conn) {
+            Ok(0) => {}
+            Ok(published) => log::info!("scheduled publishing published {} blogs", published),
+            Err(err) => log::error!("scheduled publishing failed: {}", err),
+        }
+    });
+}
+
+/// Derives word counts, excerpts and tables of contents once for blogs written
+/// before the server computed them.
+pub fn spawn_content_stats_backfill(pool: DbPool) {
+    thread::spawn(move || {
+        let mut conn = match pool.get() {
+            Ok(conn) => conn,
+            Err(err) => {
+                log::error!("content stats backfill could not get a connection: {}", err);
+                return;
+            }
+        };
+
+        match backfill_content_stats(&mut conn) {
+            Ok(0) => {}
+            Ok(filled) => log::info!("content stats backfill filled in {} blogs", filled),
+            Err(err) => log::error!("content stats backfill failed: {}", err),
+        }
+    });
+}
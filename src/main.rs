@@ -9,6 +9,22 @@ mod routes;
 mod api_doc;
 mod db;
 mod api_response;
+mod events;
+mod auth;
+mod error_handler;
+mod ws;
+mod jobs;
+mod reaction_kinds;
+mod ranking;
+mod pagination;
+mod workflow;
+mod content;
+mod site;
+mod seo;
+mod conditional;
+mod feeds;
+mod sitemap;
+mod tags;
 
 use api_doc::ApiDoc;
 // use db::DbPool;
@@ -16,13 +32,23 @@ use api_doc::ApiDoc;
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     dotenv::dotenv().ok();
+    env_logger::init();
     let pool = db::create_db_pool();
 
+    let broker = events::EventBroker::new();
+    events::spawn_listener(broker.clone());
+    let presence = web::Data::new(ws::Presence::new());
+    jobs::spawn_counter_reconciliation(pool.clone());
+    jobs::spawn_scheduled_publishing(pool.clone());
+    jobs::spawn_content_stats_backfill(pool.clone());
+
     let openapi = ApiDoc::openapi();
 
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(pool.clone()))
+            .app_data(web::Data::new(broker.clone()))
+            .app_data(presence.clone())
             .service(
                 SwaggerUi::new("/swagger-ui/{_:.*}")
                     .url("/api-docs/openapi.json", openapi.clone())
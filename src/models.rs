@@ -1,7 +1,9 @@
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use diesel::prelude::*;
 
 #[derive(Debug, Serialize, Deserialize, Queryable, Insertable, ToSchema)]
@@ -12,6 +14,34 @@ pub struct User {
     pub email: String,
     pub password_hash: String,
     pub created_at: DateTime<Utc>,
+    /// One of `user`, `moderator` or `admin`; ignored on create
+    #[serde(default)]
+    pub role: String,
+    /// One of `active`, `suspended`, `banned` or `shadow_banned`; ignored on create and
+    /// only shown to admins, see `AdminUser`
+    #[serde(default, skip_serializing)]
+    pub status: String,
+    #[serde(default, skip_serializing)]
+    pub status_reason: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub status_expires_at: Option<DateTime<Utc>>,
+    /// Last change to the account; ignored on create and update
+    #[serde(default)]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl User {
+    /// The account status in force now, treating an expired status as active.
+    pub fn account_status(&self) -> AccountStatus {
+        if self.status_expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+            return AccountStatus::Active;
+        }
+        AccountStatus::parse(&self.status).unwrap_or(AccountStatus::Active)
+    }
+
+    pub fn role(&self) -> Role {
+        Role::parse(&self.role).unwrap_or(Role::User)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Queryable, Insertable, ToSchema)]
@@ -23,6 +53,383 @@ pub struct Blog {
     pub author_id: Uuid,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub like_count: i64,
+    #[serde(default)]
+    pub comment_count: i64,
+    #[serde(default)]
+    pub view_count: i64,
+    /// Who may comment; see `CommentMode`. Ignored on create and update
+    #[serde(default)]
+    pub comment_mode: String,
+    /// Comments lock this many days after the blog was published
+    #[serde(default)]
+    pub comment_auto_close_days: Option<i32>,
+    /// Who may read the blog; see `BlogVisibility`. Ignored on create and update
+    #[serde(default)]
+    pub visibility: String,
+    #[serde(skip)]
+    pub password_hash: Option<String>,
+    /// Workflow state; see `BlogStatus`. A blog can be created as a draft
+    #[serde(default)]
+    pub status: String,
+    /// When the blog went live, or is scheduled to
+    #[serde(default)]
+    pub published_at: Option<DateTime<Utc>>,
+    /// Editor assigned to review the blog. Ignored on create and update
+    #[serde(default)]
+    pub reviewer_id: Option<Uuid>,
+    /// Publication the blog belongs to; set on create, ignored on update
+    #[serde(default)]
+    pub publication_id: Option<Uuid>,
+    /// Language of `title` and `content`; see `Locale`. On reads, the translation
+    /// served. Set on create (`en` by default), ignored on update
+    #[serde(default)]
+    pub locale: String,
+    /// Derived from `content` on every write, like the fields below. Ignored on create and update
+    #[serde(default)]
+    pub word_count: i32,
+    /// At 200 words a minute, rounded up
+    #[serde(default)]
+    pub reading_time_minutes: i32,
+    /// Plain-text summary: the start of the content unless the author set one
+    #[serde(default)]
+    pub excerpt: String,
+    /// Whether `excerpt` was set by the author; see `PUT /blogs/{id}/excerpt`
+    #[serde(default)]
+    pub excerpt_is_manual: bool,
+    /// Headings of the content, in order, with their anchors
+    #[serde(default)]
+    #[schema(value_type = Vec<TocEntry>)]
+    pub toc: serde_json::Value,
+    /// SEO overrides, set with `PUT /blogs/{id}/seo`; see `BlogMeta` for the defaults
+    #[serde(default)]
+    pub meta_title: Option<String>,
+    #[serde(default)]
+    pub meta_description: Option<String>,
+    #[serde(default)]
+    pub canonical_url: Option<String>,
+    #[serde(default)]
+    pub og_image_url: Option<String>,
+    /// Asks search engines not to index the blog
+    #[serde(default)]
+    pub noindex: bool,
+    /// Last change to anything shown with the blog except its views: counters,
+    /// settings, reactions, credits, series and translations. Ignored on create and update
+    #[serde(default)]
+    pub changed_at: DateTime<Utc>,
+}
+
+impl Blog {
+    /// The comment mode in force now: `locked` once the auto-close period,
+    /// counted from publication, has passed.
+    pub fn effective_comment_mode(&self) -> CommentMode {
+        let mode = CommentMode::parse(&self.comment_mode).unwrap_or_default();
+        let since = self.published_at.unwrap_or(self.created_at);
+        // A close time past chrono's range never arrives
+        let auto_closed = self.comment_auto_close_days
+            .and_then(|days| since.checked_add_signed(chrono::Duration::days(days.into())))
+            .is_some_and(|closes_at| closes_at <= Utc::now());
+
+        match mode {
+            CommentMode::Closed => CommentMode::Closed,
+            _ if auto_closed => CommentMode::Locked,
+            mode => mode,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BlogStatus {
+    /// Only the author can read it, or reviewers holding a preview link
+    Draft,
+    /// Submitted and waiting for the assigned editor
+    InReview,
+    /// Sent back to the author with the editor's comments
+    ChangesRequested,
+    /// Approved; published automatically at `published_at`
+    Scheduled,
+    #[default]
+    Published,
+}
+
+impl BlogStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BlogStatus::Draft => "draft",
+            BlogStatus::InReview => "in_review",
+            BlogStatus::ChangesRequested => "changes_requested",
+            BlogStatus::Scheduled => "scheduled",
+            BlogStatus::Published => "published",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "draft" => Some(BlogStatus::Draft),
+            "in_review" => Some(BlogStatus::InReview),
+            "changes_requested" => Some(BlogStatus::ChangesRequested),
+            "scheduled" => Some(BlogStatus::Scheduled),
+            "published" => Some(BlogStatus::Published),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = crate::schema::blog_transitions)]
+pub struct BlogTransition {
+    pub id: Uuid,
+    pub blog_id: Uuid,
+    pub from_status: String,
+    pub to_status: String,
+    /// Workflow action that caused the change, e.g. `submit` or `approve`
+    pub action: String,
+    /// `None` when the scheduler published the blog
+    pub actor_id: Option<Uuid>,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SubmitForReviewRequest {
+    /// Editor to review the blog; keeps the current reviewer when omitted
+    #[serde(default)]
+    pub reviewer_id: Option<Uuid>,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReviewCommentRequest {
+    /// Required when requesting changes
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ApproveRequest {
+    /// Schedule the blog for this time instead of publishing it now
+    #[serde(default)]
+    pub publish_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AssignReviewerRequest {
+    pub reviewer_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReviewQueueQuery {
+    /// Defaults to `in_review`
+    pub status: Option<BlogStatus>,
+    pub reviewer_id: Option<Uuid>,
+    /// Only blogs of this publication; its owners and editors may use this
+    /// without being site editors
+    pub publication_id: Option<Uuid>,
+    /// Page size, at most 100
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = crate::schema::blog_revisions)]
+pub struct BlogRevision {
+    pub id: Uuid,
+    pub blog_id: Uuid,
+    pub revision_number: i32,
+    pub title: String,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = crate::schema::blog_preview_tokens)]
+pub struct PreviewToken {
+    pub id: Uuid,
+    pub blog_id: Uuid,
+    /// Revision the link is pinned to; `None` follows the current draft
+    pub revision_id: Option<Uuid>,
+    pub created_by: Option<Uuid>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PreviewTokenRequest {
+    /// Lifetime of the link in hours, 72 by default and at most 720
+    #[serde(default)]
+    pub expires_in_hours: Option<i64>,
+    /// Pin the link to this revision instead of the current draft
+    #[serde(default)]
+    pub revision_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PreviewLink {
+    #[serde(flatten)]
+    pub preview: PreviewToken,
+    /// Signed token for `GET /preview/{token}`; only returned on creation
+    pub token: String,
+}
+
+/// Read-only view of a blog through a preview link.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BlogPreview {
+    pub blog_id: Uuid,
+    pub author_id: Uuid,
+    pub status: String,
+    /// Revision shown, when the link is pinned to one
+    pub revision_id: Option<Uuid>,
+    pub title: String,
+    pub content: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BlogVisibility {
+    /// Readable by anyone and shown in listings
+    #[default]
+    Public,
+    /// Readable by anyone with the link, left out of listings and feeds
+    Unlisted,
+    /// Readable by the author only
+    Private,
+    /// Readable by the author and the users it is shared with
+    Shared,
+    /// Readable by anyone who sends the post password in `X-Blog-Password`
+    Password,
+}
+
+impl BlogVisibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BlogVisibility::Public => "public",
+            BlogVisibility::Unlisted => "unlisted",
+            BlogVisibility::Private => "private",
+            BlogVisibility::Shared => "shared",
+            BlogVisibility::Password => "password",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "public" => Some(BlogVisibility::Public),
+            "unlisted" => Some(BlogVisibility::Unlisted),
+            "private" => Some(BlogVisibility::Private),
+            "shared" => Some(BlogVisibility::Shared),
+            "password" => Some(BlogVisibility::Password),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct VisibilityRequest {
+    pub visibility: BlogVisibility,
+    /// Post password; required for `password` visibility
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentMode {
+    /// Anyone signed in can comment
+    #[default]
+    Open,
+    /// Comments are hidden and no new ones are accepted
+    Closed,
+    /// Existing comments stay visible but no new ones are accepted
+    Locked,
+    /// Only followers of the author, and the author, can comment
+    FollowersOnly,
+    /// Only signed-in users can comment. Commenting always needs an account,
+    /// so this currently behaves like `open`
+    RegisteredOnly,
+}
+
+impl CommentMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentMode::Open => "open",
+            CommentMode::Closed => "closed",
+            CommentMode::Locked => "locked",
+            CommentMode::FollowersOnly => "followers_only",
+            CommentMode::RegisteredOnly => "registered_only",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "open" => Some(CommentMode::Open),
+            "closed" => Some(CommentMode::Closed),
+            "locked" => Some(CommentMode::Locked),
+            "followers_only" => Some(CommentMode::FollowersOnly),
+            "registered_only" => Some(CommentMode::RegisteredOnly),
+            _ => None,
+        }
+    }
+}
+
+/// Longest auto-close period, about ten years.
+pub const MAX_AUTO_CLOSE_DAYS: i32 = 3650;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CommentSettingsRequest {
+    pub mode: CommentMode,
+    /// Lock comments this many days after the blog was published, at most 3650; `null` to never lock
+    #[serde(default)]
+    pub auto_close_days: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BlogSort {
+    #[default]
+    Newest,
+    Likes,
+    Comments,
+    Views,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BlogListQuery {
+    /// Sort order, `newest` by default
+    pub sort: Option<BlogSort>,
+    /// Page size, at most 100
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// A blog as returned by read endpoints, with details specific to the viewer.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BlogResponse {
+    #[serde(flatten)]
+    pub blog: Blog,
+    pub liked_by_me: bool,
+    pub reactions: ReactionSummary,
+    /// Whether the viewer may comment now, given the comment mode and blocks
+    pub can_comment: bool,
+    /// Everyone credited on the blog, in byline order
+    pub authors: Vec<BlogAuthor>,
+    /// Tags in alphabetical order
+    pub tags: Vec<String>,
+    /// Position in its series with links to the neighbouring parts; only on single-blog reads
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series: Option<SeriesNav>,
+    /// Slug of the translation served, if the title and content are translated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    /// Locales the blog can be read in, the original first
+    pub available_locales: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Queryable, Insertable, ToSchema)]
@@ -34,13 +441,1040 @@ pub struct Comment {
     pub content: String,
     pub parent_comment_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub upvotes: i64,
+    #[serde(default)]
+    pub downvotes: i64,
+    #[serde(default)]
+    pub score: i64,
+    /// Last edit or vote; ignored on create and update
+    #[serde(default)]
+    pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Queryable, Insertable, ToSchema)]
-#[diesel(table_name = crate::schema::likes)]
+/// A comment as returned by read endpoints, with details specific to the viewer.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CommentResponse {
+    #[serde(flatten)]
+    pub comment: Comment,
+    pub reactions: ReactionSummary,
+    /// The viewer's vote, `1` or `-1`, if any
+    pub my_vote: Option<i16>,
+}
+
+/// A comment with its replies, as returned by `GET /blogs/{id}/comments`.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CommentThread {
+    #[serde(flatten)]
+    pub comment: CommentResponse,
+    pub replies: Vec<CommentThread>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentSort {
+    /// Best first by the lower bound of the Wilson score interval
+    #[default]
+    Top,
+    Newest,
+    Oldest,
+    /// Many votes split evenly between up and down first
+    Controversial,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CommentListQuery {
+    /// Sort order of every thread level, `top` by default
+    pub sort: Option<CommentSort>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct VoteRequest {
+    /// `1` for an upvote, `-1` for a downvote
+    pub value: i16,
+}
+
+/// A blog like, stored as a `like` reaction on the blog.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct Like {
     pub id: Uuid,
     pub blog_id: Uuid,
     pub user_id: Uuid,
     pub created_at: DateTime<Utc>,
-}
\ No newline at end of file
+}
+
+impl From<Reaction> for Like {
+    fn from(reaction: Reaction) -> Self {
+        Self {
+            id: reaction.id,
+            blog_id: reaction.target_id,
+            user_id: reaction.user_id,
+            created_at: reaction.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReactionTarget {
+    Blog,
+    Comment,
+}
+
+impl ReactionTarget {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReactionTarget::Blog => "blog",
+            ReactionTarget::Comment => "comment",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = crate::schema::reactions)]
+pub struct Reaction {
+    pub id: Uuid,
+    pub target_type: String,
+    pub target_id: Uuid,
+    pub user_id: Uuid,
+    pub kind: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Reaction counts per kind on a blog or comment, and the kinds the viewer used.
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct ReactionSummary {
+    pub counts: BTreeMap<String, i64>,
+    pub mine: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = crate::schema::blog_events)]
+pub struct BlogEvent {
+    pub id: i64,
+    pub blog_id: Uuid,
+    pub event_type: String,
+    #[schema(value_type = Object)]
+    pub payload: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl BlogEvent {
+    /// Who wrote the comment a `comment_created` or `comment_updated` event is about.
+    pub fn comment_author(&self) -> Option<Uuid> {
+        if !matches!(self.event_type.as_str(), "comment_created" | "comment_updated") {
+            return None;
+        }
+        self.payload.get("user_id")
+            .and_then(|value| value.as_str())
+            .and_then(|value| Uuid::parse_str(value).ok())
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LoginRequest {
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenResponse {
+    pub token: String,
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = crate::schema::reading_lists)]
+pub struct ReadingList {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub is_public: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = crate::schema::reading_list_items)]
+pub struct ReadingListItem {
+    pub id: Uuid,
+    pub list_id: Uuid,
+    pub blog_id: Option<Uuid>,
+    pub blog_title: String,
+    pub position: i32,
+    pub added_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReadingListRequest {
+    pub name: String,
+    pub description: Option<String>,
+    /// Public lists can be read by anyone with their URL
+    #[serde(default)]
+    pub is_public: bool,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReadingListItemRequest {
+    pub blog_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReorderRequest {
+    /// Every item ID of the list, in the new order
+    pub item_ids: Vec<Uuid>,
+}
+
+/// A saved blog; `blog` is `None` once the blog has been deleted or the viewer
+/// can no longer read it.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReadingListEntry {
+    pub id: Uuid,
+    pub position: i32,
+    pub added_at: DateTime<Utc>,
+    pub available: bool,
+    /// Title when the blog was saved; only shown to the list owner once the blog is unavailable
+    pub blog_title: Option<String>,
+    pub blog: Option<Blog>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReadingListDetail {
+    #[serde(flatten)]
+    pub list: ReadingList,
+    pub items: Vec<ReadingListEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = crate::schema::follows)]
+pub struct Follow {
+    pub follower_id: Uuid,
+    pub followee_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Public view of a user, without email or password hash.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserSummary {
+    pub id: Uuid,
+    pub username: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FollowEntry {
+    pub user: UserSummary,
+    pub followed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FollowPage {
+    /// Total number of followers or followed users
+    pub total: i64,
+    pub users: Vec<FollowEntry>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PageQuery {
+    /// Page size, at most 100
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CursorQuery {
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    /// Page size, at most 100
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FeedPage {
+    pub blogs: Vec<BlogResponse>,
+    /// Pass as `cursor` to get the next page; `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = crate::schema::user_blocks)]
+pub struct UserBlock {
+    pub blocker_id: Uuid,
+    pub blocked_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = crate::schema::user_mutes)]
+pub struct UserMute {
+    pub muter_id: Uuid,
+    pub muted_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Declared from least to most privileged, so roles compare by rank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    User,
+    /// Reviews submitted blogs
+    Editor,
+    Moderator,
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::User => "user",
+            Role::Editor => "editor",
+            Role::Moderator => "moderator",
+            Role::Admin => "admin",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "user" => Some(Role::User),
+            "editor" => Some(Role::Editor),
+            "moderator" => Some(Role::Moderator),
+            "admin" => Some(Role::Admin),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountStatus {
+    Active,
+    Suspended,
+    Banned,
+    /// Can use the site as normal, but their comments are only visible to themselves
+    ShadowBanned,
+}
+
+impl AccountStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountStatus::Active => "active",
+            AccountStatus::Suspended => "suspended",
+            AccountStatus::Banned => "banned",
+            AccountStatus::ShadowBanned => "shadow_banned",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "active" => Some(AccountStatus::Active),
+            "suspended" => Some(AccountStatus::Suspended),
+            "banned" => Some(AccountStatus::Banned),
+            "shadow_banned" => Some(AccountStatus::ShadowBanned),
+            _ => None,
+        }
+    }
+}
+
+/// A user as admins see them: the public profile plus the account status,
+/// which stays off it so shadow bans and moderators' reasons are not revealed.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminUser {
+    #[serde(flatten)]
+    pub user: User,
+    /// One of `active`, `suspended`, `banned` or `shadow_banned`
+    pub status: String,
+    pub status_reason: Option<String>,
+    pub status_expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<User> for AdminUser {
+    fn from(user: User) -> Self {
+        Self {
+            status: user.status.clone(),
+            status_reason: user.status_reason.clone(),
+            status_expires_at: user.status_expires_at,
+            user,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AccountStatusRequest {
+    pub status: AccountStatus,
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// When the status lapses; required for `suspended`, optional otherwise
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RoleRequest {
+    pub role: Role,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportTarget {
+    Blog,
+    Comment,
+    User,
+}
+
+impl ReportTarget {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportTarget::Blog => "blog",
+            ReportTarget::Comment => "comment",
+            ReportTarget::User => "user",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportReason {
+    Spam,
+    Harassment,
+    HateSpeech,
+    Violence,
+    SexualContent,
+    Misinformation,
+    Other,
+}
+
+impl ReportReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportReason::Spam => "spam",
+            ReportReason::Harassment => "harassment",
+            ReportReason::HateSpeech => "hate_speech",
+            ReportReason::Violence => "violence",
+            ReportReason::SexualContent => "sexual_content",
+            ReportReason::Misinformation => "misinformation",
+            ReportReason::Other => "other",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportStatus {
+    Open,
+    Actioned,
+    Dismissed,
+}
+
+impl ReportStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportStatus::Open => "open",
+            ReportStatus::Actioned => "actioned",
+            ReportStatus::Dismissed => "dismissed",
+        }
+    }
+}
+
+/// Action a moderator can take on the target of a report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationAction {
+    /// Deletes the reported blog or comment
+    RemoveContent,
+    /// Suspends the reported user, or the author of the reported content, until `suspended_until`
+    SuspendUser,
+    /// Bans the reported user, or the author of the reported content
+    BanUser,
+}
+
+impl ModerationAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModerationAction::RemoveContent => "remove_content",
+            ModerationAction::SuspendUser => "suspend_user",
+            ModerationAction::BanUser => "ban_user",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = crate::schema::reports)]
+pub struct Report {
+    pub id: Uuid,
+    pub reporter_id: Uuid,
+    pub target_type: String,
+    pub target_id: Uuid,
+    pub reason: String,
+    pub details: Option<String>,
+    pub status: String,
+    pub assignee_id: Option<Uuid>,
+    pub resolution_notes: Option<String>,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReportRequest {
+    pub target_type: ReportTarget,
+    pub target_id: Uuid,
+    pub reason: ReportReason,
+    #[serde(default)]
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReportQueueQuery {
+    /// Defaults to `open`
+    pub status: Option<ReportStatus>,
+    pub assignee_id: Option<Uuid>,
+    /// Page size, at most 100
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AssignReportRequest {
+    /// Moderator to assign, or `null` to unassign
+    pub assignee_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ResolveReportRequest {
+    /// `actioned` or `dismissed`
+    pub status: ReportStatus,
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Action taken on the target; only allowed with `actioned`
+    #[serde(default)]
+    pub action: Option<ModerationAction>,
+    /// End of the suspension; required with `suspend_user`
+    #[serde(default)]
+    pub suspended_until: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = crate::schema::audit_log)]
+pub struct AuditLogEntry {
+    pub id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: Uuid,
+    pub report_id: Option<Uuid>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReportDetail {
+    #[serde(flatten)]
+    pub report: Report,
+    /// Audit log entries recorded for this report
+    pub actions: Vec<AuditLogEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = crate::schema::publications)]
+pub struct Publication {
+    pub id: Uuid,
+    pub name: String,
+    /// URL-safe name: lowercase letters, digits and dashes
+    pub slug: String,
+    pub description: Option<String>,
+    pub logo_url: Option<String>,
+    /// Brand colour as a hex code, e.g. `#1a73e8`
+    pub accent_color: Option<String>,
+    /// Whether writers must have blogs reviewed before they are published
+    pub require_review: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PublicationRequest {
+    pub name: String,
+    pub slug: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub logo_url: Option<String>,
+    #[serde(default)]
+    pub accent_color: Option<String>,
+    /// Defaults to `true`
+    #[serde(default)]
+    pub require_review: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PublicationRole {
+    /// Manages settings and members
+    Owner,
+    /// Edits and reviews the publication's blogs
+    Editor,
+    /// Writes blogs for the publication
+    Writer,
+}
+
+impl PublicationRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PublicationRole::Owner => "owner",
+            PublicationRole::Editor => "editor",
+            PublicationRole::Writer => "writer",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "owner" => Some(PublicationRole::Owner),
+            "editor" => Some(PublicationRole::Editor),
+            "writer" => Some(PublicationRole::Writer),
+            _ => None,
+        }
+    }
+
+    /// Owners and editors can manage the publication's blogs.
+    pub fn manages_blogs(&self) -> bool {
+        matches!(self, PublicationRole::Owner | PublicationRole::Editor)
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublicationMember {
+    pub user: UserSummary,
+    pub role: PublicationRole,
+    pub joined_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MemberRoleRequest {
+    pub role: PublicationRole,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BlogPublicationRequest {
+    /// `None` moves the blog out of its publication
+    #[serde(default)]
+    pub publication_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthorRole {
+    /// Full co-author, credited in the byline
+    Author,
+    /// Helped with the post, e.g. edits or illustrations
+    #[default]
+    Contributor,
+}
+
+impl AuthorRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthorRole::Author => "author",
+            AuthorRole::Contributor => "contributor",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "author" => Some(AuthorRole::Author),
+            "contributor" => Some(AuthorRole::Contributor),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BlogAuthor {
+    pub user: UserSummary,
+    pub role: AuthorRole,
+    /// Place in the byline, starting at 0
+    pub position: i32,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AuthorOrderRequest {
+    /// Every author of the blog, in the new byline order
+    pub user_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum InvitationStatus {
+    Pending,
+    Accepted,
+    Declined,
+    /// Withdrawn by the blog's owner before it was answered
+    Revoked,
+}
+
+impl InvitationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InvitationStatus::Pending => "pending",
+            InvitationStatus::Accepted => "accepted",
+            InvitationStatus::Declined => "declined",
+            InvitationStatus::Revoked => "revoked",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = crate::schema::blog_author_invitations)]
+pub struct AuthorInvitation {
+    pub id: Uuid,
+    pub blog_id: Uuid,
+    pub invitee_id: Uuid,
+    pub inviter_id: Option<Uuid>,
+    pub role: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub responded_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct InvitationRequest {
+    pub user_id: Uuid,
+    /// Defaults to `contributor`
+    #[serde(default)]
+    pub role: AuthorRole,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    CoauthorInvited,
+    InvitationAccepted,
+    InvitationDeclined,
+    /// A co-author changed a blog you are credited on
+    BlogEdited,
+    /// Someone commented on a blog you are credited on
+    BlogCommented,
+    /// Blogs were transferred from or to you
+    BlogTransferred,
+}
+
+impl NotificationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::CoauthorInvited => "coauthor_invited",
+            NotificationKind::InvitationAccepted => "invitation_accepted",
+            NotificationKind::InvitationDeclined => "invitation_declined",
+            NotificationKind::BlogEdited => "blog_edited",
+            NotificationKind::BlogCommented => "blog_commented",
+            NotificationKind::BlogTransferred => "blog_transferred",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = crate::schema::notifications)]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// Who caused the notification
+    pub actor_id: Option<Uuid>,
+    /// See `NotificationKind`
+    pub kind: String,
+    pub blog_id: Option<Uuid>,
+    /// Details specific to the kind, e.g. `invitation_id` or `comment_id`
+    #[schema(value_type = Object)]
+    pub data: serde_json::Value,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NotificationQuery {
+    /// Only unread notifications
+    #[serde(default)]
+    pub unread: bool,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    /// Page size, at most 100
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationPage {
+    pub notifications: Vec<Notification>,
+    pub unread_count: i64,
+    /// Pass as `cursor` to get the next page; `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TransferRequest {
+    /// New owner of the blogs
+    #[serde(default)]
+    pub to_user_id: Option<Uuid>,
+    /// Publication to move the blogs into; the owner must be a member
+    #[serde(default)]
+    pub to_publication_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = crate::schema::series)]
+pub struct Series {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    /// Publication whose owners and editors may also manage the series
+    pub publication_id: Option<Uuid>,
+    pub title: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SeriesRequest {
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Only used on create; the owner must be a member
+    #[serde(default)]
+    pub publication_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SeriesListQuery {
+    pub owner_id: Option<Uuid>,
+    pub publication_id: Option<Uuid>,
+    /// Page size, at most 100
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SeriesDetail {
+    #[serde(flatten)]
+    pub series: Series,
+    /// Parts of the series the viewer can read, in order
+    pub blogs: Vec<BlogResponse>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SeriesOrderRequest {
+    /// Every blog of the series, in the new order
+    pub blog_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SeriesLink {
+    pub id: Uuid,
+    pub title: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SeriesNav {
+    pub id: Uuid,
+    pub title: String,
+    /// 1-based part number among the parts the viewer can read
+    pub part: usize,
+    pub total_parts: usize,
+    pub previous: Option<SeriesLink>,
+    pub next: Option<SeriesLink>,
+}
+
+/// Languages blogs are written and translated in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Locale {
+    #[default]
+    En,
+    Fr,
+    De,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::En, Locale::Fr, Locale::De];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Fr => "fr",
+            Locale::De => "de",
+        }
+    }
+
+    /// Parses a locale or a language tag such as `fr-CA`, going by its primary subtag.
+    pub fn parse(value: &str) -> Option<Self> {
+        let language = value.split(['-', '_']).next().unwrap_or_default();
+        Locale::ALL.into_iter().find(|locale| language.eq_ignore_ascii_case(locale.as_str()))
+    }
+
+    /// The locale as OpenGraph writes it.
+    pub fn open_graph_tag(&self) -> &'static str {
+        match self {
+            Locale::En => "en_US",
+            Locale::Fr => "fr_FR",
+            Locale::De => "de_DE",
+        }
+    }
+
+    /// The Postgres text-search configuration for the language.
+    pub fn search_config(&self) -> &'static str {
+        match self {
+            Locale::En => "english",
+            Locale::Fr => "french",
+            Locale::De => "german",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Queryable, ToSchema)]
+pub struct BlogTranslation {
+    pub id: Uuid,
+    pub blog_id: Uuid,
+    pub locale: String,
+    pub title: String,
+    pub content: String,
+    pub slug: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TranslationRequest {
+    pub title: String,
+    pub content: String,
+    /// Lowercase letters, digits and dashes; unique within the locale
+    pub slug: String,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LocaleQuery {
+    /// Preferred locale; takes precedence over `Accept-Language`
+    pub lang: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BlogSearchQuery {
+    /// Search terms, in web search syntax: quoted phrases, `or`, `-excluded`
+    pub q: String,
+    /// Only match text in this locale, and serve results in it where translated
+    pub lang: Option<String>,
+    /// Page size, at most 100
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// A heading in a blog's table of contents.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TocEntry {
+    /// 1 for `#`, up to 6 for `######`
+    pub level: i32,
+    pub text: String,
+    /// Fragment to link to the heading with, unique within the blog
+    pub anchor: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ExcerptRequest {
+    /// The author's excerpt, or null to go back to the derived one
+    #[serde(default)]
+    pub excerpt: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SeoRequest {
+    /// Replaces the title in metadata; at most 120 characters
+    #[serde(default)]
+    pub meta_title: Option<String>,
+    /// Replaces the excerpt in metadata; at most 300 characters
+    #[serde(default)]
+    pub meta_description: Option<String>,
+    /// Absolute http(s) URL, for blogs first published elsewhere
+    #[serde(default)]
+    pub canonical_url: Option<String>,
+    /// Absolute http(s) URL of the image shown when the blog is shared
+    #[serde(default)]
+    pub og_image_url: Option<String>,
+    #[serde(default)]
+    pub noindex: bool,
+}
+
+/// Most tags a blog may carry.
+pub const MAX_TAGS: usize = 10;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TagsRequest {
+    /// Replaces the blog's tags; at most 10 of up to 50 characters each.
+    /// Tags are lowercased and spaces become dashes, so `Web Dev` is `web-dev`
+    pub tags: Vec<String>,
+}
+
+/// Everything a page rendering the blog needs in its `<head>`.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BlogMeta {
+    /// `meta_title`, or the blog title
+    pub title: String,
+    /// `meta_description`, or the excerpt shortened to 160 characters
+    pub description: String,
+    /// `canonical_url`, or the blog's address on this site
+    pub canonical_url: String,
+    /// Content for `<meta name="robots">`; `noindex` unless the blog is published, public and not flagged
+    pub robots: String,
+    pub open_graph: OpenGraph,
+    pub twitter: TwitterCard,
+    /// schema.org `BlogPosting`, ready for a `<script type="application/ld+json">` tag
+    #[schema(value_type = Object)]
+    pub json_ld: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OpenGraph {
+    /// Always `article`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub title: String,
+    pub description: String,
+    pub url: String,
+    /// `og_image_url`, or the publication logo
+    pub image: Option<String>,
+    pub site_name: String,
+    /// Locale in OpenGraph form, e.g. `fr_FR`
+    pub locale: String,
+    pub published_time: Option<DateTime<Utc>>,
+    pub modified_time: DateTime<Utc>,
+    /// Usernames in byline order
+    pub authors: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TwitterCard {
+    /// `summary_large_image` when there is an image, `summary` otherwise
+    pub card: String,
+    pub title: String,
+    pub description: String,
+    pub image: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedContent {
+    /// The whole post
+    #[default]
+    Full,
+    /// Only the excerpt
+    Excerpt,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FeedQuery {
+    /// `full` by default
+    pub content: Option<FeedContent>,
+}
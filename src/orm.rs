@@ -1,8 +1,19 @@
+use std::collections::{HashMap, HashSet};
+
 use diesel::prelude::*;
 use diesel::pg::PgConnection;
 use uuid::Uuid;
-use crate::models::{User, Blog, Comment, Like};
-use crate::schema::{users, blogs, comments, likes};
+
+use crate::models::{User, Blog, BlogResponse, BlogSort, Comment, CommentResponse, Like, Reaction, ReactionSummary, ReactionTarget, BlogEvent, ReadingList, ReadingListItem, ReadingListEntry, ReadingListDetail, Follow, FollowEntry, UserSummary, UserBlock, UserMute,
+    BlogStatus, BlogRevision, BlogTransition, PreviewToken, BlogVisibility, CommentMode, Role, AccountStatus, Report, ReportTarget, ReportReason, ReportStatus, ReportDetail, ModerationAction, AuditLogEntry,
+    Publication, PublicationRequest, PublicationRole, PublicationMember,
+    Series, SeriesRequest, SeriesDetail, SeriesLink, SeriesNav, Locale, BlogTranslation, TranslationRequest, SeoRequest, AuthorRole, BlogAuthor, AuthorInvitation, InvitationStatus, Notification, NotificationKind, NotificationPage};
+use crate::schema::{users, blogs, comments, comment_votes, reactions, blog_events, reading_lists, reading_list_items, follows, user_blocks, user_mutes, reports, audit_log, blog_shares, blog_revisions, blog_preview_tokens, blog_transitions, publications, publication_members, blog_authors, blog_author_invitations, notifications, series, series_blogs, blog_translations, blog_tags};
+use crate::error_handler::AppError;
+use crate::pagination::Cursor;
+use crate::reaction_kinds;
+use crate::workflow::{self, Actor};
+use crate::content::{self, ContentStats};
 // use crate::orm::{ update_comment, delete_comment, get_like};
 
 #[allow(dead_code)]
@@ -23,6 +34,11 @@ pub fn get_user(conn: &mut PgConnection, user_id: Uuid) -> Result<User, diesel::
     users::table.find(user_id).get_result::<User>(conn)
 }
 
+#[allow(dead_code)]
+pub fn get_user_by_email(conn: &mut PgConnection, email: &str) -> Result<User, diesel::result::Error> {
+    users::table.filter(users::email.eq(email)).get_result::<User>(conn)
+}
+
 #[allow(dead_code)]
 pub fn update_user(conn: &mut PgConnection, user_id: Uuid, username: &str, email: &str) -> Result<User, diesel::result::Error> {
     diesel::update(users::table.find(user_id))
@@ -42,11 +58,20 @@ pub fn delete_user(conn: &mut PgConnection, user_id: Uuid) -> Result<usize, dies
 
 
 #[allow(dead_code)]
-pub fn create_blog(conn: &mut PgConnection, title: &str, content: &str, author_id: Uuid) -> Result<Blog, diesel::result::Error> {
+pub fn create_blog(conn: &mut PgConnection, title: &str, content: &str, author_id: Uuid, status: BlogStatus, publication_id: Option<Uuid>, locale: Locale) -> Result<Blog, diesel::result::Error> {
+    let stats = content::analyze(content);
     let new_blog = NewBlog {
         title,
         content,
         author_id,
+        status: status.as_str(),
+        published_at: (status == BlogStatus::Published).then(chrono::Utc::now),
+        publication_id,
+        locale: locale.as_str(),
+        word_count: stats.word_count,
+        reading_time_minutes: stats.reading_time_minutes,
+        excerpt: &stats.excerpt,
+        toc: toc_json(&stats),
     };
 
     diesel::insert_into(blogs::table)
@@ -59,17 +84,653 @@ pub fn get_blog(conn: &mut PgConnection, blog_id: Uuid) -> Result<Blog, diesel::
     blogs::table.find(blog_id).get_result::<Blog>(conn)
 }
 
+/// Blogs that appear in listings for `viewer_id`: the viewer's own and
+/// co-authored ones, and published ones that are public or shared with the viewer.
+#[diesel::dsl::auto_type]
+fn listed_for(viewer_id: Option<Uuid>) -> _ {
+    let published: &'static str = BlogStatus::Published.as_str();
+    let public: &'static str = BlogVisibility::Public.as_str();
+    let shared: &'static str = BlogVisibility::Shared.as_str();
+    let shared_with_viewer = blog_shares::table
+        .filter(blog_shares::user_id.nullable().eq(viewer_id))
+        .select(blog_shares::blog_id);
+
+    let co_authored = blog_authors::table
+        .filter(blog_authors::user_id.nullable().eq(viewer_id))
+        .select(blog_authors::blog_id);
+
+    blogs::author_id.nullable().eq(viewer_id)
+        .or(blogs::id.eq_any(co_authored))
+        .or(blogs::status.eq(published).and(
+            blogs::visibility.eq(public)
+                .or(blogs::visibility.eq(shared).and(blogs::id.eq_any(shared_with_viewer)))
+        ))
+}
+
+/// Blogs `viewer_id` can open without a password: those listed for them, plus
+/// published unlisted ones.
+#[diesel::dsl::auto_type]
+fn readable_by(viewer_id: Option<Uuid>) -> _ {
+    let published: &'static str = BlogStatus::Published.as_str();
+    let unlisted: &'static str = BlogVisibility::Unlisted.as_str();
+    let listed: listed_for = listed_for(viewer_id);
+    listed.or(blogs::status.eq(published).and(blogs::visibility.eq(unlisted)))
+}
+
+/// Whether `viewer_id` may read the blog, given the post `password` they sent.
+/// The authors and the assigned reviewer can always read it.
+#[allow(dead_code)]
+pub fn can_read_blog(conn: &mut PgConnection, blog: &Blog, viewer_id: Option<Uuid>, password: Option<&str>) -> Result<bool, diesel::result::Error> {
+    if viewer_id == Some(blog.author_id) || (viewer_id.is_some() && viewer_id == blog.reviewer_id) {
+        return Ok(true);
+    }
+    if let Some(viewer_id) = viewer_id {
+        if is_blog_author(conn, blog.id, viewer_id)? {
+            return Ok(true);
+        }
+    }
+    if blog.status != BlogStatus::Published.as_str() {
+        return Ok(false);
+    }
+
+    match BlogVisibility::parse(&blog.visibility).unwrap_or_default() {
+        BlogVisibility::Public | BlogVisibility::Unlisted => Ok(true),
+        BlogVisibility::Private => Ok(false),
+        BlogVisibility::Shared => match viewer_id {
+            Some(viewer_id) => diesel::select(diesel::dsl::exists(blog_shares::table.find((blog.id, viewer_id)))).get_result(conn),
+            None => Ok(false),
+        },
+        BlogVisibility::Password => Ok(match (password, &blog.password_hash) {
+            (Some(password), Some(hash)) => bcrypt::verify(password, hash).unwrap_or(false),
+            _ => false,
+        }),
+    }
+}
+
+#[allow(dead_code)]
+pub fn list_blogs(conn: &mut PgConnection, sort: BlogSort, limit: i64, offset: i64, viewer_id: Option<Uuid>, publication_id: Option<Uuid>, tag: Option<&str>) -> Result<Vec<Blog>, diesel::result::Error> {
+    let mut query = blogs::table
+        .filter(listed_for(viewer_id))
+        .into_boxed();
+    if let Some(publication_id) = publication_id {
+        query = query.filter(blogs::publication_id.eq(publication_id));
+    }
+    if let Some(tag) = tag {
+        query = query.filter(blogs::id.eq_any(tagged_blogs(tag)));
+    }
+    let query = match sort {
+        BlogSort::Newest => query.order((blogs::created_at.desc(), blogs::id.desc())),
+        BlogSort::Likes => query.order((blogs::like_count.desc(), blogs::created_at.desc())),
+        BlogSort::Comments => query.order((blogs::comment_count.desc(), blogs::created_at.desc())),
+        BlogSort::Views => query.order((blogs::view_count.desc(), blogs::created_at.desc())),
+    };
+
+    query.limit(limit).offset(offset).load::<Blog>(conn)
+}
+
+#[allow(dead_code)]
+pub fn record_blog_view(conn: &mut PgConnection, blog_id: Uuid) -> Result<usize, diesel::result::Error> {
+    diesel::update(blogs::table.find(blog_id))
+        .set(blogs::view_count.eq(blogs::view_count + 1))
+        .execute(conn)
+}
+
+/// Recomputes `like_count` and `comment_count` from the source tables and
+/// fixes every blog whose counters have drifted. Returns the number of blogs fixed.
+#[allow(dead_code)]
+pub fn reconcile_blog_counters(conn: &mut PgConnection) -> Result<usize, diesel::result::Error> {
+    diesel::sql_query(
+        "UPDATE blogs SET like_count = actual.like_count, comment_count = actual.comment_count \
+         FROM (SELECT b.id, \
+                      (SELECT COUNT(*) FROM reactions r \
+                       WHERE r.target_type = 'blog' AND r.target_id = b.id AND r.kind = 'like') AS like_count, \
+                      (SELECT COUNT(*) FROM comments c \
+                       WHERE c.blog_id = b.id AND NOT is_shadow_banned(c.user_id)) AS comment_count \
+               FROM blogs b) AS actual \
+         WHERE blogs.id = actual.id \
+           AND (blogs.like_count <> actual.like_count OR blogs.comment_count <> actual.comment_count)"
+    )
+    .execute(conn)
+}
+
 #[allow(dead_code)]
 pub fn update_blog(conn: &mut PgConnection, blog_id: Uuid, title: &str, content: &str) -> Result<Blog, diesel::result::Error> {
+    let stats = content::analyze(content);
     diesel::update(blogs::table.find(blog_id))
         .set((
             blogs::title.eq(title),
             blogs::content.eq(content),
             blogs::updated_at.eq(diesel::dsl::now),
+            blogs::word_count.eq(stats.word_count),
+            blogs::reading_time_minutes.eq(stats.reading_time_minutes),
+            blogs::excerpt.eq(diesel::dsl::case_when(blogs::excerpt_is_manual, blogs::excerpt).otherwise(stats.excerpt.clone())),
+            blogs::toc.eq(toc_json(&stats)),
+        ))
+        .get_result::<Blog>(conn)
+}
+
+fn toc_json(stats: &ContentStats) -> serde_json::Value {
+    serde_json::to_value(&stats.toc).unwrap_or_default()
+}
+
+#[allow(dead_code)]
+pub fn set_blog_seo(conn: &mut PgConnection, blog_id: Uuid, request: &SeoRequest) -> Result<Blog, diesel::result::Error> {
+    diesel::update(blogs::table.find(blog_id))
+        .set((
+            blogs::meta_title.eq(&request.meta_title),
+            blogs::meta_description.eq(&request.meta_description),
+            blogs::canonical_url.eq(&request.canonical_url),
+            blogs::og_image_url.eq(&request.og_image_url),
+            blogs::noindex.eq(request.noindex),
+        ))
+        .get_result::<Blog>(conn)
+}
+
+/// The latest published public blogs for syndication, optionally only those
+/// `author_id` is credited on or those tagged `tag`. Shadow-banned authors are left out.
+#[allow(dead_code)]
+pub fn list_syndicated_blogs(conn: &mut PgConnection, author_id: Option<Uuid>, tag: Option<&str>, limit: i64) -> Result<Vec<Blog>, diesel::result::Error> {
+    let mut query = blogs::table
+        .filter(listed_for(None))
+        .filter(diesel::dsl::not(blogs::author_id.eq_any(shadow_banned_users())))
+        .into_boxed();
+    if let Some(author_id) = author_id {
+        query = query.filter(blogs::id.eq_any(
+            blog_authors::table
+                .filter(blog_authors::user_id.eq(author_id))
+                .select(blog_authors::blog_id)
+        ));
+    }
+    if let Some(tag) = tag {
+        query = query.filter(blogs::id.eq_any(tagged_blogs(tag)));
+    }
+
+    query
+        .order((blogs::published_at.desc().nulls_last(), blogs::id.desc()))
+        .limit(limit)
+        .load::<Blog>(conn)
+}
+
+/// Blogs search engines may index: published, public, not flagged `noindex`,
+/// canonical on this site, and not by a shadow-banned author.
+#[diesel::dsl::auto_type]
+fn indexable() -> _ {
+    let published: &'static str = BlogStatus::Published.as_str();
+    let public: &'static str = BlogVisibility::Public.as_str();
+    let banned: shadow_banned_users = shadow_banned_users();
+    blogs::status.eq(published)
+        .and(blogs::visibility.eq(public))
+        .and(blogs::noindex.eq(false))
+        .and(blogs::canonical_url.is_null())
+        .and(diesel::dsl::not(blogs::author_id.eq_any(banned)))
+}
+
+#[allow(dead_code)]
+pub fn count_sitemap_blogs(conn: &mut PgConnection) -> Result<i64, diesel::result::Error> {
+    blogs::table.filter(indexable()).count().get_result(conn)
+}
+
+/// Ids and last update of indexable blogs, in a stable order for paging.
+#[allow(dead_code)]
+pub fn list_sitemap_blogs(conn: &mut PgConnection, limit: i64, offset: i64) -> Result<Vec<(Uuid, chrono::DateTime<chrono::Utc>)>, diesel::result::Error> {
+    blogs::table
+        .filter(indexable())
+        .order(blogs::id.asc())
+        .select((blogs::id, blogs::updated_at))
+        .limit(limit)
+        .offset(offset)
+        .load(conn)
+}
+
+#[allow(dead_code)]
+pub fn count_sitemap_authors(conn: &mut PgConnection) -> Result<i64, diesel::result::Error> {
+    blog_authors::table
+        .inner_join(blogs::table.on(blogs::id.eq(blog_authors::blog_id)))
+        .filter(indexable())
+        .select(diesel::dsl::count(blog_authors::user_id).aggregate_distinct())
+        .get_result(conn)
+}
+
+/// Users credited on an indexable blog, with when the latest of those blogs changed.
+#[allow(dead_code)]
+pub fn list_sitemap_authors(conn: &mut PgConnection, limit: i64, offset: i64) -> Result<Vec<(Uuid, chrono::DateTime<chrono::Utc>)>, diesel::result::Error> {
+    let authors = blog_authors::table
+        .inner_join(blogs::table.on(blogs::id.eq(blog_authors::blog_id)))
+        .filter(indexable())
+        .group_by(blog_authors::user_id)
+        .order(blog_authors::user_id.asc())
+        .select((blog_authors::user_id, diesel::dsl::max(blogs::updated_at)))
+        .limit(limit)
+        .offset(offset)
+        .load::<(Uuid, Option<chrono::DateTime<chrono::Utc>>)>(conn)?;
+
+    // Every group has at least one blog, so the maximum is never null
+    Ok(authors.into_iter().filter_map(|(user_id, updated_at)| Some((user_id, updated_at?))).collect())
+}
+
+#[allow(dead_code)]
+pub fn count_sitemap_tags(conn: &mut PgConnection) -> Result<i64, diesel::result::Error> {
+    blog_tags::table
+        .inner_join(blogs::table.on(blogs::id.eq(blog_tags::blog_id)))
+        .filter(indexable())
+        .select(diesel::dsl::count(blog_tags::tag).aggregate_distinct())
+        .get_result(conn)
+}
+
+/// Tags carried by an indexable blog, with when the latest of those blogs changed.
+#[allow(dead_code)]
+pub fn list_sitemap_tags(conn: &mut PgConnection, limit: i64, offset: i64) -> Result<Vec<(String, chrono::DateTime<chrono::Utc>)>, diesel::result::Error> {
+    let tags = blog_tags::table
+        .inner_join(blogs::table.on(blogs::id.eq(blog_tags::blog_id)))
+        .filter(indexable())
+        .group_by(blog_tags::tag)
+        .order(blog_tags::tag.asc())
+        .select((blog_tags::tag, diesel::dsl::max(blogs::updated_at)))
+        .limit(limit)
+        .offset(offset)
+        .load::<(String, Option<chrono::DateTime<chrono::Utc>>)>(conn)?;
+
+    // Every group has at least one blog, so the maximum is never null
+    Ok(tags.into_iter().filter_map(|(tag, updated_at)| Some((tag, updated_at?))).collect())
+}
+
+/// When the newest blog on each page of `page_size` listed by
+/// `list_sitemap_blogs` was updated, for the sitemap index.
+#[allow(dead_code)]
+pub fn list_sitemap_blog_pages(conn: &mut PgConnection, page_size: i64) -> Result<Vec<chrono::DateTime<chrono::Utc>>, diesel::result::Error> {
+    let mut pages = Vec::new();
+    let mut start = blogs::table
+        .filter(indexable())
+        .order(blogs::id.asc())
+        .select(blogs::id)
+        .first::<Uuid>(conn)
+        .optional()?;
+
+    while let Some(from) = start {
+        let next = blogs::table
+            .filter(indexable())
+            .filter(blogs::id.ge(from))
+            .order(blogs::id.asc())
+            .select(blogs::id)
+            .offset(page_size)
+            .first::<Uuid>(conn)
+            .optional()?;
+
+        let mut page = blogs::table
+            .filter(indexable())
+            .filter(blogs::id.ge(from))
+            .select(diesel::dsl::max(blogs::updated_at))
+            .into_boxed();
+        if let Some(to) = next {
+            page = page.filter(blogs::id.lt(to));
+        }
+        pages.extend(page.get_result::<Option<chrono::DateTime<chrono::Utc>>>(conn)?);
+        start = next;
+    }
+
+    Ok(pages)
+}
+
+/// Like `list_sitemap_blog_pages`, for the pages of `list_sitemap_authors`.
+#[allow(dead_code)]
+pub fn list_sitemap_author_pages(conn: &mut PgConnection, page_size: i64) -> Result<Vec<chrono::DateTime<chrono::Utc>>, diesel::result::Error> {
+    let mut pages = Vec::new();
+    let mut start = blog_authors::table
+        .inner_join(blogs::table.on(blogs::id.eq(blog_authors::blog_id)))
+        .filter(indexable())
+        .order(blog_authors::user_id.asc())
+        .select(blog_authors::user_id)
+        .first::<Uuid>(conn)
+        .optional()?;
+
+    while let Some(from) = start {
+        let next = blog_authors::table
+            .inner_join(blogs::table.on(blogs::id.eq(blog_authors::blog_id)))
+            .filter(indexable())
+            .filter(blog_authors::user_id.ge(from))
+            .order(blog_authors::user_id.asc())
+            .select(blog_authors::user_id)
+            .distinct()
+            .offset(page_size)
+            .first::<Uuid>(conn)
+            .optional()?;
+
+        let mut page = blog_authors::table
+            .inner_join(blogs::table.on(blogs::id.eq(blog_authors::blog_id)))
+            .filter(indexable())
+            .filter(blog_authors::user_id.ge(from))
+            .select(diesel::dsl::max(blogs::updated_at))
+            .into_boxed();
+        if let Some(to) = next {
+            page = page.filter(blog_authors::user_id.lt(to));
+        }
+        pages.extend(page.get_result::<Option<chrono::DateTime<chrono::Utc>>>(conn)?);
+        start = next;
+    }
+
+    Ok(pages)
+}
+
+/// Like `list_sitemap_blog_pages`, for the pages of `list_sitemap_tags`.
+#[allow(dead_code)]
+pub fn list_sitemap_tag_pages(conn: &mut PgConnection, page_size: i64) -> Result<Vec<chrono::DateTime<chrono::Utc>>, diesel::result::Error> {
+    let mut pages = Vec::new();
+    let mut start = blog_tags::table
+        .inner_join(blogs::table.on(blogs::id.eq(blog_tags::blog_id)))
+        .filter(indexable())
+        .order(blog_tags::tag.asc())
+        .select(blog_tags::tag)
+        .first::<String>(conn)
+        .optional()?;
+
+    while let Some(from) = start {
+        let next = blog_tags::table
+            .inner_join(blogs::table.on(blogs::id.eq(blog_tags::blog_id)))
+            .filter(indexable())
+            .filter(blog_tags::tag.ge(&from))
+            .order(blog_tags::tag.asc())
+            .select(blog_tags::tag)
+            .distinct()
+            .offset(page_size)
+            .first::<String>(conn)
+            .optional()?;
+
+        let mut page = blog_tags::table
+            .inner_join(blogs::table.on(blogs::id.eq(blog_tags::blog_id)))
+            .filter(indexable())
+            .filter(blog_tags::tag.ge(from))
+            .select(diesel::dsl::max(blogs::updated_at))
+            .into_boxed();
+        if let Some(to) = &next {
+            page = page.filter(blog_tags::tag.lt(to.clone()));
+        }
+        pages.extend(page.get_result::<Option<chrono::DateTime<chrono::Utc>>>(conn)?);
+        start = next;
+    }
+
+    Ok(pages)
+}
+
+/// Derives the content stats of blogs written before they were stored, in
+/// batches. Returns the number of blogs filled in.
+#[allow(dead_code)]
+pub fn backfill_content_stats(conn: &mut PgConnection) -> Result<usize, diesel::result::Error> {
+    const BATCH: i64 = 100;
+    let mut filled = 0;
+    let mut after = Uuid::nil();
+
+    loop {
+        let batch = blogs::table
+            .filter(blogs::word_count.eq(0))
+            .filter(blogs::content.ne(""))
+            .filter(blogs::id.gt(after))
+            .order(blogs::id.asc())
+            .select((blogs::id, blogs::content))
+            .limit(BATCH)
+            .load::<(Uuid, String)>(conn)?;
+
+        for (blog_id, content) in &batch {
+            let stats = content::analyze(content);
+            filled += diesel::update(blogs::table.find(blog_id))
+                .set((
+                    blogs::word_count.eq(stats.word_count),
+                    blogs::reading_time_minutes.eq(stats.reading_time_minutes),
+                    blogs::excerpt.eq(diesel::dsl::case_when(blogs::excerpt_is_manual, blogs::excerpt).otherwise(stats.excerpt.clone())),
+                    blogs::toc.eq(toc_json(&stats)),
+                ))
+                .execute(conn)?;
+        }
+
+        match batch.last() {
+            Some((blog_id, _)) if batch.len() as i64 == BATCH => after = *blog_id,
+            _ => return Ok(filled),
+        }
+    }
+}
+
+/// Sets the author's excerpt, or with `None` goes back to the one derived from the content.
+#[allow(dead_code)]
+pub fn set_blog_excerpt(conn: &mut PgConnection, blog: &Blog, excerpt: Option<&str>) -> Result<Blog, diesel::result::Error> {
+    let manual = excerpt.is_some();
+    let excerpt = match excerpt {
+        Some(excerpt) => excerpt.to_string(),
+        None => content::analyze(&blog.content).excerpt,
+    };
+
+    diesel::update(blogs::table.find(blog.id))
+        .set((
+            blogs::excerpt.eq(excerpt),
+            blogs::excerpt_is_manual.eq(manual),
+        ))
+        .get_result::<Blog>(conn)
+}
+
+
+#[allow(dead_code)]
+pub fn update_comment_settings(conn: &mut PgConnection, blog_id: Uuid, mode: CommentMode, auto_close_days: Option<i32>) -> Result<Blog, diesel::result::Error> {
+    diesel::update(blogs::table.find(blog_id))
+        .set((
+            blogs::comment_mode.eq(mode.as_str()),
+            blogs::comment_auto_close_days.eq(auto_close_days),
+        ))
+        .get_result::<Blog>(conn)
+}
+
+/// Sets who may read the blog. `password_hash` is stored only for `password`
+/// visibility; shares are kept so switching back to `shared` restores them.
+#[allow(dead_code)]
+pub fn set_blog_visibility(conn: &mut PgConnection, blog_id: Uuid, visibility: BlogVisibility, password_hash: Option<&str>) -> Result<Blog, diesel::result::Error> {
+    let password_hash = password_hash.filter(|_| visibility == BlogVisibility::Password);
+    diesel::update(blogs::table.find(blog_id))
+        .set((
+            blogs::visibility.eq(visibility.as_str()),
+            blogs::password_hash.eq(password_hash),
+        ))
+        .get_result::<Blog>(conn)
+}
+
+#[allow(dead_code)]
+pub fn share_blog(conn: &mut PgConnection, blog_id: Uuid, user_id: Uuid) -> Result<usize, diesel::result::Error> {
+    diesel::insert_into(blog_shares::table)
+        .values((
+            blog_shares::blog_id.eq(blog_id),
+            blog_shares::user_id.eq(user_id),
         ))
+        .on_conflict((blog_shares::blog_id, blog_shares::user_id))
+        .do_nothing()
+        .execute(conn)
+}
+
+#[allow(dead_code)]
+pub fn unshare_blog(conn: &mut PgConnection, blog_id: Uuid, user_id: Uuid) -> Result<usize, diesel::result::Error> {
+    diesel::delete(blog_shares::table.find((blog_id, user_id)))
+        .execute(conn)
+}
+
+#[allow(dead_code)]
+pub fn list_blog_shares(conn: &mut PgConnection, blog_id: Uuid) -> Result<Vec<UserSummary>, diesel::result::Error> {
+    let rows = blog_shares::table
+        .inner_join(users::table.on(users::id.eq(blog_shares::user_id)))
+        .filter(blog_shares::blog_id.eq(blog_id))
+        .order(blog_shares::created_at.asc())
+        .select((users::id, users::username))
+        .load::<(Uuid, String)>(conn)?;
+
+    Ok(rows.into_iter().map(|(id, username)| UserSummary { id, username }).collect())
+}
+
+/// Moves the blog through the editorial workflow and records the transition.
+/// `actor_id` is `None` for the scheduler; `publish_at` is required when scheduling.
+#[allow(dead_code)]
+pub fn transition_blog(conn: &mut PgConnection, blog: &Blog, action: workflow::Action, actor_id: Option<Uuid>, comment: Option<&str>, publish_at: Option<chrono::DateTime<chrono::Utc>>) -> Result<Blog, AppError> {
+    let from = BlogStatus::parse(&blog.status).unwrap_or_default();
+    let actor = match actor_id {
+        Some(actor_id) => Actor::of(blog, actor_id, is_blog_author(conn, blog.id, actor_id)?),
+        None => Some(Actor::Scheduler),
+    };
+    let review_required = match blog.publication_id {
+        Some(publication_id) => {
+            let publication = get_publication(conn, publication_id)?;
+            let author_role = publication_role(conn, publication_id, blog.author_id)?;
+            publication.require_review && !author_role.is_some_and(|role| role.manages_blogs())
+        }
+        None => false,
+    };
+    let to = workflow::transition(from, action, actor, review_required)?;
+    let published_at = match to {
+        BlogStatus::Published if from == BlogStatus::Scheduled => blog.published_at,
+        BlogStatus::Published => Some(chrono::Utc::now()),
+        BlogStatus::Scheduled => publish_at,
+        _ => None,
+    };
+
+    conn.transaction(|conn| {
+        // Only move from the status we checked, so concurrent transitions can't both apply.
+        let updated = diesel::update(blogs::table.find(blog.id).filter(blogs::status.eq(from.as_str())))
+            .set((
+                blogs::status.eq(to.as_str()),
+                blogs::published_at.eq(published_at),
+            ))
+            .get_result::<Blog>(conn)
+            .optional()?
+            .ok_or_else(|| AppError::BadRequest("blog status changed, reload and try again".to_string()))?;
+
+        diesel::insert_into(blog_transitions::table)
+            .values(NewBlogTransition {
+                blog_id: blog.id,
+                from_status: from.as_str(),
+                to_status: to.as_str(),
+                action: action.as_str(),
+                actor_id,
+                comment,
+            })
+            .execute(conn)?;
+
+        Ok(updated)
+    })
+}
+
+/// Submits the blog for review, assigning `reviewer_id` first when given.
+#[allow(dead_code)]
+pub fn submit_blog_for_review(conn: &mut PgConnection, blog: &Blog, actor_id: Uuid, reviewer_id: Option<Uuid>, comment: Option<&str>) -> Result<Blog, AppError> {
+    conn.transaction(|conn| {
+        if let Some(reviewer_id) = reviewer_id {
+            assign_reviewer(conn, blog.id, Some(reviewer_id))?;
+        }
+        transition_blog(conn, blog, workflow::Action::Submit, Some(actor_id), comment, None)
+    })
+}
+
+#[allow(dead_code)]
+pub fn assign_reviewer(conn: &mut PgConnection, blog_id: Uuid, reviewer_id: Option<Uuid>) -> Result<Blog, diesel::result::Error> {
+    diesel::update(blogs::table.find(blog_id))
+        .set(blogs::reviewer_id.eq(reviewer_id))
         .get_result::<Blog>(conn)
 }
 
+/// Publishes scheduled blogs whose time has come. Returns how many went live.
+#[allow(dead_code)]
+pub fn publish_due_blogs(conn: &mut PgConnection) -> Result<usize, AppError> {
+    let due = blogs::table
+        .filter(blogs::status.eq(BlogStatus::Scheduled.as_str()))
+        .filter(blogs::published_at.le(diesel::dsl::now))
+        .load::<Blog>(conn)?;
+
+    let mut published = 0;
+    for blog in &due {
+        match transition_blog(conn, blog, workflow::Action::Publish, None, None, None) {
+            Ok(_) => published += 1,
+            // Unscheduled by its reviewer since we loaded it
+            Err(AppError::BadRequest(_)) => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(published)
+}
+
+/// Blogs in `status`, optionally only those assigned to `reviewer_id`, longest waiting first.
+#[allow(dead_code)]
+pub fn list_review_queue(conn: &mut PgConnection, status: BlogStatus, reviewer_id: Option<Uuid>, publication_id: Option<Uuid>, limit: i64, offset: i64) -> Result<Vec<Blog>, diesel::result::Error> {
+    let mut query = blogs::table
+        .filter(blogs::status.eq(status.as_str()))
+        .into_boxed();
+    if let Some(reviewer_id) = reviewer_id {
+        query = query.filter(blogs::reviewer_id.eq(reviewer_id));
+    }
+    if let Some(publication_id) = publication_id {
+        query = query.filter(blogs::publication_id.eq(publication_id));
+    }
+
+    query
+        .order((blogs::updated_at.asc(), blogs::id.asc()))
+        .limit(limit)
+        .offset(offset)
+        .load::<Blog>(conn)
+}
+
+#[allow(dead_code)]
+pub fn list_blog_transitions(conn: &mut PgConnection, blog_id: Uuid) -> Result<Vec<BlogTransition>, diesel::result::Error> {
+    blog_transitions::table
+        .filter(blog_transitions::blog_id.eq(blog_id))
+        .order(blog_transitions::created_at.asc())
+        .load::<BlogTransition>(conn)
+}
+
+#[allow(dead_code)]
+pub fn list_blog_revisions(conn: &mut PgConnection, blog_id: Uuid) -> Result<Vec<BlogRevision>, diesel::result::Error> {
+    blog_revisions::table
+        .filter(blog_revisions::blog_id.eq(blog_id))
+        .order(blog_revisions::revision_number.desc())
+        .load::<BlogRevision>(conn)
+}
+
+#[allow(dead_code)]
+pub fn get_blog_revision(conn: &mut PgConnection, blog_id: Uuid, revision_id: Uuid) -> Result<BlogRevision, diesel::result::Error> {
+    blog_revisions::table
+        .filter(blog_revisions::blog_id.eq(blog_id))
+        .find(revision_id)
+        .get_result::<BlogRevision>(conn)
+}
+
+#[allow(dead_code)]
+pub fn create_preview_token(conn: &mut PgConnection, blog_id: Uuid, revision_id: Option<Uuid>, created_by: Uuid, expires_at: chrono::DateTime<chrono::Utc>) -> Result<PreviewToken, diesel::result::Error> {
+    diesel::insert_into(blog_preview_tokens::table)
+        .values((
+            blog_preview_tokens::blog_id.eq(blog_id),
+            blog_preview_tokens::revision_id.eq(revision_id),
+            blog_preview_tokens::created_by.eq(created_by),
+            blog_preview_tokens::expires_at.eq(expires_at),
+        ))
+        .get_result(conn)
+}
+
+#[allow(dead_code)]
+pub fn get_preview_token(conn: &mut PgConnection, preview_id: Uuid) -> Result<PreviewToken, diesel::result::Error> {
+    blog_preview_tokens::table.find(preview_id).get_result::<PreviewToken>(conn)
+}
+
+/// Preview links of a blog that are neither revoked nor expired, newest first.
+#[allow(dead_code)]
+pub fn list_active_preview_tokens(conn: &mut PgConnection, blog_id: Uuid) -> Result<Vec<PreviewToken>, diesel::result::Error> {
+    blog_preview_tokens::table
+        .filter(blog_preview_tokens::blog_id.eq(blog_id))
+        .filter(blog_preview_tokens::revoked_at.is_null())
+        .filter(blog_preview_tokens::expires_at.gt(diesel::dsl::now))
+        .order(blog_preview_tokens::created_at.desc())
+        .load::<PreviewToken>(conn)
+}
+
+#[allow(dead_code)]
+pub fn revoke_preview_token(conn: &mut PgConnection, blog_id: Uuid, preview_id: Uuid) -> Result<usize, diesel::result::Error> {
+    diesel::update(
+        blog_preview_tokens::table
+            .filter(blog_preview_tokens::blog_id.eq(blog_id))
+            .filter(blog_preview_tokens::revoked_at.is_null())
+            .find(preview_id)
+    )
+        .set(blog_preview_tokens::revoked_at.eq(diesel::dsl::now))
+        .execute(conn)
+}
 
 #[allow(dead_code)]
 pub fn delete_blog(conn: &mut PgConnection, blog_id: Uuid) -> Result<usize, diesel::result::Error> {
@@ -77,9 +738,52 @@ pub fn delete_blog(conn: &mut PgConnection, blog_id: Uuid) -> Result<usize, dies
         .execute(conn)
 }
 
+/// Deletes the blog for `actor_id`, recording it in the audit log when they
+/// are not its owner, e.g. a moderator or publication editor.
+#[allow(dead_code)]
+pub fn remove_blog(conn: &mut PgConnection, blog: &Blog, actor_id: Uuid) -> Result<usize, diesel::result::Error> {
+    conn.transaction(|conn| {
+        if blog.author_id != actor_id {
+            record_audit(conn, actor_id, ModerationAction::RemoveContent.as_str(), "blog", blog.id, None, None)?;
+        }
+        delete_blog(conn, blog.id)
+    })
+}
+
 
+/// Only blogs the commenter can read, given the post `password`, take comments,
+/// and only once published. Refused with `Forbidden` when the password is
+/// missing or wrong, when the blog is not published yet, when the blog's
+/// comment mode does not let the commenter in, or when the blog's author, or
+/// the author of the comment being replied to, has blocked the commenter.
 #[allow(dead_code)]
-pub fn create_comment(conn: &mut PgConnection, blog_id: Uuid, user_id: Uuid, content: &str, parent_comment_id: Option<Uuid>) -> Result<Comment, diesel::result::Error> {
+pub fn create_comment(conn: &mut PgConnection, blog_id: Uuid, user_id: Uuid, content: &str, parent_comment_id: Option<Uuid>, password: Option<&str>) -> Result<Comment, AppError> {
+    let blog = get_blog(conn, blog_id)?;
+    if !can_read_blog(conn, &blog, Some(user_id), password)? {
+        return Err(match BlogVisibility::parse(&blog.visibility) {
+            Some(BlogVisibility::Password) if blog.status == BlogStatus::Published.as_str() => AppError::Forbidden,
+            _ => AppError::NotFound,
+        });
+    }
+    if blog.status != BlogStatus::Published.as_str() {
+        return Err(AppError::Forbidden);
+    }
+    let allowed = match blog.effective_comment_mode() {
+        CommentMode::Closed | CommentMode::Locked => false,
+        CommentMode::FollowersOnly => user_id == blog.author_id || is_following(conn, user_id, blog.author_id)?,
+        CommentMode::Open | CommentMode::RegisteredOnly => true,
+    };
+    if !allowed || is_blocked(conn, blog.author_id, user_id)? {
+        return Err(AppError::Forbidden);
+    }
+    if let Some(parent_id) = parent_comment_id {
+        let parent = get_comment(conn, parent_id)?;
+        if is_blocked(conn, parent.user_id, user_id)? {
+            return Err(AppError::Forbidden);
+        }
+    }
+
+    let shadow_banned = get_user(conn, user_id)?.account_status() == AccountStatus::ShadowBanned;
     let new_comment = NewComment {
         blog_id,
         user_id,
@@ -87,9 +791,16 @@ pub fn create_comment(conn: &mut PgConnection, blog_id: Uuid, user_id: Uuid, con
         parent_comment_id,
     };
 
-    diesel::insert_into(comments::table)
-        .values(&new_comment)
-        .get_result(conn)
+    conn.transaction(|conn| {
+        let comment = diesel::insert_into(comments::table)
+            .values(&new_comment)
+            .get_result::<Comment>(conn)?;
+        // Nobody else sees a shadow-banned user's comments, so nobody is told about them.
+        if !shadow_banned {
+            notify_blog_authors(conn, blog_id, user_id, NotificationKind::BlogCommented, serde_json::json!({ "comment_id": comment.id }))?;
+        }
+        Ok(comment)
+    })
 }
 
 
@@ -112,58 +823,1966 @@ pub fn delete_comment(conn: &mut PgConnection, comment_id: Uuid) -> Result<usize
         .execute(conn)
 }
 
+/// Deletes the comment for `actor_id`, recording it in the audit log when
+/// they are not its author.
 #[allow(dead_code)]
-pub fn create_like(conn: &mut PgConnection, blog_id: Uuid, user_id: Uuid) -> Result<Like, diesel::result::Error> {
-    let new_like = NewLike {
-        blog_id,
+pub fn remove_comment(conn: &mut PgConnection, comment: &Comment, actor_id: Uuid) -> Result<usize, diesel::result::Error> {
+    conn.transaction(|conn| {
+        if comment.user_id != actor_id {
+            record_audit(conn, actor_id, ModerationAction::RemoveContent.as_str(), "comment", comment.id, None, None)?;
+        }
+        delete_comment(conn, comment.id)
+    })
+}
+
+#[allow(dead_code)]
+pub fn add_reaction(conn: &mut PgConnection, target: ReactionTarget, target_id: Uuid, user_id: Uuid, kind: &str) -> Result<Reaction, diesel::result::Error> {
+    let new_reaction = NewReaction {
+        target_type: target.as_str(),
+        target_id,
         user_id,
+        kind,
     };
 
-    diesel::insert_into(likes::table)
-        .values(&new_like)
-        .get_result(conn)
+    // Reacting twice is a no-op that returns the existing reaction.
+    let inserted = diesel::insert_into(reactions::table)
+        .values(&new_reaction)
+        .on_conflict((reactions::target_type, reactions::target_id, reactions::user_id, reactions::kind))
+        .do_nothing()
+        .get_result::<Reaction>(conn)
+        .optional()?;
+
+    match inserted {
+        Some(reaction) => Ok(reaction),
+        None => get_user_reaction(conn, target, target_id, user_id, kind),
+    }
+}
+
+#[allow(dead_code)]
+pub fn get_user_reaction(conn: &mut PgConnection, target: ReactionTarget, target_id: Uuid, user_id: Uuid, kind: &str) -> Result<Reaction, diesel::result::Error> {
+    reactions::table
+        .filter(reactions::target_type.eq(target.as_str()))
+        .filter(reactions::target_id.eq(target_id))
+        .filter(reactions::user_id.eq(user_id))
+        .filter(reactions::kind.eq(kind))
+        .get_result::<Reaction>(conn)
+}
+
+#[allow(dead_code)]
+pub fn remove_reaction(conn: &mut PgConnection, target: ReactionTarget, target_id: Uuid, user_id: Uuid, kind: &str) -> Result<usize, diesel::result::Error> {
+    diesel::delete(
+        reactions::table
+            .filter(reactions::target_type.eq(target.as_str()))
+            .filter(reactions::target_id.eq(target_id))
+            .filter(reactions::user_id.eq(user_id))
+            .filter(reactions::kind.eq(kind))
+    )
+    .execute(conn)
+}
+
+/// Aggregates reaction counts per kind for every target, plus the kinds the
+/// viewer reacted with. Targets without reactions are missing from the map.
+#[allow(dead_code)]
+pub fn get_reaction_summaries(conn: &mut PgConnection, target: ReactionTarget, target_ids: &[Uuid], viewer_id: Option<Uuid>) -> Result<HashMap<Uuid, ReactionSummary>, diesel::result::Error> {
+    let counts = reactions::table
+        .filter(reactions::target_type.eq(target.as_str()))
+        .filter(reactions::target_id.eq_any(target_ids))
+        .group_by((reactions::target_id, reactions::kind))
+        .select((reactions::target_id, reactions::kind, diesel::dsl::count_star()))
+        .load::<(Uuid, String, i64)>(conn)?;
+
+    let mut summaries: HashMap<Uuid, ReactionSummary> = HashMap::new();
+    for (target_id, kind, count) in counts {
+        summaries.entry(target_id).or_default().counts.insert(kind, count);
+    }
+
+    if let Some(user_id) = viewer_id {
+        let mine = reactions::table
+            .filter(reactions::target_type.eq(target.as_str()))
+            .filter(reactions::target_id.eq_any(target_ids))
+            .filter(reactions::user_id.eq(user_id))
+            .order(reactions::kind.asc())
+            .select((reactions::target_id, reactions::kind))
+            .load::<(Uuid, String)>(conn)?;
+
+        for (target_id, kind) in mine {
+            summaries.entry(target_id).or_default().mine.push(kind);
+        }
+    }
+
+    Ok(summaries)
+}
+
+#[allow(dead_code)]
+pub fn create_like(conn: &mut PgConnection, blog_id: Uuid, user_id: Uuid) -> Result<Like, diesel::result::Error> {
+    add_reaction(conn, ReactionTarget::Blog, blog_id, user_id, reaction_kinds::LIKE).map(Like::from)
+}
+
+#[allow(dead_code)]
+pub fn get_like_by_user(conn: &mut PgConnection, blog_id: Uuid, user_id: Uuid) -> Result<Like, diesel::result::Error> {
+    get_user_reaction(conn, ReactionTarget::Blog, blog_id, user_id, reaction_kinds::LIKE).map(Like::from)
+}
+
+#[allow(dead_code)]
+pub fn has_liked(conn: &mut PgConnection, blog_id: Uuid, user_id: Uuid) -> Result<bool, diesel::result::Error> {
+    get_like_by_user(conn, blog_id, user_id)
+        .optional()
+        .map(|like| like.is_some())
 }
 
 #[allow(dead_code)]
 pub fn get_like(conn: &mut PgConnection, like_id: Uuid) -> Result<Like, diesel::result::Error> {
-    likes::table.find(like_id).get_result::<Like>(conn)
+    reactions::table
+        .find(like_id)
+        .filter(reactions::target_type.eq(ReactionTarget::Blog.as_str()))
+        .filter(reactions::kind.eq(reaction_kinds::LIKE))
+        .get_result::<Reaction>(conn)
+        .map(Like::from)
 }
 
 
 #[allow(dead_code)]
 pub fn delete_like(conn: &mut PgConnection, like_id: Uuid) -> Result<usize, diesel::result::Error> {
-    diesel::delete(likes::table.find(like_id))
-        .execute(conn)
+    diesel::delete(
+        reactions::table
+            .find(like_id)
+            .filter(reactions::target_type.eq(ReactionTarget::Blog.as_str()))
+            .filter(reactions::kind.eq(reaction_kinds::LIKE))
+    )
+    .execute(conn)
 }
 
-#[derive(Insertable)]
-#[diesel(table_name = users)]
-struct NewUser<'a> {
-    username: &'a str,
-    email: &'a str,
-    password_hash: &'a str,
+#[allow(dead_code)]
+pub fn delete_like_by_user(conn: &mut PgConnection, blog_id: Uuid, user_id: Uuid) -> Result<usize, diesel::result::Error> {
+    remove_reaction(conn, ReactionTarget::Blog, blog_id, user_id, reaction_kinds::LIKE)
 }
 
-#[derive(Insertable)]
-#[diesel(table_name = blogs)]
-struct NewBlog<'a> {
-    title: &'a str,
-    content: &'a str,
-    author_id: Uuid,
+#[allow(dead_code)]
+pub fn get_blog_response(conn: &mut PgConnection, blog_id: Uuid, viewer_id: Option<Uuid>) -> Result<BlogResponse, diesel::result::Error> {
+    let blog = get_blog(conn, blog_id)?;
+    let mut responses = get_blog_responses(conn, vec![blog], viewer_id)?;
+    let mut response = responses.remove(0);
+    response.series = get_series_nav(conn, blog_id, viewer_id)?;
+
+    Ok(response)
 }
 
-#[derive(Insertable)]
-#[diesel(table_name = comments)]
-struct NewComment<'a> {
-    blog_id: Uuid,
-    user_id: Uuid,
-    content: &'a str,
-    parent_comment_id: Option<Uuid>,
+/// When anything an anonymous reader is shown with the blog last changed, for
+/// Last-Modified: the blog itself (see `changed_at`), its series and the other
+/// parts in it, and its authors' profiles.
+#[allow(dead_code)]
+pub fn blog_last_modified(conn: &mut PgConnection, blog: &Blog) -> Result<chrono::DateTime<chrono::Utc>, diesel::result::Error> {
+    let series_id = series_blogs::table
+        .filter(series_blogs::blog_id.eq(blog.id))
+        .select(series_blogs::series_id)
+        .first::<Uuid>(conn)
+        .optional()?;
+
+    let mut last_modified = blog.changed_at;
+    if let Some(series_id) = series_id {
+        let series_changed_at = series::table.find(series_id).select(series::updated_at).first(conn)?;
+        let parts_changed_at = series_blogs::table
+            .inner_join(blogs::table.on(blogs::id.eq(series_blogs::blog_id)))
+            .filter(series_blogs::series_id.eq(series_id))
+            .select(diesel::dsl::max(blogs::changed_at))
+            .get_result::<Option<chrono::DateTime<chrono::Utc>>>(conn)?;
+        last_modified = last_modified.max(series_changed_at).max(parts_changed_at.unwrap_or(last_modified));
+    }
+    let authors_changed_at = blog_authors::table
+        .inner_join(users::table.on(users::id.eq(blog_authors::user_id)))
+        .filter(blog_authors::blog_id.eq(blog.id))
+        .select(diesel::dsl::max(users::updated_at))
+        .get_result::<Option<chrono::DateTime<chrono::Utc>>>(conn)?;
+
+    Ok(last_modified.max(authors_changed_at.unwrap_or(last_modified)))
 }
 
-#[derive(Insertable)]
-#[diesel(table_name = likes)]
-struct NewLike {
-    blog_id: Uuid,
-    user_id: Uuid,
-}
\ No newline at end of file
+#[allow(dead_code)]
+pub fn get_blog_responses(conn: &mut PgConnection, blogs: Vec<Blog>, viewer_id: Option<Uuid>) -> Result<Vec<BlogResponse>, diesel::result::Error> {
+    let blog_ids: Vec<Uuid> = blogs.iter().map(|blog| blog.id).collect();
+    let mut summaries = get_reaction_summaries(conn, ReactionTarget::Blog, &blog_ids, viewer_id)?;
+
+    let mut authors = list_blog_authors(conn, &blog_ids)?;
+    let mut tags = list_blog_tags(conn, &blog_ids)?;
+
+    let mut translated: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for (blog_id, locale) in blog_translations::table
+        .filter(blog_translations::blog_id.eq_any(&blog_ids))
+        .order(blog_translations::locale.asc())
+        .select((blog_translations::blog_id, blog_translations::locale))
+        .load::<(Uuid, String)>(conn)?
+    {
+        translated.entry(blog_id).or_default().push(locale);
+    }
+
+    let author_ids: Vec<Uuid> = blogs.iter().map(|blog| blog.author_id).collect();
+    let (followed, blocked_by): (HashSet<Uuid>, HashSet<Uuid>) = match viewer_id {
+        Some(viewer_id) => (
+            follows::table
+                .filter(follows::follower_id.eq(viewer_id))
+                .filter(follows::followee_id.eq_any(&author_ids))
+                .select(follows::followee_id)
+                .load::<Uuid>(conn)?
+                .into_iter()
+                .collect(),
+            user_blocks::table
+                .filter(user_blocks::blocked_id.eq(viewer_id))
+                .filter(user_blocks::blocker_id.eq_any(&author_ids))
+                .select(user_blocks::blocker_id)
+                .load::<Uuid>(conn)?
+                .into_iter()
+                .collect(),
+        ),
+        None => (HashSet::new(), HashSet::new()),
+    };
+
+    Ok(blogs
+        .into_iter()
+        .map(|blog| {
+            let reactions = summaries.remove(&blog.id).unwrap_or_default();
+            let can_comment = match viewer_id {
+                Some(viewer_id) => !blocked_by.contains(&blog.author_id) && match blog.effective_comment_mode() {
+                    CommentMode::Closed | CommentMode::Locked => false,
+                    CommentMode::FollowersOnly => viewer_id == blog.author_id || followed.contains(&blog.author_id),
+                    CommentMode::Open | CommentMode::RegisteredOnly => true,
+                },
+                None => false,
+            };
+            BlogResponse {
+                liked_by_me: reactions.mine.iter().any(|kind| kind == reaction_kinds::LIKE),
+                reactions,
+                can_comment,
+                authors: authors.remove(&blog.id).unwrap_or_default(),
+                tags: tags.remove(&blog.id).unwrap_or_default(),
+                series: None,
+                slug: None,
+                available_locales: std::iter::once(blog.locale.clone())
+                    .chain(translated.remove(&blog.id).unwrap_or_default())
+                    .collect(),
+                blog,
+            }
+        })
+        .collect())
+}
+
+#[allow(dead_code)]
+pub fn get_comment_response(conn: &mut PgConnection, comment_id: Uuid, viewer_id: Option<Uuid>) -> Result<CommentResponse, diesel::result::Error> {
+    let comment = get_comment(conn, comment_id)?;
+    if let Some(viewer_id) = viewer_id {
+        if is_blocked(conn, viewer_id, comment.user_id)? {
+            return Err(diesel::result::Error::NotFound);
+        }
+    }
+    if viewer_id != Some(comment.user_id) && get_user(conn, comment.user_id)?.account_status() == AccountStatus::ShadowBanned {
+        return Err(diesel::result::Error::NotFound);
+    }
+    let mut responses = get_comment_responses(conn, vec![comment], viewer_id)?;
+
+    Ok(responses.remove(0))
+}
+
+#[allow(dead_code)]
+pub fn get_comment_responses(conn: &mut PgConnection, comments: Vec<Comment>, viewer_id: Option<Uuid>) -> Result<Vec<CommentResponse>, diesel::result::Error> {
+    let comment_ids: Vec<Uuid> = comments.iter().map(|comment| comment.id).collect();
+    let mut summaries = get_reaction_summaries(conn, ReactionTarget::Comment, &comment_ids, viewer_id)?;
+    let votes: HashMap<Uuid, i16> = match viewer_id {
+        Some(user_id) => comment_votes::table
+            .filter(comment_votes::user_id.eq(user_id))
+            .filter(comment_votes::comment_id.eq_any(&comment_ids))
+            .select((comment_votes::comment_id, comment_votes::value))
+            .load::<(Uuid, i16)>(conn)?
+            .into_iter()
+            .collect(),
+        None => HashMap::new(),
+    };
+
+    Ok(comments
+        .into_iter()
+        .map(|comment| CommentResponse {
+            reactions: summaries.remove(&comment.id).unwrap_or_default(),
+            my_vote: votes.get(&comment.id).copied(),
+            comment,
+        })
+        .collect())
+}
+
+/// Ids of users whose shadow-ban is in force.
+#[diesel::dsl::auto_type]
+fn shadow_banned_users() -> _ {
+    let shadow_banned: &'static str = AccountStatus::ShadowBanned.as_str();
+    users::table
+        .filter(users::status.eq(shadow_banned))
+        .filter(users::status_expires_at.is_null().or(users::status_expires_at.gt(diesel::dsl::now)))
+        .select(users::id)
+}
+
+/// Comments on a blog, leaving out those by users `viewer_id` has blocked and
+/// those by shadow-banned users other than the viewer.
+#[allow(dead_code)]
+pub fn list_blog_comments(conn: &mut PgConnection, blog_id: Uuid, viewer_id: Option<Uuid>) -> Result<Vec<Comment>, diesel::result::Error> {
+    let mut query = comments::table
+        .filter(comments::blog_id.eq(blog_id))
+        .filter(diesel::dsl::not(comments::user_id.eq_any(shadow_banned_users())).or(comments::user_id.nullable().eq(viewer_id)))
+        .into_boxed();
+    if let Some(viewer_id) = viewer_id {
+        let blocked = user_blocks::table
+            .filter(user_blocks::blocker_id.eq(viewer_id))
+            .select(user_blocks::blocked_id);
+        query = query.filter(diesel::dsl::not(comments::user_id.eq_any(blocked)));
+    }
+
+    query.load::<Comment>(conn)
+}
+
+/// Records the user's vote on a comment, replacing any earlier vote, and
+/// returns the comment with its updated score.
+#[allow(dead_code)]
+pub fn vote_comment(conn: &mut PgConnection, comment_id: Uuid, user_id: Uuid, value: i16) -> Result<Comment, diesel::result::Error> {
+    diesel::insert_into(comment_votes::table)
+        .values((
+            comment_votes::comment_id.eq(comment_id),
+            comment_votes::user_id.eq(user_id),
+            comment_votes::value.eq(value),
+        ))
+        .on_conflict((comment_votes::comment_id, comment_votes::user_id))
+        .do_update()
+        .set((
+            comment_votes::value.eq(value),
+            comment_votes::updated_at.eq(diesel::dsl::now),
+        ))
+        .execute(conn)?;
+
+    get_comment(conn, comment_id)
+}
+
+#[allow(dead_code)]
+pub fn clear_comment_vote(conn: &mut PgConnection, comment_id: Uuid, user_id: Uuid) -> Result<Comment, diesel::result::Error> {
+    diesel::delete(comment_votes::table.find((comment_id, user_id)))
+        .execute(conn)?;
+
+    get_comment(conn, comment_id)
+}
+
+/// Recomputes comment vote tallies from `comment_votes` and fixes every
+/// comment whose tallies have drifted. Returns the number of comments fixed.
+#[allow(dead_code)]
+pub fn reconcile_comment_scores(conn: &mut PgConnection) -> Result<usize, diesel::result::Error> {
+    diesel::sql_query(
+        "UPDATE comments SET upvotes = actual.upvotes, downvotes = actual.downvotes, \
+                             score = actual.upvotes - actual.downvotes \
+         FROM (SELECT c.id, \
+                      (SELECT COUNT(*) FROM comment_votes v WHERE v.comment_id = c.id AND v.value = 1) AS upvotes, \
+                      (SELECT COUNT(*) FROM comment_votes v WHERE v.comment_id = c.id AND v.value = -1) AS downvotes \
+               FROM comments c) AS actual \
+         WHERE comments.id = actual.id \
+           AND (comments.upvotes <> actual.upvotes OR comments.downvotes <> actual.downvotes \
+                OR comments.score <> actual.upvotes - actual.downvotes)"
+    )
+    .execute(conn)
+}
+
+#[allow(dead_code)]
+pub fn get_blog_event(conn: &mut PgConnection, event_id: i64) -> Result<BlogEvent, diesel::result::Error> {
+    blog_events::table.find(event_id).get_result::<BlogEvent>(conn)
+}
+
+#[allow(dead_code)]
+pub fn get_blog_events_after(conn: &mut PgConnection, event_id: i64, limit: i64) -> Result<Vec<BlogEvent>, diesel::result::Error> {
+    blog_events::table
+        .filter(blog_events::id.gt(event_id))
+        .order(blog_events::id.asc())
+        .limit(limit)
+        .load::<BlogEvent>(conn)
+}
+
+#[allow(dead_code)]
+pub fn get_blog_events_since(conn: &mut PgConnection, blog_id: Uuid, event_id: i64, limit: i64) -> Result<Vec<BlogEvent>, diesel::result::Error> {
+    blog_events::table
+        .filter(blog_events::blog_id.eq(blog_id))
+        .filter(blog_events::id.gt(event_id))
+        .order(blog_events::id.asc())
+        .limit(limit)
+        .load::<BlogEvent>(conn)
+}
+
+/// Whether the user's account is shadow banned. Deleted users are not.
+#[allow(dead_code)]
+pub fn is_shadow_banned(conn: &mut PgConnection, user_id: Uuid) -> Result<bool, diesel::result::Error> {
+    match get_user(conn, user_id) {
+        Ok(user) => Ok(user.account_status() == AccountStatus::ShadowBanned),
+        Err(diesel::result::Error::NotFound) => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Users whose comments `user_id` has asked not to see: those they blocked or muted.
+#[allow(dead_code)]
+pub fn list_hidden_users(conn: &mut PgConnection, user_id: Uuid) -> Result<Vec<Uuid>, diesel::result::Error> {
+    let mut hidden = user_blocks::table
+        .filter(user_blocks::blocker_id.eq(user_id))
+        .select(user_blocks::blocked_id)
+        .load::<Uuid>(conn)?;
+    hidden.extend(
+        user_mutes::table
+            .filter(user_mutes::muter_id.eq(user_id))
+            .select(user_mutes::muted_id)
+            .load::<Uuid>(conn)?,
+    );
+    Ok(hidden)
+}
+
+#[allow(dead_code)]
+pub fn delete_blog_events_before(conn: &mut PgConnection, cutoff: chrono::DateTime<chrono::Utc>) -> Result<usize, diesel::result::Error> {
+    diesel::delete(blog_events::table.filter(blog_events::created_at.lt(cutoff)))
+        .execute(conn)
+}
+
+#[allow(dead_code)]
+pub fn create_reading_list(conn: &mut PgConnection, user_id: Uuid, name: &str, description: Option<&str>, is_public: bool) -> Result<ReadingList, diesel::result::Error> {
+    let new_list = NewReadingList {
+        user_id,
+        name,
+        description,
+        is_public,
+    };
+
+    diesel::insert_into(reading_lists::table)
+        .values(&new_list)
+        .get_result(conn)
+}
+
+#[allow(dead_code)]
+pub fn get_reading_list(conn: &mut PgConnection, list_id: Uuid) -> Result<ReadingList, diesel::result::Error> {
+    reading_lists::table.find(list_id).get_result::<ReadingList>(conn)
+}
+
+#[allow(dead_code)]
+pub fn list_user_reading_lists(conn: &mut PgConnection, user_id: Uuid, public_only: bool) -> Result<Vec<ReadingList>, diesel::result::Error> {
+    let mut query = reading_lists::table
+        .filter(reading_lists::user_id.eq(user_id))
+        .into_boxed();
+    if public_only {
+        query = query.filter(reading_lists::is_public.eq(true));
+    }
+
+    query.order(reading_lists::created_at.asc()).load::<ReadingList>(conn)
+}
+
+#[allow(dead_code)]
+pub fn update_reading_list(conn: &mut PgConnection, list_id: Uuid, name: &str, description: Option<&str>, is_public: bool) -> Result<ReadingList, diesel::result::Error> {
+    diesel::update(reading_lists::table.find(list_id))
+        .set((
+            reading_lists::name.eq(name),
+            reading_lists::description.eq(description),
+            reading_lists::is_public.eq(is_public),
+            reading_lists::updated_at.eq(diesel::dsl::now),
+        ))
+        .get_result::<ReadingList>(conn)
+}
+
+#[allow(dead_code)]
+pub fn delete_reading_list(conn: &mut PgConnection, list_id: Uuid) -> Result<usize, diesel::result::Error> {
+    diesel::delete(reading_lists::table.find(list_id))
+        .execute(conn)
+}
+
+/// Appends the blog to the end of the list. Saving a blog that is already on
+/// the list returns the existing item.
+#[allow(dead_code)]
+pub fn add_reading_list_item(conn: &mut PgConnection, list_id: Uuid, blog: &Blog) -> Result<ReadingListItem, diesel::result::Error> {
+    conn.transaction(|conn| {
+        let last_position = reading_list_items::table
+            .filter(reading_list_items::list_id.eq(list_id))
+            .select(diesel::dsl::max(reading_list_items::position))
+            .get_result::<Option<i32>>(conn)?;
+
+        let new_item = NewReadingListItem {
+            list_id,
+            blog_id: blog.id,
+            blog_title: &blog.title,
+            position: last_position.map_or(0, |position| position + 1),
+        };
+
+        let inserted = diesel::insert_into(reading_list_items::table)
+            .values(&new_item)
+            .on_conflict((reading_list_items::list_id, reading_list_items::blog_id))
+            .do_nothing()
+            .get_result::<ReadingListItem>(conn)
+            .optional()?;
+
+        match inserted {
+            Some(item) => Ok(item),
+            None => reading_list_items::table
+                .filter(reading_list_items::list_id.eq(list_id))
+                .filter(reading_list_items::blog_id.eq(blog.id))
+                .get_result::<ReadingListItem>(conn),
+        }
+    })
+}
+
+#[allow(dead_code)]
+pub fn get_reading_list_item(conn: &mut PgConnection, item_id: Uuid) -> Result<ReadingListItem, diesel::result::Error> {
+    reading_list_items::table.find(item_id).get_result::<ReadingListItem>(conn)
+}
+
+#[allow(dead_code)]
+pub fn delete_reading_list_item(conn: &mut PgConnection, item_id: Uuid) -> Result<usize, diesel::result::Error> {
+    diesel::delete(reading_list_items::table.find(item_id))
+        .execute(conn)
+}
+
+#[allow(dead_code)]
+pub fn list_reading_list_items(conn: &mut PgConnection, list_id: Uuid) -> Result<Vec<ReadingListItem>, diesel::result::Error> {
+    reading_list_items::table
+        .filter(reading_list_items::list_id.eq(list_id))
+        .order((reading_list_items::position.asc(), reading_list_items::added_at.asc()))
+        .load::<ReadingListItem>(conn)
+}
+
+/// Renumbers the items of a list in the order of `item_ids`.
+#[allow(dead_code)]
+pub fn reorder_reading_list_items(conn: &mut PgConnection, list_id: Uuid, item_ids: &[Uuid]) -> Result<(), diesel::result::Error> {
+    conn.transaction(|conn| {
+        for (position, item_id) in item_ids.iter().enumerate() {
+            diesel::update(
+                reading_list_items::table
+                    .find(item_id)
+                    .filter(reading_list_items::list_id.eq(list_id))
+            )
+            .set(reading_list_items::position.eq(position as i32))
+            .execute(conn)?;
+        }
+
+        diesel::update(reading_lists::table.find(list_id))
+            .set(reading_lists::updated_at.eq(diesel::dsl::now))
+            .execute(conn)?;
+
+        Ok(())
+    })
+}
+
+/// Saved blogs the viewer cannot read are shown as unavailable, and without
+/// their title unless the viewer owns the list.
+#[allow(dead_code)]
+pub fn get_reading_list_detail(conn: &mut PgConnection, list: ReadingList, viewer_id: Option<Uuid>) -> Result<ReadingListDetail, diesel::result::Error> {
+    let items = list_reading_list_items(conn, list.id)?;
+    let blog_ids: Vec<Uuid> = items.iter().filter_map(|item| item.blog_id).collect();
+    let mut saved_blogs: HashMap<Uuid, Blog> = blogs::table
+        .filter(blogs::id.eq_any(&blog_ids))
+        .filter(readable_by(viewer_id))
+        .load::<Blog>(conn)?
+        .into_iter()
+        .map(|blog| (blog.id, blog))
+        .collect();
+
+    let items = items
+        .into_iter()
+        .map(|item| {
+            let blog = item.blog_id.and_then(|blog_id| saved_blogs.remove(&blog_id));
+            let show_title = blog.is_some() || viewer_id == Some(list.user_id);
+            ReadingListEntry {
+                id: item.id,
+                position: item.position,
+                added_at: item.added_at,
+                available: blog.is_some(),
+                blog_title: show_title.then_some(item.blog_title),
+                blog,
+            }
+        })
+        .collect();
+
+    Ok(ReadingListDetail { list, items })
+}
+
+/// Following a user twice is a no-op that returns the existing follow.
+/// Refused with `Forbidden` when either user has blocked the other.
+#[allow(dead_code)]
+pub fn follow_user(conn: &mut PgConnection, follower_id: Uuid, followee_id: Uuid) -> Result<Follow, AppError> {
+    if is_blocked(conn, followee_id, follower_id)? || is_blocked(conn, follower_id, followee_id)? {
+        return Err(AppError::Forbidden);
+    }
+
+    let inserted = diesel::insert_into(follows::table)
+        .values((
+            follows::follower_id.eq(follower_id),
+            follows::followee_id.eq(followee_id),
+        ))
+        .on_conflict((follows::follower_id, follows::followee_id))
+        .do_nothing()
+        .get_result::<Follow>(conn)
+        .optional()?;
+
+    match inserted {
+        Some(follow) => Ok(follow),
+        None => Ok(follows::table.find((follower_id, followee_id)).get_result::<Follow>(conn)?),
+    }
+}
+
+#[allow(dead_code)]
+pub fn unfollow_user(conn: &mut PgConnection, follower_id: Uuid, followee_id: Uuid) -> Result<usize, diesel::result::Error> {
+    diesel::delete(follows::table.find((follower_id, followee_id)))
+        .execute(conn)
+}
+
+#[allow(dead_code)]
+pub fn is_following(conn: &mut PgConnection, follower_id: Uuid, followee_id: Uuid) -> Result<bool, diesel::result::Error> {
+    diesel::select(diesel::dsl::exists(follows::table.find((follower_id, followee_id))))
+        .get_result(conn)
+}
+
+#[allow(dead_code)]
+pub fn count_followers(conn: &mut PgConnection, user_id: Uuid) -> Result<i64, diesel::result::Error> {
+    follows::table
+        .filter(follows::followee_id.eq(user_id))
+        .count()
+        .get_result(conn)
+}
+
+#[allow(dead_code)]
+pub fn count_following(conn: &mut PgConnection, user_id: Uuid) -> Result<i64, diesel::result::Error> {
+    follows::table
+        .filter(follows::follower_id.eq(user_id))
+        .count()
+        .get_result(conn)
+}
+
+#[allow(dead_code)]
+pub fn list_followers(conn: &mut PgConnection, user_id: Uuid, limit: i64, offset: i64) -> Result<Vec<FollowEntry>, diesel::result::Error> {
+    let rows = follows::table
+        .inner_join(users::table.on(users::id.eq(follows::follower_id)))
+        .filter(follows::followee_id.eq(user_id))
+        .order(follows::created_at.desc())
+        .limit(limit)
+        .offset(offset)
+        .select((users::id, users::username, follows::created_at))
+        .load::<(Uuid, String, chrono::DateTime<chrono::Utc>)>(conn)?;
+
+    Ok(rows.into_iter().map(follow_entry).collect())
+}
+
+#[allow(dead_code)]
+pub fn list_following(conn: &mut PgConnection, user_id: Uuid, limit: i64, offset: i64) -> Result<Vec<FollowEntry>, diesel::result::Error> {
+    let rows = follows::table
+        .inner_join(users::table.on(users::id.eq(follows::followee_id)))
+        .filter(follows::follower_id.eq(user_id))
+        .order(follows::created_at.desc())
+        .limit(limit)
+        .offset(offset)
+        .select((users::id, users::username, follows::created_at))
+        .load::<(Uuid, String, chrono::DateTime<chrono::Utc>)>(conn)?;
+
+    Ok(rows.into_iter().map(follow_entry).collect())
+}
+
+fn follow_entry((id, username, followed_at): (Uuid, String, chrono::DateTime<chrono::Utc>)) -> FollowEntry {
+    FollowEntry {
+        user: UserSummary { id, username },
+        followed_at,
+    }
+}
+
+/// Published blogs the authors `user_id` follows wrote or co-wrote, most
+/// recently published first, starting after `cursor`. Blogs owned by authors
+/// the user has muted or blocked, or by shadow-banned authors, are left out.
+#[allow(dead_code)]
+pub fn get_feed(conn: &mut PgConnection, user_id: Uuid, cursor: Option<Cursor>, limit: i64) -> Result<Vec<Blog>, diesel::result::Error> {
+    let published: &'static str = BlogStatus::Published.as_str();
+    let followed = follows::table
+        .filter(follows::follower_id.eq(user_id))
+        .select(follows::followee_id);
+    // Owners are credited in blog_authors too, so this covers co-authored blogs.
+    let by_followed = blog_authors::table
+        .filter(blog_authors::user_id.eq_any(followed))
+        .select(blog_authors::blog_id);
+
+    let muted = user_mutes::table
+        .filter(user_mutes::muter_id.eq(user_id))
+        .select(user_mutes::muted_id);
+    let blocked = user_blocks::table
+        .filter(user_blocks::blocker_id.eq(user_id))
+        .select(user_blocks::blocked_id);
+
+    let mut query = blogs::table
+        .filter(blogs::id.eq_any(by_followed))
+        .filter(blogs::status.eq(published))
+        .filter(blogs::published_at.is_not_null())
+        .filter(diesel::dsl::not(blogs::author_id.eq_any(muted)))
+        .filter(diesel::dsl::not(blogs::author_id.eq_any(blocked)))
+        .filter(diesel::dsl::not(blogs::author_id.eq_any(shadow_banned_users())))
+        .filter(listed_for(Some(user_id)))
+        .into_boxed();
+    if let Some(cursor) = cursor {
+        query = query.filter(
+            blogs::published_at.lt(cursor.at)
+                .or(blogs::published_at.eq(cursor.at).and(blogs::id.lt(cursor.id)))
+        );
+    }
+
+    query
+        .order((blogs::published_at.desc(), blogs::id.desc()))
+        .limit(limit)
+        .load::<Blog>(conn)
+}
+
+#[allow(dead_code)]
+pub fn is_blocked(conn: &mut PgConnection, blocker_id: Uuid, blocked_id: Uuid) -> Result<bool, diesel::result::Error> {
+    diesel::select(diesel::dsl::exists(user_blocks::table.find((blocker_id, blocked_id))))
+        .get_result(conn)
+}
+
+#[allow(dead_code)]
+pub fn is_muted(conn: &mut PgConnection, muter_id: Uuid, muted_id: Uuid) -> Result<bool, diesel::result::Error> {
+    diesel::select(diesel::dsl::exists(user_mutes::table.find((muter_id, muted_id))))
+        .get_result(conn)
+}
+
+/// Blocking also removes any follow between the two users, in both directions.
+#[allow(dead_code)]
+pub fn block_user(conn: &mut PgConnection, blocker_id: Uuid, blocked_id: Uuid) -> Result<UserBlock, diesel::result::Error> {
+    conn.transaction(|conn| {
+        diesel::insert_into(user_blocks::table)
+            .values((
+                user_blocks::blocker_id.eq(blocker_id),
+                user_blocks::blocked_id.eq(blocked_id),
+            ))
+            .on_conflict((user_blocks::blocker_id, user_blocks::blocked_id))
+            .do_nothing()
+            .execute(conn)?;
+
+        diesel::delete(follows::table.find((blocker_id, blocked_id))).execute(conn)?;
+        diesel::delete(follows::table.find((blocked_id, blocker_id))).execute(conn)?;
+
+        user_blocks::table.find((blocker_id, blocked_id)).get_result::<UserBlock>(conn)
+    })
+}
+
+#[allow(dead_code)]
+pub fn unblock_user(conn: &mut PgConnection, blocker_id: Uuid, blocked_id: Uuid) -> Result<usize, diesel::result::Error> {
+    diesel::delete(user_blocks::table.find((blocker_id, blocked_id)))
+        .execute(conn)
+}
+
+#[allow(dead_code)]
+pub fn list_blocked_users(conn: &mut PgConnection, blocker_id: Uuid) -> Result<Vec<UserSummary>, diesel::result::Error> {
+    let rows = user_blocks::table
+        .inner_join(users::table.on(users::id.eq(user_blocks::blocked_id)))
+        .filter(user_blocks::blocker_id.eq(blocker_id))
+        .order(user_blocks::created_at.desc())
+        .select((users::id, users::username))
+        .load::<(Uuid, String)>(conn)?;
+
+    Ok(rows.into_iter().map(|(id, username)| UserSummary { id, username }).collect())
+}
+
+#[allow(dead_code)]
+pub fn mute_user(conn: &mut PgConnection, muter_id: Uuid, muted_id: Uuid) -> Result<UserMute, diesel::result::Error> {
+    diesel::insert_into(user_mutes::table)
+        .values((
+            user_mutes::muter_id.eq(muter_id),
+            user_mutes::muted_id.eq(muted_id),
+        ))
+        .on_conflict((user_mutes::muter_id, user_mutes::muted_id))
+        .do_nothing()
+        .execute(conn)?;
+
+    user_mutes::table.find((muter_id, muted_id)).get_result::<UserMute>(conn)
+}
+
+#[allow(dead_code)]
+pub fn unmute_user(conn: &mut PgConnection, muter_id: Uuid, muted_id: Uuid) -> Result<usize, diesel::result::Error> {
+    diesel::delete(user_mutes::table.find((muter_id, muted_id)))
+        .execute(conn)
+}
+
+#[allow(dead_code)]
+pub fn list_muted_users(conn: &mut PgConnection, muter_id: Uuid) -> Result<Vec<UserSummary>, diesel::result::Error> {
+    let rows = user_mutes::table
+        .inner_join(users::table.on(users::id.eq(user_mutes::muted_id)))
+        .filter(user_mutes::muter_id.eq(muter_id))
+        .order(user_mutes::created_at.desc())
+        .select((users::id, users::username))
+        .load::<(Uuid, String)>(conn)?;
+
+    Ok(rows.into_iter().map(|(id, username)| UserSummary { id, username }).collect())
+}
+
+/// Changes a user's role and records the change in the audit log.
+#[allow(dead_code)]
+pub fn set_user_role(conn: &mut PgConnection, user_id: Uuid, role: Role, actor_id: Uuid) -> Result<User, diesel::result::Error> {
+    conn.transaction(|conn| {
+        let user = diesel::update(users::table.find(user_id))
+            .set(users::role.eq(role.as_str()))
+            .get_result::<User>(conn)?;
+        record_audit(conn, actor_id, "set_role", "user", user_id, None, Some(role.as_str()))?;
+        Ok(user)
+    })
+}
+
+#[allow(dead_code)]
+pub fn record_audit(conn: &mut PgConnection, actor_id: Uuid, action: &str, target_type: &str, target_id: Uuid, report_id: Option<Uuid>, notes: Option<&str>) -> Result<AuditLogEntry, diesel::result::Error> {
+    let new_entry = NewAuditLogEntry {
+        actor_id,
+        action,
+        target_type,
+        target_id,
+        report_id,
+        notes,
+    };
+
+    diesel::insert_into(audit_log::table)
+        .values(&new_entry)
+        .get_result(conn)
+}
+
+#[allow(dead_code)]
+pub fn create_report(conn: &mut PgConnection, reporter_id: Uuid, target: ReportTarget, target_id: Uuid, reason: ReportReason, details: Option<&str>) -> Result<Report, diesel::result::Error> {
+    let new_report = NewReport {
+        reporter_id,
+        target_type: target.as_str(),
+        target_id,
+        reason: reason.as_str(),
+        details,
+    };
+
+    diesel::insert_into(reports::table)
+        .values(&new_report)
+        .get_result(conn)
+}
+
+#[allow(dead_code)]
+pub fn get_report(conn: &mut PgConnection, report_id: Uuid) -> Result<Report, diesel::result::Error> {
+    reports::table.find(report_id).get_result::<Report>(conn)
+}
+
+/// The moderation queue: reports with `status`, oldest first.
+#[allow(dead_code)]
+pub fn list_reports(conn: &mut PgConnection, status: ReportStatus, assignee_id: Option<Uuid>, limit: i64, offset: i64) -> Result<Vec<Report>, diesel::result::Error> {
+    let mut query = reports::table
+        .filter(reports::status.eq(status.as_str()))
+        .into_boxed();
+    if let Some(assignee_id) = assignee_id {
+        query = query.filter(reports::assignee_id.eq(assignee_id));
+    }
+
+    query
+        .order((reports::created_at.asc(), reports::id.asc()))
+        .limit(limit)
+        .offset(offset)
+        .load::<Report>(conn)
+}
+
+#[allow(dead_code)]
+pub fn assign_report(conn: &mut PgConnection, report_id: Uuid, assignee_id: Option<Uuid>, actor_id: Uuid) -> Result<Report, diesel::result::Error> {
+    conn.transaction(|conn| {
+        let report = diesel::update(reports::table.find(report_id))
+            .set((
+                reports::assignee_id.eq(assignee_id),
+                reports::updated_at.eq(diesel::dsl::now),
+            ))
+            .get_result::<Report>(conn)?;
+        let action = if assignee_id.is_some() { "assign_report" } else { "unassign_report" };
+        record_audit(conn, actor_id, action, "report", report_id, Some(report_id), None)?;
+        Ok(report)
+    })
+}
+
+/// Closes a report as `status`, first carrying out `action` on its target.
+/// The action, or the resolution when there is none, is recorded in the audit
+/// log against the report.
+#[allow(dead_code)]
+pub fn resolve_report(conn: &mut PgConnection, report: &Report, moderator_id: Uuid, status: ReportStatus, notes: Option<&str>, action: Option<ModerationAction>, suspended_until: Option<chrono::DateTime<chrono::Utc>>) -> Result<Report, diesel::result::Error> {
+    conn.transaction(|conn| {
+        match action {
+            Some(ModerationAction::RemoveContent) => {
+                match report.target_type.as_str() {
+                    "blog" => delete_blog(conn, report.target_id)?,
+                    "comment" => delete_comment(conn, report.target_id)?,
+                    _ => 0,
+                };
+                record_audit(conn, moderator_id, ModerationAction::RemoveContent.as_str(), &report.target_type, report.target_id, Some(report.id), notes)?;
+            }
+            // set_account_status writes its own audit entry.
+            Some(ModerationAction::SuspendUser) => {
+                let user_id = report_target_user(conn, report)?;
+                set_account_status(conn, user_id, AccountStatus::Suspended, notes, suspended_until, moderator_id, Some(report.id))?;
+            }
+            Some(ModerationAction::BanUser) => {
+                let user_id = report_target_user(conn, report)?;
+                set_account_status(conn, user_id, AccountStatus::Banned, notes, None, moderator_id, Some(report.id))?;
+            }
+            None => {
+                let resolution = match status {
+                    ReportStatus::Dismissed => "dismiss_report",
+                    _ => "resolve_report",
+                };
+                record_audit(conn, moderator_id, resolution, &report.target_type, report.target_id, Some(report.id), notes)?;
+            }
+        }
+
+        diesel::update(reports::table.find(report.id))
+            .set((
+                reports::status.eq(status.as_str()),
+                reports::resolution_notes.eq(notes),
+                reports::resolved_by.eq(moderator_id),
+                reports::resolved_at.eq(diesel::dsl::now),
+                reports::updated_at.eq(diesel::dsl::now),
+            ))
+            .get_result::<Report>(conn)
+    })
+}
+
+/// The user responsible for a report's target: the reported user, or the
+/// author of the reported blog or comment.
+#[allow(dead_code)]
+pub fn report_target_user(conn: &mut PgConnection, report: &Report) -> Result<Uuid, diesel::result::Error> {
+    match report.target_type.as_str() {
+        "blog" => get_blog(conn, report.target_id).map(|blog| blog.author_id),
+        "comment" => get_comment(conn, report.target_id).map(|comment| comment.user_id),
+        _ => Ok(report.target_id),
+    }
+}
+
+/// Sets a user's account status and records the change in the audit log,
+/// linked to `report_id` when it was taken from a report.
+#[allow(dead_code)]
+pub fn set_account_status(conn: &mut PgConnection, user_id: Uuid, status: AccountStatus, reason: Option<&str>, expires_at: Option<chrono::DateTime<chrono::Utc>>, actor_id: Uuid, report_id: Option<Uuid>) -> Result<User, diesel::result::Error> {
+    conn.transaction(|conn| {
+        let user = diesel::update(users::table.find(user_id))
+            .set((
+                users::status.eq(status.as_str()),
+                users::status_reason.eq(reason),
+                users::status_expires_at.eq(expires_at),
+            ))
+            .get_result::<User>(conn)?;
+
+        let action = match status {
+            AccountStatus::Active => "restore_user",
+            AccountStatus::Suspended => "suspend_user",
+            AccountStatus::Banned => "ban_user",
+            AccountStatus::ShadowBanned => "shadow_ban_user",
+        };
+        record_audit(conn, actor_id, action, "user", user_id, report_id, reason)?;
+        Ok(user)
+    })
+}
+
+#[allow(dead_code)]
+pub fn get_report_detail(conn: &mut PgConnection, report: Report) -> Result<ReportDetail, diesel::result::Error> {
+    let actions = audit_log::table
+        .filter(audit_log::report_id.eq(report.id))
+        .order(audit_log::created_at.asc())
+        .load::<AuditLogEntry>(conn)?;
+
+    Ok(ReportDetail { report, actions })
+}
+
+/// Turns a clash on `publications_slug_key` into a client error.
+fn publication_write_error(error: diesel::result::Error) -> AppError {
+    match error {
+        diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _) => {
+            AppError::BadRequest("slug is already taken".to_string())
+        }
+        other => AppError::from(other),
+    }
+}
+
+/// Creates a publication with `owner_id` as its first owner.
+#[allow(dead_code)]
+pub fn create_publication(conn: &mut PgConnection, request: &PublicationRequest, owner_id: Uuid) -> Result<Publication, AppError> {
+    conn.transaction(|conn| {
+        let publication = diesel::insert_into(publications::table)
+            .values(NewPublication::from_request(request, true))
+            .get_result::<Publication>(conn)
+            .map_err(publication_write_error)?;
+
+        diesel::insert_into(publication_members::table)
+            .values(NewPublicationMember {
+                publication_id: publication.id,
+                user_id: owner_id,
+                role: PublicationRole::Owner.as_str(),
+            })
+            .execute(conn)?;
+
+        Ok(publication)
+    })
+}
+
+#[allow(dead_code)]
+pub fn get_publication(conn: &mut PgConnection, publication_id: Uuid) -> Result<Publication, diesel::result::Error> {
+    publications::table.find(publication_id).get_result::<Publication>(conn)
+}
+
+#[allow(dead_code)]
+pub fn update_publication(conn: &mut PgConnection, publication: &Publication, request: &PublicationRequest) -> Result<Publication, AppError> {
+    diesel::update(publications::table.find(publication.id))
+        .set((
+            NewPublication::from_request(request, publication.require_review),
+            publications::updated_at.eq(diesel::dsl::now),
+        ))
+        .get_result::<Publication>(conn)
+        .map_err(publication_write_error)
+}
+
+#[allow(dead_code)]
+pub fn delete_publication(conn: &mut PgConnection, publication_id: Uuid) -> Result<usize, diesel::result::Error> {
+    diesel::delete(publications::table.find(publication_id)).execute(conn)
+}
+
+/// The role `user_id` holds in the publication, if they are a member.
+#[allow(dead_code)]
+pub fn publication_role(conn: &mut PgConnection, publication_id: Uuid, user_id: Uuid) -> Result<Option<PublicationRole>, diesel::result::Error> {
+    let role = publication_members::table
+        .find((publication_id, user_id))
+        .select(publication_members::role)
+        .get_result::<String>(conn)
+        .optional()?;
+
+    Ok(role.as_deref().and_then(PublicationRole::parse))
+}
+
+#[allow(dead_code)]
+pub fn list_publication_members(conn: &mut PgConnection, publication_id: Uuid) -> Result<Vec<PublicationMember>, diesel::result::Error> {
+    let rows = publication_members::table
+        .inner_join(users::table.on(users::id.eq(publication_members::user_id)))
+        .filter(publication_members::publication_id.eq(publication_id))
+        .order((publication_members::created_at.asc(), users::id.asc()))
+        .select((users::id, users::username, publication_members::role, publication_members::created_at))
+        .load::<(Uuid, String, String, chrono::DateTime<chrono::Utc>)>(conn)?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(id, username, role, joined_at)| {
+            PublicationRole::parse(&role).map(|role| PublicationMember {
+                user: UserSummary { id, username },
+                role,
+                joined_at,
+            })
+        })
+        .collect())
+}
+
+/// Whether `user_id` is the only owner left. Locks the owner rows so two
+/// owners can't step down at the same time.
+fn is_last_owner(conn: &mut PgConnection, publication_id: Uuid, user_id: Uuid) -> Result<bool, diesel::result::Error> {
+    let owners = publication_members::table
+        .filter(publication_members::publication_id.eq(publication_id))
+        .filter(publication_members::role.eq(PublicationRole::Owner.as_str()))
+        .select(publication_members::user_id)
+        .for_update()
+        .load::<Uuid>(conn)?;
+
+    Ok(owners == [user_id])
+}
+
+/// Adds `user_id` to the publication or changes their role.
+#[allow(dead_code)]
+pub fn set_publication_member(conn: &mut PgConnection, publication_id: Uuid, user_id: Uuid, role: PublicationRole) -> Result<(), AppError> {
+    conn.transaction(|conn| {
+        if role != PublicationRole::Owner && is_last_owner(conn, publication_id, user_id)? {
+            return Err(AppError::BadRequest("a publication needs at least one owner".to_string()));
+        }
+
+        diesel::insert_into(publication_members::table)
+            .values(NewPublicationMember { publication_id, user_id, role: role.as_str() })
+            .on_conflict((publication_members::publication_id, publication_members::user_id))
+            .do_update()
+            .set(publication_members::role.eq(role.as_str()))
+            .execute(conn)?;
+
+        Ok(())
+    })
+}
+
+#[allow(dead_code)]
+pub fn remove_publication_member(conn: &mut PgConnection, publication_id: Uuid, user_id: Uuid) -> Result<usize, AppError> {
+    conn.transaction(|conn| {
+        if is_last_owner(conn, publication_id, user_id)? {
+            return Err(AppError::BadRequest("a publication needs at least one owner".to_string()));
+        }
+
+        Ok(diesel::delete(publication_members::table.find((publication_id, user_id))).execute(conn)?)
+    })
+}
+
+#[allow(dead_code)]
+pub fn set_blog_publication(conn: &mut PgConnection, blog_id: Uuid, publication_id: Option<Uuid>) -> Result<Blog, diesel::result::Error> {
+    diesel::update(blogs::table.find(blog_id))
+        .set(blogs::publication_id.eq(publication_id))
+        .get_result::<Blog>(conn)
+}
+
+#[allow(dead_code)]
+pub fn is_blog_author(conn: &mut PgConnection, blog_id: Uuid, user_id: Uuid) -> Result<bool, diesel::result::Error> {
+    diesel::select(diesel::dsl::exists(blog_authors::table.find((blog_id, user_id))))
+        .get_result(conn)
+}
+
+/// Ids of the blogs tagged `tag`.
+fn tagged_blogs(tag: &str) -> blog_tags::BoxedQuery<'_, diesel::pg::Pg, diesel::sql_types::Uuid> {
+    blog_tags::table
+        .filter(blog_tags::tag.eq(tag))
+        .select(blog_tags::blog_id)
+        .into_boxed()
+}
+
+/// Tags of each blog in alphabetical order, keyed by blog.
+#[allow(dead_code)]
+pub fn list_blog_tags(conn: &mut PgConnection, blog_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<String>>, diesel::result::Error> {
+    let rows = blog_tags::table
+        .filter(blog_tags::blog_id.eq_any(blog_ids))
+        .order((blog_tags::blog_id, blog_tags::tag.asc()))
+        .select((blog_tags::blog_id, blog_tags::tag))
+        .load::<(Uuid, String)>(conn)?;
+
+    let mut tags: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for (blog_id, tag) in rows {
+        tags.entry(blog_id).or_default().push(tag);
+    }
+    Ok(tags)
+}
+
+/// Replaces the blog's tags with `tags`, which must already be normalized.
+/// Tags it keeps are left untouched. Returns the new tags in alphabetical order.
+#[allow(dead_code)]
+pub fn set_blog_tags(conn: &mut PgConnection, blog_id: Uuid, tags: &[String]) -> Result<Vec<String>, diesel::result::Error> {
+    conn.transaction(|conn| {
+        diesel::delete(
+            blog_tags::table
+                .filter(blog_tags::blog_id.eq(blog_id))
+                .filter(diesel::dsl::not(blog_tags::tag.eq_any(tags)))
+        )
+        .execute(conn)?;
+
+        let rows: Vec<_> = tags
+            .iter()
+            .map(|tag| (blog_tags::blog_id.eq(blog_id), blog_tags::tag.eq(tag)))
+            .collect();
+        diesel::insert_into(blog_tags::table)
+            .values(&rows)
+            .on_conflict_do_nothing()
+            .execute(conn)?;
+
+        Ok(list_blog_tags(conn, &[blog_id])?.remove(&blog_id).unwrap_or_default())
+    })
+}
+
+/// Authors of each blog in byline order, keyed by blog.
+#[allow(dead_code)]
+pub fn list_blog_authors(conn: &mut PgConnection, blog_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<BlogAuthor>>, diesel::result::Error> {
+    let rows = blog_authors::table
+        .inner_join(users::table.on(users::id.eq(blog_authors::user_id)))
+        .filter(blog_authors::blog_id.eq_any(blog_ids))
+        .order((blog_authors::blog_id, blog_authors::position.asc(), blog_authors::created_at.asc()))
+        .select((blog_authors::blog_id, users::id, users::username, blog_authors::role, blog_authors::position))
+        .load::<(Uuid, Uuid, String, String, i32)>(conn)?;
+
+    let mut authors: HashMap<Uuid, Vec<BlogAuthor>> = HashMap::new();
+    for (blog_id, id, username, role, position) in rows {
+        authors.entry(blog_id).or_default().push(BlogAuthor {
+            user: UserSummary { id, username },
+            role: AuthorRole::parse(&role).unwrap_or_default(),
+            position,
+        });
+    }
+    Ok(authors)
+}
+
+/// Sends a notification unless the recipient caused it, has muted the actor,
+/// or either of them has blocked the other.
+#[allow(dead_code)]
+pub fn notify(conn: &mut PgConnection, user_id: Uuid, actor_id: Option<Uuid>, kind: NotificationKind, blog_id: Option<Uuid>, data: serde_json::Value) -> Result<Option<Notification>, diesel::result::Error> {
+    if let Some(actor_id) = actor_id {
+        if actor_id == user_id {
+            return Ok(None);
+        }
+        if is_muted(conn, user_id, actor_id)? || is_blocked(conn, user_id, actor_id)? || is_blocked(conn, actor_id, user_id)? {
+            return Ok(None);
+        }
+    }
+
+    diesel::insert_into(notifications::table)
+        .values(NewNotification {
+            user_id,
+            actor_id,
+            kind: kind.as_str(),
+            blog_id,
+            data,
+        })
+        .get_result::<Notification>(conn)
+        .map(Some)
+}
+
+/// Notifies every author of the blog except `actor_id`.
+#[allow(dead_code)]
+pub fn notify_blog_authors(conn: &mut PgConnection, blog_id: Uuid, actor_id: Uuid, kind: NotificationKind, data: serde_json::Value) -> Result<(), diesel::result::Error> {
+    let author_ids = blog_authors::table
+        .filter(blog_authors::blog_id.eq(blog_id))
+        .select(blog_authors::user_id)
+        .load::<Uuid>(conn)?;
+
+    for author_id in author_ids {
+        notify(conn, author_id, Some(actor_id), kind, Some(blog_id), data.clone())?;
+    }
+    Ok(())
+}
+
+/// A page of the user's notifications, newest first.
+#[allow(dead_code)]
+pub fn list_notifications(conn: &mut PgConnection, user_id: Uuid, unread_only: bool, cursor: Option<Cursor>, limit: i64) -> Result<NotificationPage, diesel::result::Error> {
+    let mut query = notifications::table
+        .filter(notifications::user_id.eq(user_id))
+        .into_boxed();
+    if unread_only {
+        query = query.filter(notifications::read_at.is_null());
+    }
+    if let Some(cursor) = cursor {
+        query = query.filter(
+            notifications::created_at.lt(cursor.at)
+                .or(notifications::created_at.eq(cursor.at).and(notifications::id.lt(cursor.id)))
+        );
+    }
+
+    // Fetch one extra row to learn whether there is a next page.
+    let mut page = query
+        .order((notifications::created_at.desc(), notifications::id.desc()))
+        .limit(limit + 1)
+        .load::<Notification>(conn)?;
+    let next_cursor = if page.len() as i64 > limit {
+        page.truncate(limit as usize);
+        page.last().map(|notification| Cursor { at: notification.created_at, id: notification.id }.encode())
+    } else {
+        None
+    };
+
+    let unread_count = notifications::table
+        .filter(notifications::user_id.eq(user_id))
+        .filter(notifications::read_at.is_null())
+        .count()
+        .get_result::<i64>(conn)?;
+
+    Ok(NotificationPage { notifications: page, unread_count, next_cursor })
+}
+
+/// Marks one of the user's notifications read, or all of them when `notification_id` is `None`.
+#[allow(dead_code)]
+pub fn mark_notifications_read(conn: &mut PgConnection, user_id: Uuid, notification_id: Option<Uuid>) -> Result<usize, diesel::result::Error> {
+    let mut query = diesel::update(notifications::table)
+        .filter(notifications::user_id.eq(user_id))
+        .filter(notifications::read_at.is_null())
+        .into_boxed();
+    if let Some(notification_id) = notification_id {
+        query = query.filter(notifications::id.eq(notification_id));
+    }
+
+    query.set(notifications::read_at.eq(diesel::dsl::now)).execute(conn)
+}
+
+/// Invites `invitee_id` to co-author the blog and notifies them.
+#[allow(dead_code)]
+pub fn create_author_invitation(conn: &mut PgConnection, blog_id: Uuid, invitee_id: Uuid, inviter_id: Uuid, role: AuthorRole) -> Result<AuthorInvitation, AppError> {
+    if is_blog_author(conn, blog_id, invitee_id)? {
+        return Err(AppError::BadRequest("user is already an author of this blog".to_string()));
+    }
+    if is_blocked(conn, invitee_id, inviter_id)? || is_blocked(conn, inviter_id, invitee_id)? {
+        return Err(AppError::Forbidden);
+    }
+
+    conn.transaction(|conn| {
+        let invitation = diesel::insert_into(blog_author_invitations::table)
+            .values(NewAuthorInvitation { blog_id, invitee_id, inviter_id, role: role.as_str() })
+            .get_result::<AuthorInvitation>(conn)
+            .map_err(|error| match error {
+                diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _) => {
+                    AppError::BadRequest("user already has a pending invitation".to_string())
+                }
+                other => AppError::from(other),
+            })?;
+        notify(conn, invitee_id, Some(inviter_id), NotificationKind::CoauthorInvited, Some(blog_id), serde_json::json!({ "invitation_id": invitation.id }))?;
+        Ok(invitation)
+    })
+}
+
+#[allow(dead_code)]
+pub fn get_author_invitation(conn: &mut PgConnection, invitation_id: Uuid) -> Result<AuthorInvitation, diesel::result::Error> {
+    blog_author_invitations::table.find(invitation_id).get_result::<AuthorInvitation>(conn)
+}
+
+#[allow(dead_code)]
+pub fn list_pending_invitations(conn: &mut PgConnection, invitee_id: Uuid) -> Result<Vec<AuthorInvitation>, diesel::result::Error> {
+    blog_author_invitations::table
+        .filter(blog_author_invitations::invitee_id.eq(invitee_id))
+        .filter(blog_author_invitations::status.eq(InvitationStatus::Pending.as_str()))
+        .order(blog_author_invitations::created_at.desc())
+        .load::<AuthorInvitation>(conn)
+}
+
+#[allow(dead_code)]
+pub fn list_blog_invitations(conn: &mut PgConnection, blog_id: Uuid) -> Result<Vec<AuthorInvitation>, diesel::result::Error> {
+    blog_author_invitations::table
+        .filter(blog_author_invitations::blog_id.eq(blog_id))
+        .filter(blog_author_invitations::status.eq(InvitationStatus::Pending.as_str()))
+        .order(blog_author_invitations::created_at.asc())
+        .load::<AuthorInvitation>(conn)
+}
+
+/// Accepts or declines a pending invitation. Accepting adds the invitee at
+/// the end of the byline; either way the inviter is notified.
+#[allow(dead_code)]
+pub fn respond_to_invitation(conn: &mut PgConnection, invitation: &AuthorInvitation, accept: bool) -> Result<AuthorInvitation, AppError> {
+    let status = if accept { InvitationStatus::Accepted } else { InvitationStatus::Declined };
+
+    conn.transaction(|conn| {
+        let updated = diesel::update(
+            blog_author_invitations::table
+                .find(invitation.id)
+                .filter(blog_author_invitations::status.eq(InvitationStatus::Pending.as_str()))
+        )
+        .set((
+            blog_author_invitations::status.eq(status.as_str()),
+            blog_author_invitations::responded_at.eq(diesel::dsl::now),
+        ))
+        .get_result::<AuthorInvitation>(conn)
+        .optional()?
+        .ok_or_else(|| AppError::BadRequest("invitation is no longer pending".to_string()))?;
+
+        if accept {
+            let last_position = blog_authors::table
+                .filter(blog_authors::blog_id.eq(invitation.blog_id))
+                .select(diesel::dsl::max(blog_authors::position))
+                .get_result::<Option<i32>>(conn)?;
+            diesel::insert_into(blog_authors::table)
+                .values(NewBlogAuthor {
+                    blog_id: invitation.blog_id,
+                    user_id: invitation.invitee_id,
+                    position: last_position.map_or(0, |position| position + 1),
+                    role: &invitation.role,
+                })
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+        }
+
+        if let Some(inviter_id) = invitation.inviter_id {
+            let kind = if accept { NotificationKind::InvitationAccepted } else { NotificationKind::InvitationDeclined };
+            notify(conn, inviter_id, Some(invitation.invitee_id), kind, Some(invitation.blog_id), serde_json::json!({ "invitation_id": invitation.id }))?;
+        }
+
+        Ok(updated)
+    })
+}
+
+#[allow(dead_code)]
+pub fn revoke_invitation(conn: &mut PgConnection, blog_id: Uuid, invitation_id: Uuid) -> Result<usize, diesel::result::Error> {
+    diesel::update(
+        blog_author_invitations::table
+            .find(invitation_id)
+            .filter(blog_author_invitations::blog_id.eq(blog_id))
+            .filter(blog_author_invitations::status.eq(InvitationStatus::Pending.as_str()))
+    )
+    .set((
+        blog_author_invitations::status.eq(InvitationStatus::Revoked.as_str()),
+        blog_author_invitations::responded_at.eq(diesel::dsl::now),
+    ))
+    .execute(conn)
+}
+
+/// Removes a co-author. The blog's owner can't be removed this way.
+#[allow(dead_code)]
+pub fn remove_blog_author(conn: &mut PgConnection, blog: &Blog, user_id: Uuid) -> Result<usize, AppError> {
+    if user_id == blog.author_id {
+        return Err(AppError::BadRequest("the blog's owner cannot be removed as an author".to_string()));
+    }
+    Ok(diesel::delete(blog_authors::table.find((blog.id, user_id))).execute(conn)?)
+}
+
+#[allow(dead_code)]
+pub fn reorder_blog_authors(conn: &mut PgConnection, blog_id: Uuid, user_ids: &[Uuid]) -> Result<(), diesel::result::Error> {
+    conn.transaction(|conn| {
+        for (position, user_id) in user_ids.iter().enumerate() {
+            diesel::update(blog_authors::table.find((blog_id, user_id)))
+                .set(blog_authors::position.eq(position as i32))
+                .execute(conn)?;
+        }
+        Ok(())
+    })
+}
+
+/// Hands the blogs to `to_user_id` and/or moves them into `to_publication_id`,
+/// all or nothing. The new owner heads the byline and the previous owner stays
+/// credited. Each blog gets an audit entry, and the previous and new owners
+/// each get one notification.
+#[allow(dead_code)]
+pub fn transfer_blogs(conn: &mut PgConnection, blog_ids: &[Uuid], to_user_id: Option<Uuid>, to_publication_id: Option<Uuid>, actor_id: Uuid) -> Result<Vec<Blog>, AppError> {
+    conn.transaction(|conn| {
+        let blogs = blogs::table
+            .filter(blogs::id.eq_any(blog_ids))
+            .for_update()
+            .load::<Blog>(conn)?;
+
+        let mut transferred = Vec::with_capacity(blogs.len());
+        let mut previous_owners: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for blog in blogs {
+            let owner_id = to_user_id.unwrap_or(blog.author_id);
+            let publication_id = to_publication_id.or(blog.publication_id);
+            if let Some(publication_id) = to_publication_id {
+                if publication_role(conn, publication_id, owner_id)?.is_none() {
+                    return Err(AppError::BadRequest("the new owner must be a member of the publication".to_string()));
+                }
+            }
+
+            let updated = diesel::update(blogs::table.find(blog.id))
+                .set((
+                    blogs::author_id.eq(owner_id),
+                    blogs::publication_id.eq(publication_id),
+                    // Authors can't review their own blogs
+                    blogs::reviewer_id.eq(blog.reviewer_id.filter(|reviewer_id| *reviewer_id != owner_id)),
+                ))
+                .get_result::<Blog>(conn)?;
+
+            if owner_id != blog.author_id {
+                diesel::update(blog_authors::table.filter(blog_authors::blog_id.eq(blog.id)))
+                    .set(blog_authors::position.eq(blog_authors::position + 1))
+                    .execute(conn)?;
+                diesel::insert_into(blog_authors::table)
+                    .values(NewBlogAuthor {
+                        blog_id: blog.id,
+                        user_id: owner_id,
+                        position: 0,
+                        role: AuthorRole::Author.as_str(),
+                    })
+                    .on_conflict((blog_authors::blog_id, blog_authors::user_id))
+                    .do_update()
+                    .set((
+                        blog_authors::position.eq(0),
+                        blog_authors::role.eq(AuthorRole::Author.as_str()),
+                    ))
+                    .execute(conn)?;
+            }
+
+            let notes = format!(
+                "owner {} -> {}, publication {} -> {}",
+                blog.author_id,
+                owner_id,
+                blog.publication_id.map_or("none".to_string(), |id| id.to_string()),
+                publication_id.map_or("none".to_string(), |id| id.to_string()),
+            );
+            record_audit(conn, actor_id, "transfer_blog", "blog", blog.id, None, Some(&notes))?;
+
+            previous_owners.entry(blog.author_id).or_default().push(blog.id);
+            transferred.push(updated);
+        }
+
+        let data = |blog_ids: &[Uuid]| serde_json::json!({
+            "blog_ids": blog_ids,
+            "to_user_id": to_user_id,
+            "to_publication_id": to_publication_id,
+        });
+        let single_blog = |blog_ids: &[Uuid]| match blog_ids {
+            [blog_id] => Some(*blog_id),
+            _ => None,
+        };
+        for (previous_owner, blog_ids) in &previous_owners {
+            if Some(*previous_owner) != to_user_id {
+                notify(conn, *previous_owner, Some(actor_id), NotificationKind::BlogTransferred, single_blog(blog_ids), data(blog_ids))?;
+            }
+        }
+        if let Some(to_user_id) = to_user_id {
+            let received: Vec<Uuid> = previous_owners
+                .iter()
+                .filter(|(previous_owner, _)| **previous_owner != to_user_id)
+                .flat_map(|(_, blog_ids)| blog_ids.iter().copied())
+                .collect();
+            if !received.is_empty() {
+                notify(conn, to_user_id, Some(actor_id), NotificationKind::BlogTransferred, single_blog(&received), data(&received))?;
+            }
+        }
+
+        Ok(transferred)
+    })
+}
+
+#[allow(dead_code)]
+pub fn list_owned_blog_ids(conn: &mut PgConnection, author_id: Uuid) -> Result<Vec<Uuid>, diesel::result::Error> {
+    blogs::table
+        .filter(blogs::author_id.eq(author_id))
+        .select(blogs::id)
+        .load::<Uuid>(conn)
+}
+
+#[allow(dead_code)]
+pub fn create_series(conn: &mut PgConnection, owner_id: Uuid, request: &SeriesRequest) -> Result<Series, diesel::result::Error> {
+    diesel::insert_into(series::table)
+        .values(NewSeries {
+            owner_id,
+            publication_id: request.publication_id,
+            title: request.title.trim(),
+            description: request.description.as_deref(),
+        })
+        .get_result::<Series>(conn)
+}
+
+#[allow(dead_code)]
+pub fn get_series(conn: &mut PgConnection, series_id: Uuid) -> Result<Series, diesel::result::Error> {
+    series::table.find(series_id).get_result::<Series>(conn)
+}
+
+#[allow(dead_code)]
+pub fn update_series(conn: &mut PgConnection, series_id: Uuid, title: &str, description: Option<&str>) -> Result<Series, diesel::result::Error> {
+    diesel::update(series::table.find(series_id))
+        .set((
+            series::title.eq(title),
+            series::description.eq(description),
+            series::updated_at.eq(diesel::dsl::now),
+        ))
+        .get_result::<Series>(conn)
+}
+
+/// Deletes the series; its blogs are kept.
+#[allow(dead_code)]
+pub fn delete_series(conn: &mut PgConnection, series_id: Uuid) -> Result<usize, diesel::result::Error> {
+    diesel::delete(series::table.find(series_id)).execute(conn)
+}
+
+#[allow(dead_code)]
+pub fn list_series(conn: &mut PgConnection, owner_id: Option<Uuid>, publication_id: Option<Uuid>, limit: i64, offset: i64) -> Result<Vec<Series>, diesel::result::Error> {
+    let mut query = series::table.into_boxed();
+    if let Some(owner_id) = owner_id {
+        query = query.filter(series::owner_id.eq(owner_id));
+    }
+    if let Some(publication_id) = publication_id {
+        query = query.filter(series::publication_id.eq(publication_id));
+    }
+
+    query
+        .order((series::created_at.desc(), series::id.desc()))
+        .limit(limit)
+        .offset(offset)
+        .load::<Series>(conn)
+}
+
+/// Every blog of the series in order, whether or not a viewer can read it.
+#[allow(dead_code)]
+pub fn list_series_blog_ids(conn: &mut PgConnection, series_id: Uuid) -> Result<Vec<Uuid>, diesel::result::Error> {
+    series_blogs::table
+        .filter(series_blogs::series_id.eq(series_id))
+        .order((series_blogs::position.asc(), series_blogs::created_at.asc()))
+        .select(series_blogs::blog_id)
+        .load::<Uuid>(conn)
+}
+
+/// Appends the blog to the series. A blog already in a series is a client error.
+#[allow(dead_code)]
+pub fn add_blog_to_series(conn: &mut PgConnection, series_id: Uuid, blog_id: Uuid) -> Result<(), AppError> {
+    conn.transaction(|conn| {
+        let last_position = series_blogs::table
+            .filter(series_blogs::series_id.eq(series_id))
+            .select(diesel::dsl::max(series_blogs::position))
+            .get_result::<Option<i32>>(conn)?;
+
+        diesel::insert_into(series_blogs::table)
+            .values(NewSeriesBlog {
+                series_id,
+                blog_id,
+                position: last_position.map_or(0, |position| position + 1),
+            })
+            .execute(conn)
+            .map_err(|error| match error {
+                diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _) => {
+                    AppError::BadRequest("blog is already in a series".to_string())
+                }
+                other => AppError::from(other),
+            })?;
+
+        diesel::update(series::table.find(series_id))
+            .set(series::updated_at.eq(diesel::dsl::now))
+            .execute(conn)?;
+
+        Ok(())
+    })
+}
+
+#[allow(dead_code)]
+pub fn remove_blog_from_series(conn: &mut PgConnection, series_id: Uuid, blog_id: Uuid) -> Result<usize, diesel::result::Error> {
+    diesel::delete(series_blogs::table.find((series_id, blog_id))).execute(conn)
+}
+
+#[allow(dead_code)]
+pub fn reorder_series(conn: &mut PgConnection, series_id: Uuid, blog_ids: &[Uuid]) -> Result<(), diesel::result::Error> {
+    conn.transaction(|conn| {
+        for (position, blog_id) in blog_ids.iter().enumerate() {
+            diesel::update(series_blogs::table.find((series_id, blog_id)))
+                .set(series_blogs::position.eq(position as i32))
+                .execute(conn)?;
+        }
+
+        diesel::update(series::table.find(series_id))
+            .set(series::updated_at.eq(diesel::dsl::now))
+            .execute(conn)?;
+
+        Ok(())
+    })
+}
+
+/// Blogs of the series `viewer_id` can open without a password, in order.
+fn readable_series_blogs(conn: &mut PgConnection, series_id: Uuid, viewer_id: Option<Uuid>) -> Result<Vec<Blog>, diesel::result::Error> {
+    series_blogs::table
+        .inner_join(blogs::table.on(blogs::id.eq(series_blogs::blog_id)))
+        .filter(series_blogs::series_id.eq(series_id))
+        .filter(readable_by(viewer_id))
+        .order((series_blogs::position.asc(), series_blogs::created_at.asc()))
+        .select(blogs::all_columns)
+        .load::<Blog>(conn)
+}
+
+/// The series with the parts the viewer can read.
+#[allow(dead_code)]
+pub fn get_series_detail(conn: &mut PgConnection, series: Series, viewer_id: Option<Uuid>) -> Result<SeriesDetail, diesel::result::Error> {
+    let blogs = readable_series_blogs(conn, series.id, viewer_id)?;
+    let blogs = get_blog_responses(conn, blogs, viewer_id)?;
+
+    Ok(SeriesDetail { series, blogs })
+}
+
+/// Where the blog sits in its series, counting only parts the viewer can read.
+#[allow(dead_code)]
+pub fn get_series_nav(conn: &mut PgConnection, blog_id: Uuid, viewer_id: Option<Uuid>) -> Result<Option<SeriesNav>, diesel::result::Error> {
+    let Some(series) = series_blogs::table
+        .inner_join(series::table.on(series::id.eq(series_blogs::series_id)))
+        .filter(series_blogs::blog_id.eq(blog_id))
+        .select(series::all_columns)
+        .get_result::<Series>(conn)
+        .optional()?
+    else {
+        return Ok(None);
+    };
+
+    let parts = readable_series_blogs(conn, series.id, viewer_id)?;
+    let Some(index) = parts.iter().position(|part| part.id == blog_id) else {
+        return Ok(None);
+    };
+    let link = |blog: &Blog| SeriesLink { id: blog.id, title: blog.title.clone() };
+
+    Ok(Some(SeriesNav {
+        id: series.id,
+        title: series.title,
+        part: index + 1,
+        total_parts: parts.len(),
+        previous: index.checked_sub(1).map(|previous| link(&parts[previous])),
+        next: parts.get(index + 1).map(link),
+    }))
+}
+
+/// Serves each blog in the first of the `preferred` locales it is available in,
+/// falling back to the original.
+#[allow(dead_code)]
+pub fn localize_blog_responses(conn: &mut PgConnection, responses: &mut [BlogResponse], preferred: &[Locale]) -> Result<(), diesel::result::Error> {
+    let chosen: HashMap<Uuid, &'static str> = responses
+        .iter()
+        .filter_map(|response| {
+            let locale = preferred.iter().find(|locale| response.available_locales.iter().any(|available| available == locale.as_str()))?;
+            (locale.as_str() != response.blog.locale).then_some((response.blog.id, locale.as_str()))
+        })
+        .collect();
+    if chosen.is_empty() {
+        return Ok(());
+    }
+
+    let blog_ids: Vec<Uuid> = chosen.keys().copied().collect();
+    let mut translations: HashMap<Uuid, BlogTranslation> = blog_translations::table
+        .filter(blog_translations::blog_id.eq_any(&blog_ids))
+        .load::<BlogTranslation>(conn)?
+        .into_iter()
+        .filter(|translation| chosen.get(&translation.blog_id) == Some(&translation.locale.as_str()))
+        .map(|translation| (translation.blog_id, translation))
+        .collect();
+
+    for response in responses.iter_mut() {
+        if let Some(translation) = translations.remove(&response.blog.id) {
+            let stats = content::analyze(&translation.content);
+            response.blog.word_count = stats.word_count;
+            response.blog.reading_time_minutes = stats.reading_time_minutes;
+            response.blog.excerpt = stats.excerpt.clone();
+            response.blog.excerpt_is_manual = false;
+            response.blog.toc = toc_json(&stats);
+            response.blog.title = translation.title;
+            response.blog.content = translation.content;
+            response.blog.locale = translation.locale;
+            response.slug = Some(translation.slug);
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(dead_code)]
+pub fn list_blog_translations(conn: &mut PgConnection, blog_id: Uuid) -> Result<Vec<BlogTranslation>, diesel::result::Error> {
+    blog_translations::table
+        .filter(blog_translations::blog_id.eq(blog_id))
+        .order(blog_translations::locale.asc())
+        .load::<BlogTranslation>(conn)
+}
+
+/// Creates or replaces the blog's translation into `locale`.
+#[allow(dead_code)]
+pub fn upsert_blog_translation(conn: &mut PgConnection, blog_id: Uuid, locale: Locale, request: &TranslationRequest) -> Result<BlogTranslation, AppError> {
+    let new_translation = NewBlogTranslation {
+        blog_id,
+        locale: locale.as_str(),
+        title: &request.title,
+        content: &request.content,
+        slug: &request.slug,
+    };
+
+    diesel::insert_into(blog_translations::table)
+        .values(&new_translation)
+        .on_conflict((blog_translations::blog_id, blog_translations::locale))
+        .do_update()
+        .set((
+            blog_translations::title.eq(&request.title),
+            blog_translations::content.eq(&request.content),
+            blog_translations::slug.eq(&request.slug),
+            blog_translations::updated_at.eq(diesel::dsl::now),
+        ))
+        .get_result::<BlogTranslation>(conn)
+        .map_err(|err| match err {
+            diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _) => {
+                AppError::BadRequest("slug is already taken in this locale".to_string())
+            }
+            err => AppError::from(err),
+        })
+}
+
+#[allow(dead_code)]
+pub fn delete_blog_translation(conn: &mut PgConnection, blog_id: Uuid, locale: Locale) -> Result<usize, diesel::result::Error> {
+    diesel::delete(
+        blog_translations::table
+            .filter(blog_translations::blog_id.eq(blog_id))
+            .filter(blog_translations::locale.eq(locale.as_str()))
+    )
+    .execute(conn)
+}
+
+/// Blogs whose original or translation in `locale` matches `terms`, parsed with
+/// that language's text-search configuration so the GIN indexes apply.
+fn matches_in(locale: Locale, terms: &str) -> Box<dyn BoxableExpression<blogs::table, diesel::pg::Pg, SqlType = diesel::sql_types::Bool>> {
+    use diesel::dsl::sql;
+    use diesel::sql_types::{Bool, Text};
+
+    let (code, config) = (locale.as_str(), locale.search_config());
+    Box::new(
+        sql::<Bool>(&format!("((blogs.locale = '{code}' AND blogs.search_vector @@ websearch_to_tsquery('{config}', "))
+            .bind::<Text, _>(terms.to_string())
+            .sql(&format!(")) OR blogs.id IN (SELECT blog_id FROM blog_translations WHERE locale = '{code}' AND search_vector @@ websearch_to_tsquery('{config}', "))
+            .bind::<Text, _>(terms.to_string())
+            .sql(")))")
+    )
+}
+
+/// Full-text search over blogs listed for the viewer, best match first. Each
+/// language is searched with its own stemming; `lang` limits the search to one.
+#[allow(dead_code)]
+pub fn search_blogs(conn: &mut PgConnection, terms: &str, lang: Option<Locale>, viewer_id: Option<Uuid>, limit: i64, offset: i64) -> Result<Vec<Blog>, diesel::result::Error> {
+    use diesel::dsl::sql;
+    use diesel::sql_types::{Float, Nullable, Text};
+
+    let matches = match lang {
+        Some(locale) => matches_in(locale, terms),
+        None => Locale::ALL
+            .into_iter()
+            .map(|locale| matches_in(locale, terms))
+            .reduce(|matches, other| Box::new(matches.or(other)))
+            .expect("at least one locale"),
+    };
+    let rank = sql::<Float>("blog_search_rank(blogs.id, ")
+        .bind::<Text, _>(terms.to_string())
+        .sql(", ")
+        .bind::<Nullable<Text>, _>(lang.map(|locale| locale.as_str()))
+        .sql(")");
+
+    blogs::table
+        .filter(listed_for(viewer_id))
+        .into_boxed()
+        .filter(matches)
+        .order((rank.desc(), blogs::created_at.desc(), blogs::id.desc()))
+        .limit(limit)
+        .offset(offset)
+        .load::<Blog>(conn)
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = users)]
+struct NewUser<'a> {
+    username: &'a str,
+    email: &'a str,
+    password_hash: &'a str,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = blogs)]
+struct NewBlog<'a> {
+    title: &'a str,
+    content: &'a str,
+    author_id: Uuid,
+    status: &'a str,
+    published_at: Option<chrono::DateTime<chrono::Utc>>,
+    publication_id: Option<Uuid>,
+    locale: &'a str,
+    word_count: i32,
+    reading_time_minutes: i32,
+    excerpt: &'a str,
+    toc: serde_json::Value,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = comments)]
+struct NewComment<'a> {
+    blog_id: Uuid,
+    user_id: Uuid,
+    content: &'a str,
+    parent_comment_id: Option<Uuid>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = reading_lists)]
+struct NewReadingList<'a> {
+    user_id: Uuid,
+    name: &'a str,
+    description: Option<&'a str>,
+    is_public: bool,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = reading_list_items)]
+struct NewReadingListItem<'a> {
+    list_id: Uuid,
+    blog_id: Uuid,
+    blog_title: &'a str,
+    position: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = reactions)]
+struct NewReaction<'a> {
+    target_type: &'a str,
+    target_id: Uuid,
+    user_id: Uuid,
+    kind: &'a str,
+}
+#[derive(Insertable)]
+#[diesel(table_name = reports)]
+struct NewReport<'a> {
+    reporter_id: Uuid,
+    target_type: &'a str,
+    target_id: Uuid,
+    reason: &'a str,
+    details: Option<&'a str>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = audit_log)]
+struct NewAuditLogEntry<'a> {
+    actor_id: Uuid,
+    action: &'a str,
+    target_type: &'a str,
+    target_id: Uuid,
+    report_id: Option<Uuid>,
+    notes: Option<&'a str>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = blog_transitions)]
+struct NewBlogTransition<'a> {
+    blog_id: Uuid,
+    from_status: &'a str,
+    to_status: &'a str,
+    action: &'a str,
+    actor_id: Option<Uuid>,
+    comment: Option<&'a str>,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = publications, treat_none_as_null = true)]
+struct NewPublication<'a> {
+    name: &'a str,
+    slug: &'a str,
+    description: Option<&'a str>,
+    logo_url: Option<&'a str>,
+    accent_color: Option<&'a str>,
+    require_review: bool,
+}
+
+impl<'a> NewPublication<'a> {
+    /// `require_review` is used when the request leaves it out.
+    fn from_request(request: &'a PublicationRequest, require_review: bool) -> Self {
+        NewPublication {
+            name: request.name.trim(),
+            slug: &request.slug,
+            description: request.description.as_deref(),
+            logo_url: request.logo_url.as_deref(),
+            accent_color: request.accent_color.as_deref(),
+            require_review: request.require_review.unwrap_or(require_review),
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = publication_members)]
+struct NewPublicationMember<'a> {
+    publication_id: Uuid,
+    user_id: Uuid,
+    role: &'a str,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = blog_authors)]
+struct NewBlogAuthor<'a> {
+    blog_id: Uuid,
+    user_id: Uuid,
+    position: i32,
+    role: &'a str,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = blog_author_invitations)]
+struct NewAuthorInvitation<'a> {
+    blog_id: Uuid,
+    invitee_id: Uuid,
+    inviter_id: Uuid,
+    role: &'a str,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = notifications)]
+struct NewNotification<'a> {
+    user_id: Uuid,
+    actor_id: Option<Uuid>,
+    kind: &'a str,
+    blog_id: Option<Uuid>,
+    data: serde_json::Value,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = series)]
+struct NewSeries<'a> {
+    owner_id: Uuid,
+    publication_id: Option<Uuid>,
+    title: &'a str,
+    description: Option<&'a str>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = series_blogs)]
+struct NewSeriesBlog {
+    series_id: Uuid,
+    blog_id: Uuid,
+    position: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = blog_translations)]
+struct NewBlogTranslation<'a> {
+    blog_id: Uuid,
+    locale: &'a str,
+    title: &'a str,
+    content: &'a str,
+    slug: &'a str,
+}
@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Position in a list ordered by `(<timestamp> DESC, id DESC)`, such as
+/// notifications by `created_at` or the feed by `published_at`, exchanged with
+/// clients as an opaque `<microseconds>_<id>` string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        format!("{}_{}", self.at.timestamp_micros(), self.id)
+    }
+
+    pub fn decode(value: &str) -> Option<Self> {
+        let (micros, id) = value.split_once('_')?;
+        Some(Self {
+            at: DateTime::from_timestamp_micros(micros.parse().ok()?)?,
+            id: Uuid::parse_str(id).ok()?,
+        })
+    }
+}
+
+/// Clamps a client supplied page size to `1..=100`.
+pub fn page_limit(limit: Option<i64>) -> i64 {
+    limit.unwrap_or(20).clamp(1, 100)
+}
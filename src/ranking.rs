@@ -0,0 +1,243 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use uuid::Uuid;
+
+use crate::models::{CommentResponse, CommentSort, CommentThread};
+
+/// z-score for an 80% confidence interval.
+const WILSON_Z: f64 = 1.281551565545;
+/// Deepest nesting of reply threads, top-level comments being the first
+/// level. Deeper replies are listed alongside their ancestor at this level.
+pub const MAX_THREAD_DEPTH: usize = 8;
+
+/// Lower bound of the Wilson score interval for the share of upvotes, so a
+/// comment with few votes does not outrank one that is consistently liked.
+pub fn wilson_score(upvotes: i64, downvotes: i64) -> f64 {
+    let n = (upvotes + downvotes) as f64;
+    if n == 0.0 {
+        return 0.0;
+    }
+
+    let p = upvotes as f64 / n;
+    let z2 = WILSON_Z * WILSON_Z;
+    let centre = p + z2 / (2.0 * n);
+    let spread = WILSON_Z * ((p * (1.0 - p) + z2 / (4.0 * n)) / n).sqrt();
+
+    (centre - spread) / (1.0 + z2 / n)
+}
+
+/// Highest for comments with many votes split evenly between up and down.
+pub fn controversy(upvotes: i64, downvotes: i64) -> f64 {
+    if upvotes <= 0 || downvotes <= 0 {
+        return 0.0;
+    }
+
+    let magnitude = (upvotes + downvotes) as f64;
+    let balance = upvotes.min(downvotes) as f64 / upvotes.max(downvotes) as f64;
+
+    magnitude.powf(balance)
+}
+
+fn compare(a: &CommentResponse, b: &CommentResponse, sort: CommentSort) -> Ordering {
+    let (a, b) = (&a.comment, &b.comment);
+    let newest_first = b.created_at.cmp(&a.created_at);
+
+    match sort {
+        CommentSort::Top => wilson_score(b.upvotes, b.downvotes)
+            .total_cmp(&wilson_score(a.upvotes, a.downvotes))
+            .then(newest_first),
+        CommentSort::Controversial => controversy(b.upvotes, b.downvotes)
+            .total_cmp(&controversy(a.upvotes, a.downvotes))
+            .then(newest_first),
+        CommentSort::Newest => newest_first,
+        CommentSort::Oldest => a.created_at.cmp(&b.created_at),
+    }
+}
+
+/// Arranges comments into reply threads, ordering every level by `sort`.
+/// Replies whose parent is not in `comments` are shown at the top level, and
+/// threads nest at most `MAX_THREAD_DEPTH` levels deep.
+pub fn build_threads(comments: Vec<CommentResponse>, sort: CommentSort) -> Vec<CommentThread> {
+    let ids: HashSet<Uuid> = comments.iter().map(|comment| comment.comment.id).collect();
+    let mut replies_to: HashMap<Option<Uuid>, Vec<Uuid>> = HashMap::new();
+    for comment in &comments {
+        let parent = comment.comment.parent_comment_id.filter(|parent| ids.contains(parent));
+        replies_to.entry(parent).or_default().push(comment.comment.id);
+    }
+
+    // Walks the reply tree breadth-first to find where each comment is shown:
+    // under its parent or, past the depth limit, alongside its ancestor at the
+    // limit. `anchors` maps each comment to itself or, below the
+    // limit, to its ancestor one level above it.
+    let mut shown_under: HashMap<Uuid, Option<Uuid>> = HashMap::new();
+    let mut anchors: HashMap<Uuid, Uuid> = HashMap::new();
+    let mut queue: VecDeque<(Option<Uuid>, usize)> = VecDeque::from([(None, 0)]);
+    while let Some((parent, depth)) = queue.pop_front() {
+        let depth = depth + 1;
+        for id in replies_to.remove(&parent).unwrap_or_default() {
+            let (shown, anchor) = match parent {
+                Some(parent) if depth > MAX_THREAD_DEPTH => (Some(anchors[&parent]), anchors[&parent]),
+                Some(parent) if depth == MAX_THREAD_DEPTH => (Some(parent), anchors[&parent]),
+                _ => (parent, id),
+            };
+            shown_under.insert(id, shown);
+            anchors.insert(id, anchor);
+            queue.push_back((Some(id), depth));
+        }
+    }
+
+    let mut children: HashMap<Option<Uuid>, Vec<CommentResponse>> = HashMap::new();
+    for comment in comments {
+        // Comments in a reply cycle are never reached from the top level.
+        let parent = shown_under.get(&comment.comment.id).copied().unwrap_or(None);
+        children.entry(parent).or_default().push(comment);
+    }
+
+    attach_replies(None, &mut children, sort)
+}
+
+/// Recurses once per nesting level, so at most `MAX_THREAD_DEPTH` deep.
+fn attach_replies(parent: Option<Uuid>, children: &mut HashMap<Option<Uuid>, Vec<CommentResponse>>, sort: CommentSort) -> Vec<CommentThread> {
+    let mut level = children.remove(&parent).unwrap_or_default();
+    level.sort_by(|a, b| compare(a, b, sort));
+
+    level
+        .into_iter()
+        .map(|comment| {
+            let replies = attach_replies(Some(comment.comment.id), children, sort);
+            CommentThread { comment, replies }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use super::*;
+    use crate::models::Comment;
+
+    fn comment(n: i64, parent: Option<Uuid>, upvotes: i64, downvotes: i64) -> CommentResponse {
+        let created_at = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap() + Duration::minutes(n);
+        CommentResponse {
+            comment: Comment {
+                id: Uuid::from_u128(n as u128),
+                blog_id: Uuid::nil(),
+                user_id: Uuid::nil(),
+                content: format!("comment {}", n),
+                parent_comment_id: parent,
+                created_at,
+                upvotes,
+                downvotes,
+                score: upvotes - downvotes,
+                updated_at: created_at,
+            },
+            reactions: Default::default(),
+            my_vote: None,
+        }
+    }
+
+    fn ids(threads: &[CommentThread]) -> Vec<u128> {
+        threads.iter().map(|thread| thread.comment.comment.id.as_u128()).collect()
+    }
+
+    fn depth(threads: &[CommentThread]) -> usize {
+        let mut depth = 0;
+        let mut level: Vec<&CommentThread> = threads.iter().collect();
+        while !level.is_empty() {
+            depth += 1;
+            level = level.iter().flat_map(|thread| thread.replies.iter()).collect();
+        }
+        depth
+    }
+
+    #[test]
+    fn wilson_score_is_zero_without_votes() {
+        assert_eq!(wilson_score(0, 0), 0.0);
+    }
+
+    #[test]
+    fn wilson_score_prefers_consistent_votes_over_few() {
+        assert!(wilson_score(90, 10) > wilson_score(1, 0));
+        assert!(wilson_score(10, 0) > wilson_score(1, 0));
+        assert!(wilson_score(10, 0) > wilson_score(10, 5));
+    }
+
+    #[test]
+    fn wilson_score_stays_within_bounds() {
+        for (up, down) in [(1, 0), (0, 1), (1000, 0), (0, 1000), (500, 500)] {
+            let score = wilson_score(up, down);
+            assert!((0.0..=1.0).contains(&score), "{} for {}/{}", score, up, down);
+        }
+    }
+
+    #[test]
+    fn controversy_needs_votes_both_ways() {
+        assert_eq!(controversy(0, 0), 0.0);
+        assert_eq!(controversy(50, 0), 0.0);
+        assert_eq!(controversy(0, 50), 0.0);
+    }
+
+    #[test]
+    fn controversy_favours_balanced_and_busy_comments() {
+        assert!(controversy(50, 50) > controversy(90, 10));
+        assert!(controversy(50, 50) > controversy(5, 5));
+        assert_eq!(controversy(10, 30), controversy(30, 10));
+    }
+
+    #[test]
+    fn build_threads_nests_and_sorts_every_level() {
+        let root = Uuid::from_u128(1);
+        let comments = vec![
+            comment(1, None, 0, 0),
+            comment(2, Some(root), 1, 0),
+            comment(3, Some(root), 20, 0),
+            comment(4, None, 5, 0),
+        ];
+
+        let threads = build_threads(comments, CommentSort::Top);
+        assert_eq!(ids(&threads), vec![4, 1]);
+        assert_eq!(ids(&threads[1].replies), vec![3, 2]);
+
+        let comments = vec![comment(1, None, 0, 0), comment(2, None, 0, 0), comment(3, Some(root), 0, 0)];
+        let threads = build_threads(comments, CommentSort::Oldest);
+        assert_eq!(ids(&threads), vec![1, 2]);
+        assert_eq!(ids(&threads[0].replies), vec![3]);
+    }
+
+    #[test]
+    fn build_threads_lifts_replies_to_missing_parents() {
+        let missing = Uuid::from_u128(99);
+        let threads = build_threads(vec![comment(1, Some(missing), 0, 0)], CommentSort::Newest);
+        assert_eq!(ids(&threads), vec![1]);
+    }
+
+    #[test]
+    fn build_threads_caps_nesting_depth() {
+        let comments: Vec<CommentResponse> = (1..=MAX_THREAD_DEPTH as i64 + 5)
+            .map(|n| comment(n, (n > 1).then(|| Uuid::from_u128(n as u128 - 1)), 0, 0))
+            .collect();
+
+        let threads = build_threads(comments, CommentSort::Oldest);
+        assert_eq!(depth(&threads), MAX_THREAD_DEPTH);
+
+        let mut deepest = &threads[0];
+        for _ in 2..MAX_THREAD_DEPTH {
+            deepest = &deepest.replies[0];
+        }
+        let expected: Vec<u128> = (MAX_THREAD_DEPTH as u128..=MAX_THREAD_DEPTH as u128 + 5).collect();
+        assert_eq!(ids(&deepest.replies), expected);
+        assert!(deepest.replies.iter().all(|reply| reply.replies.is_empty()));
+    }
+
+    #[test]
+    fn build_threads_handles_very_long_reply_chains() {
+        let comments: Vec<CommentResponse> = (1..=100_000)
+            .map(|n| comment(n, (n > 1).then(|| Uuid::from_u128(n as u128 - 1)), 0, 0))
+            .collect();
+
+        let threads = build_threads(comments, CommentSort::Newest);
+        assert_eq!(depth(&threads), MAX_THREAD_DEPTH);
+    }
+}
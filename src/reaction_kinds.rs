@@ -0,0 +1,27 @@
+use std::env;
+use std::sync::OnceLock;
+
+pub const LIKE: &str = "like";
+
+const DEFAULT_KINDS: &str = "like,love,insightful,funny";
+
+/// Reaction kinds enabled for this site, read once from the comma-separated
+/// `REACTION_KINDS` variable. `like` is always enabled since `/likes` is built on it.
+pub fn configured() -> &'static [String] {
+    static KINDS: OnceLock<Vec<String>> = OnceLock::new();
+
+    KINDS.get_or_init(|| {
+        let raw = env::var("REACTION_KINDS").unwrap_or_else(|_| DEFAULT_KINDS.to_string());
+        let mut kinds = vec![LIKE.to_string()];
+        for kind in raw.split(',').map(|kind| kind.trim().to_lowercase()) {
+            if !kind.is_empty() && !kinds.contains(&kind) {
+                kinds.push(kind);
+            }
+        }
+        kinds
+    })
+}
+
+pub fn is_enabled(kind: &str) -> bool {
+    configured().iter().any(|configured| configured == kind)
+}
@@ -1,28 +1,173 @@
-use actix_web::{web, HttpResponse, Responder};
+use std::sync::Arc;
+
+use actix_web::{http::header, web, HttpRequest, HttpResponse, Responder, ResponseError};
 // use serde_json::json;
 use uuid::Uuid;
+use chrono::{DateTime, Duration, Utc};
 use serde::Serialize;
+use diesel::pg::PgConnection;
 
-use crate::models::{User, Blog, Comment, Like};
-use crate::orm::{create_user, create_blog, create_comment, create_like, get_user, get_blog, update_blog, delete_blog, get_comment, update_comment, delete_comment, get_like};
+use crate::models::{User, AdminUser, Blog, BlogStatus, BlogPreview, PreviewLink, PreviewTokenRequest, BlogVisibility, VisibilityRequest, CommentMode, CommentSettingsRequest, MAX_AUTO_CLOSE_DAYS, BlogListQuery, Comment, CommentListQuery, Like, VoteRequest, LoginRequest, ReactionTarget, TokenResponse, ReadingList, ReadingListRequest, ReadingListItemRequest, ReorderRequest, FollowPage, FeedPage, PageQuery, CursorQuery,
+    RoleRequest, AccountStatus, AccountStatusRequest, ReportRequest, ReportTarget, ReportQueueQuery, ReportStatus, AssignReportRequest, ResolveReportRequest, ModerationAction,
+    SubmitForReviewRequest, ReviewCommentRequest, ApproveRequest, AssignReviewerRequest, ReviewQueueQuery};
+use crate::orm::{create_user, get_user_by_email, create_blog, create_comment, create_like, get_user, get_blog, get_blog_response, get_blog_responses, blog_last_modified, list_blogs, update_comment_settings, can_read_blog, set_blog_visibility, transition_blog, submit_blog_for_review, assign_reviewer, list_review_queue, list_blog_transitions,
+    create_publication, get_publication, update_publication, delete_publication, publication_role, list_publication_members, set_publication_member, remove_publication_member, set_blog_publication,
+    is_blog_author, list_blog_authors, notify_blog_authors, list_notifications, mark_notifications_read, create_author_invitation, get_author_invitation, list_pending_invitations, list_blog_invitations, respond_to_invitation, revoke_invitation, remove_blog_author, reorder_blog_authors, transfer_blogs, list_owned_blog_ids, is_blocked,
+    create_series, get_series, update_series, delete_series, list_series, list_series_blog_ids, add_blog_to_series, remove_blog_from_series, reorder_series, get_series_detail,
+    localize_blog_responses, list_blog_translations, upsert_blog_translation, delete_blog_translation, search_blogs, set_blog_excerpt, set_blog_seo, set_blog_tags, list_syndicated_blogs, count_sitemap_blogs, list_sitemap_blogs, count_sitemap_authors, list_sitemap_authors, list_sitemap_blog_pages, list_sitemap_author_pages, count_sitemap_tags, list_sitemap_tags, list_sitemap_tag_pages, list_blog_revisions, get_blog_revision, create_preview_token, get_preview_token, list_active_preview_tokens, revoke_preview_token, share_blog, unshare_blog, list_blog_shares, record_blog_view, update_blog, remove_blog, get_comment, get_comment_response, get_comment_responses, list_blog_comments, vote_comment, clear_comment_vote, update_comment, remove_comment, get_like, delete_like, delete_like_by_user, add_reaction, remove_reaction, get_reaction_summaries, get_blog_events_since,
+    create_reading_list, get_reading_list, list_user_reading_lists, update_reading_list, delete_reading_list, add_reading_list_item, get_reading_list_item, delete_reading_list_item, list_reading_list_items, reorder_reading_list_items, get_reading_list_detail,
+    follow_user, unfollow_user, block_user, unblock_user, list_blocked_users, mute_user, unmute_user, list_muted_users,
+    set_user_role, set_account_status, create_report, get_report, list_reports, assign_report, resolve_report, report_target_user, get_report_detail, count_followers, count_following, list_followers, list_following, get_feed};
 use crate::db::DbPool;
 use crate::api_response::ApiResponse;
+use crate::events::{self, Audience, EventBroker, LiveEvent};
+use crate::auth::{check_account_status, issue_preview_token, issue_token, verify_preview_token, ActiveUser, AuthUser};
+use crate::error_handler::AppError;
+use crate::ws;
+use crate::reaction_kinds;
+use crate::ranking;
+use crate::pagination::{page_limit, Cursor};
+use crate::workflow::Action;
+use crate::seo;
+use crate::site;
+use crate::tags;
+use crate::conditional::Validators;
+use crate::feeds::{self, Feed, FeedFormat, FeedScope};
+use crate::sitemap::{self, SitemapUrl};
+use crate::models::{Publication, PublicationRequest, PublicationRole, MemberRoleRequest, BlogPublicationRequest,
+    AuthorOrderRequest, InvitationRequest, NotificationKind, NotificationQuery, AuthorInvitation, TransferRequest,
+    Series, SeriesRequest, SeriesListQuery, SeriesOrderRequest, Locale, LocaleQuery, BlogSearchQuery, TranslationRequest, ExcerptRequest, SeoRequest, TagsRequest, FeedContent, FeedQuery};
 
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("")
+            .route("/auth/login", web::post().to(login_handler))
+            .route("/ws", web::get().to(ws::websocket_handler))
             .route("/users", web::post().to(create_user_handler))
             .route("/users/{id}", web::get().to(get_user_by_id))
+            .route("/users/{id}/reading-lists", web::get().to(list_user_reading_lists_handler))
+            .route("/users/{id}/follow", web::put().to(follow_user_handler))
+            .route("/users/{id}/follow", web::delete().to(unfollow_user_handler))
+            .route("/users/{id}/followers", web::get().to(list_followers_handler))
+            .route("/users/{id}/following", web::get().to(list_following_handler))
+            .route("/users/{id}/block", web::put().to(block_user_handler))
+            .route("/users/{id}/block", web::delete().to(unblock_user_handler))
+            .route("/users/{id}/mute", web::put().to(mute_user_handler))
+            .route("/users/{id}/mute", web::delete().to(unmute_user_handler))
+            .route("/blocks", web::get().to(list_blocks_handler))
+            .route("/mutes", web::get().to(list_mutes_handler))
+            .route("/feed", web::get().to(feed_handler))
+            .route("/robots.txt", web::get().to(robots_txt_handler))
+            .route("/sitemap.xml", web::get().to(sitemap_handler))
+            .route("/sitemaps/blogs-{page:\\d+}.xml", web::get().to(blog_sitemap_handler))
+            .route("/sitemaps/authors-{page:\\d+}.xml", web::get().to(author_sitemap_handler))
+            .route("/sitemaps/tags-{page:\\d+}.xml", web::get().to(tag_sitemap_handler))
+            .route("/feed.xml", web::get().to(rss_feed_handler))
+            .route("/atom.xml", web::get().to(atom_feed_handler))
+            .route("/feed.json", web::get().to(json_feed_handler))
+            .route("/users/{id}/feed.xml", web::get().to(user_rss_feed_handler))
+            .route("/users/{id}/atom.xml", web::get().to(user_atom_feed_handler))
+            .route("/users/{id}/feed.json", web::get().to(user_json_feed_handler))
+            .route("/tags/{tag}", web::get().to(list_tag_blogs_handler))
+            .route("/tags/{tag}/feed.xml", web::get().to(tag_rss_feed_handler))
+            .route("/tags/{tag}/atom.xml", web::get().to(tag_atom_feed_handler))
+            .route("/tags/{tag}/feed.json", web::get().to(tag_json_feed_handler))
+            .route("/reports", web::post().to(create_report_handler))
+            .route("/moderation/reports", web::get().to(list_reports_handler))
+            .route("/moderation/reports/{id}", web::get().to(get_report_handler))
+            .route("/moderation/reports/{id}/assignee", web::put().to(assign_report_handler))
+            .route("/moderation/reports/{id}/resolve", web::post().to(resolve_report_handler))
+            .route("/admin/users/{id}/role", web::put().to(set_user_role_handler))
+            .route("/admin/users/{id}/status", web::put().to(set_account_status_handler))
+            .route("/blogs", web::get().to(list_blogs_handler))
             .route("/blogs", web::post().to(create_blog_handler))
+            .route("/blogs/search", web::get().to(search_blogs_handler))
             .route("/blogs/{id}", web::get().to(get_blog_by_id))
             .route("/blogs/{id}", web::put().to(update_blog_by_id))
             .route("/blogs/{id}", web::delete().to(delete_blog_by_id))
+            .route("/blogs/{id}/events", web::get().to(blog_events_stream))
+            .route("/blogs/{id}/like", web::put().to(like_blog_handler))
+            .route("/blogs/{id}/like", web::delete().to(unlike_blog_handler))
+            .route("/blogs/{id}/comments", web::get().to(list_blog_comments_handler))
+            .route("/blogs/{id}/excerpt", web::put().to(set_blog_excerpt_handler))
+            .route("/blogs/{id}/seo", web::put().to(set_blog_seo_handler))
+            .route("/blogs/{id}/tags", web::put().to(set_blog_tags_handler))
+            .route("/blogs/{id}/meta", web::get().to(get_blog_meta_handler))
+            .route("/blogs/{id}/comment-settings", web::put().to(update_comment_settings_handler))
+            .route("/blogs/{id}/visibility", web::put().to(update_visibility_handler))
+            .route("/blogs/{id}/shares", web::get().to(list_blog_shares_handler))
+            .route("/blogs/{id}/shares/{user_id}", web::put().to(share_blog_handler))
+            .route("/blogs/{id}/shares/{user_id}", web::delete().to(unshare_blog_handler))
+            .route("/blogs/{id}/publish", web::post().to(publish_blog_handler))
+            .route("/blogs/{id}/submit", web::post().to(submit_blog_handler))
+            .route("/blogs/{id}/withdraw", web::post().to(withdraw_blog_handler))
+            .route("/blogs/{id}/request-changes", web::post().to(request_changes_handler))
+            .route("/blogs/{id}/approve", web::post().to(approve_blog_handler))
+            .route("/blogs/{id}/unschedule", web::post().to(unschedule_blog_handler))
+            .route("/blogs/{id}/reviewer", web::put().to(assign_reviewer_handler))
+            .route("/blogs/{id}/transitions", web::get().to(list_blog_transitions_handler))
+            .route("/reviews", web::get().to(review_queue_handler))
+            .route("/blogs/{id}/publication", web::put().to(set_blog_publication_handler))
+            .route("/blogs/{id}/translations", web::get().to(list_blog_translations_handler))
+            .route("/blogs/{id}/translations/{locale}", web::put().to(upsert_blog_translation_handler))
+            .route("/blogs/{id}/translations/{locale}", web::delete().to(delete_blog_translation_handler))
+            .route("/blogs/{id}/authors", web::get().to(list_blog_authors_handler))
+            .route("/blogs/{id}/authors", web::put().to(reorder_blog_authors_handler))
+            .route("/blogs/{id}/authors/{user_id}", web::delete().to(remove_blog_author_handler))
+            .route("/blogs/{id}/invitations", web::get().to(list_blog_invitations_handler))
+            .route("/blogs/{id}/invitations", web::post().to(invite_author_handler))
+            .route("/blogs/{id}/invitations/{invitation_id}", web::delete().to(revoke_invitation_handler))
+            .route("/invitations", web::get().to(list_my_invitations_handler))
+            .route("/invitations/{id}/accept", web::post().to(accept_invitation_handler))
+            .route("/invitations/{id}/decline", web::post().to(decline_invitation_handler))
+            .route("/blogs/{id}/transfer", web::post().to(transfer_blog_handler))
+            .route("/users/{id}/transfer-blogs", web::post().to(transfer_user_blogs_handler))
+            .route("/series", web::get().to(list_series_handler))
+            .route("/series", web::post().to(create_series_handler))
+            .route("/series/{id}", web::get().to(get_series_handler))
+            .route("/series/{id}", web::put().to(update_series_handler))
+            .route("/series/{id}", web::delete().to(delete_series_handler))
+            .route("/series/{id}/order", web::put().to(reorder_series_handler))
+            .route("/series/{id}/blogs/{blog_id}", web::put().to(add_series_blog_handler))
+            .route("/series/{id}/blogs/{blog_id}", web::delete().to(remove_series_blog_handler))
+            .route("/notifications", web::get().to(list_notifications_handler))
+            .route("/notifications/read", web::post().to(mark_all_notifications_read_handler))
+            .route("/notifications/{id}/read", web::post().to(mark_notification_read_handler))
+            .route("/publications", web::post().to(create_publication_handler))
+            .route("/publications/{id}", web::get().to(get_publication_handler))
+            .route("/publications/{id}", web::put().to(update_publication_handler))
+            .route("/publications/{id}", web::delete().to(delete_publication_handler))
+            .route("/publications/{id}/blogs", web::get().to(list_publication_blogs_handler))
+            .route("/publications/{id}/members", web::get().to(list_publication_members_handler))
+            .route("/publications/{id}/members/{user_id}", web::put().to(set_publication_member_handler))
+            .route("/publications/{id}/members/{user_id}", web::delete().to(remove_publication_member_handler))
+            .route("/blogs/{id}/revisions", web::get().to(list_blog_revisions_handler))
+            .route("/blogs/{id}/previews", web::get().to(list_previews_handler))
+            .route("/blogs/{id}/previews", web::post().to(create_preview_handler))
+            .route("/blogs/{id}/previews/{preview_id}", web::delete().to(revoke_preview_handler))
+            .route("/preview/{token}", web::get().to(get_preview_handler))
+            .route("/blogs/{id}/reactions/{kind}", web::put().to(add_blog_reaction_handler))
+            .route("/blogs/{id}/reactions/{kind}", web::delete().to(remove_blog_reaction_handler))
             .route("/comments", web::post().to(create_comment_handler))
             .route("/comments/{id}", web::get().to(get_comment_by_id))
             .route("/comments/{id}", web::put().to(update_comment_handler))
             .route("/comments/{id}", web::delete().to(delete_comment_handler))
+            .route("/comments/{id}/vote", web::put().to(vote_comment_handler))
+            .route("/comments/{id}/vote", web::delete().to(clear_comment_vote_handler))
+            .route("/comments/{id}/reactions/{kind}", web::put().to(add_comment_reaction_handler))
+            .route("/comments/{id}/reactions/{kind}", web::delete().to(remove_comment_reaction_handler))
+            .route("/reactions", web::get().to(list_reaction_kinds))
+            .route("/reading-lists", web::get().to(list_my_reading_lists_handler))
+            .route("/reading-lists", web::post().to(create_reading_list_handler))
+            .route("/reading-lists/{id}", web::get().to(get_reading_list_handler))
+            .route("/reading-lists/{id}", web::put().to(update_reading_list_handler))
+            .route("/reading-lists/{id}", web::delete().to(delete_reading_list_handler))
+            .route("/reading-lists/{id}/items", web::post().to(add_reading_list_item_handler))
+            .route("/reading-lists/{id}/items/order", web::put().to(reorder_reading_list_handler))
+            .route("/reading-lists/{id}/items/{item_id}", web::delete().to(remove_reading_list_item_handler))
             .route("/likes", web::post().to(create_like_handler))
             .route("/likes/{id}", web::get().to(get_like_by_id))
+            .route("/likes/{id}", web::delete().to(delete_like_handler))
     );
 }
 
@@ -33,6 +178,45 @@ fn handle_diesel_result<T: Serialize>(result: Result<T, diesel::result::Error>)
     }
 }
 
+fn handle_app_result<T: Serialize>(result: Result<T, AppError>) -> HttpResponse {
+    match result {
+        Ok(data) => HttpResponse::Ok().json(ApiResponse::success(data)),
+        Err(err) => err.error_response(),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Logged in successfully", body = TokenResponse),
+        (status = 401, description = "Invalid email or password"),
+        (status = 403, description = "Account suspended or banned"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "auth"
+)]
+async fn login_handler(credentials: web::Json<LoginRequest>, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        get_user_by_email(&mut conn, &credentials.email)
+            .map(|user| bcrypt::verify(&credentials.password, &user.password_hash).unwrap_or(false).then_some(user))
+    }).await;
+
+    match result {
+        Ok(Ok(Some(user))) => match check_account_status(&user).map(|_| issue_token(user.id)) {
+            Ok(Ok(token)) => HttpResponse::Ok().json(ApiResponse::success(TokenResponse { token, user_id: user.id })),
+            Ok(Err(_)) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+            Err(err) => err.error_response(),
+        },
+        Ok(Ok(None)) | Ok(Err(diesel::result::Error::NotFound)) => HttpResponse::Unauthorized().json(ApiResponse::<()>::error("Invalid email or password".to_string())),
+        Ok(Err(_)) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Database error".to_string())),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
 #[utoipa::path(
     post,
     path = "/users",
@@ -61,14 +245,17 @@ async fn create_user_handler(user: web::Json<User>, pool: web::Data<DbPool>) ->
     path = "/users/{id}",
     responses(
         (status = 200, description = "User found", body = User),
+        (status = 304, description = "The cached copy is current"),
         (status = 404, description = "User not found")
     ),
     params(
-        ("id" = Uuid, Path, description = "User ID")
+        ("id" = Uuid, Path, description = "User ID"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag of the cached copy"),
+        ("If-Modified-Since" = Option<String>, Header, description = "Last-Modified of the cached copy")
     ),
     tag = "users"
 )]
-async fn get_user_by_id(user_id: web::Path<Uuid>, pool: web::Data<DbPool>) -> impl Responder {
+async fn get_user_by_id(req: HttpRequest, user_id: web::Path<Uuid>, pool: web::Data<DbPool>) -> impl Responder {
     let pool = pool.clone();
     let result = web::block(move || {
         let mut conn = pool.get().expect("couldn't get db connection from pool");
@@ -76,7 +263,8 @@ async fn get_user_by_id(user_id: web::Path<Uuid>, pool: web::Data<DbPool>) -> im
     }).await;
     
     match result {
-        Ok(user_result) => handle_diesel_result(user_result),
+        Ok(Ok(user)) => Validators::for_json(&user, Some(user.updated_at)).respond_json(&req, user),
+        Ok(Err(err)) => AppError::from(err).error_response(),
         Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
     }
 }
@@ -86,55 +274,208 @@ async fn get_user_by_id(user_id: web::Path<Uuid>, pool: web::Data<DbPool>) -> im
     path = "/blogs",
     request_body = Blog,
     responses(
-        (status = 200, description = "Blog created successfully", body = Blog),
+        (status = 200, description = "Blog created successfully; the authenticated user is the author. Send `status: draft` to start as a draft", body = Blog),
+        (status = 400, description = "Status other than draft or published"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a member of the publication, or account suspended or banned"),
+        (status = 404, description = "Publication not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "blogs"
 )]
-async fn create_blog_handler(blog: web::Json<Blog>, pool: web::Data<DbPool>) -> impl Responder {
+async fn create_blog_handler(blog: web::Json<Blog>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    // Review states are only reachable through the workflow endpoints
+    let status = match BlogStatus::parse(&blog.status) {
+        Some(BlogStatus::Draft) => BlogStatus::Draft,
+        Some(BlogStatus::Published) | None => BlogStatus::Published,
+        Some(_) => return AppError::BadRequest("new blogs can only be draft or published".to_string()).error_response(),
+    };
+    let locale = match blog.locale.as_str() {
+        "" => Locale::default(),
+        code => match Locale::parse(code) {
+            Some(locale) => locale,
+            None => return AppError::BadRequest(format!("unsupported locale '{}'", code)).error_response(),
+        },
+    };
+
     let pool = pool.clone();
     let result = web::block(move || {
         let mut conn = pool.get().expect("couldn't get db connection from pool");
-        create_blog(&mut conn, &blog.title, &blog.content, blog.author_id)
+        if let Some(publication_id) = blog.publication_id {
+            get_publication(&mut conn, publication_id)?;
+            if publication_role(&mut conn, publication_id, user.id)?.is_none() {
+                return Err(AppError::Forbidden);
+            }
+        }
+        create_blog(&mut conn, &blog.title, &blog.content, user.id, status, blog.publication_id, locale).map_err(AppError::from)
     }).await;
     
     match result {
-        Ok(blog_result) => handle_diesel_result(blog_result),
+        Ok(blog_result) => handle_app_result(blog_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/blogs",
+    responses(
+        (status = 200, description = "Page of blogs", body = [BlogResponse]),
+        (status = 500, description = "Internal server error")
+    ),
+    params(BlogListQuery),
+    tag = "blogs"
+)]
+async fn list_blogs_handler(query: web::Query<BlogListQuery>, viewer: Option<AuthUser>, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let limit = page_limit(query.limit);
+        let offset = query.offset.unwrap_or(0).max(0);
+        let blogs = list_blogs(&mut conn, query.sort.unwrap_or_default(), limit, offset, viewer.map(|user| user.id), None, None)?;
+        get_blog_responses(&mut conn, blogs, viewer.map(|user| user.id))
+    }).await;
+
+    match result {
+        Ok(blogs_result) => handle_diesel_result(blogs_result),
         Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
     }
 }
 
+/// The post password sent for a password-protected blog.
+fn blog_password(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get("X-Blog-Password")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Loads a blog the viewer may read. Blogs hidden from the viewer are reported
+/// as missing; a password-protected blog without the right password is `Forbidden`.
+fn readable_blog(conn: &mut PgConnection, blog_id: Uuid, viewer_id: Option<Uuid>, password: Option<&str>) -> Result<Blog, AppError> {
+    let blog = get_blog(conn, blog_id)?;
+    if can_read_blog(conn, &blog, viewer_id, password)? {
+        return Ok(blog);
+    }
+    match BlogVisibility::parse(&blog.visibility) {
+        Some(BlogVisibility::Password) => Err(AppError::Forbidden),
+        _ => Err(AppError::NotFound),
+    }
+}
+
+/// The comment, when `viewer_id` can read its blog and the blog's comments
+/// are not closed to them.
+fn readable_comment(conn: &mut PgConnection, comment_id: Uuid, viewer_id: Option<Uuid>, password: Option<&str>) -> Result<Comment, AppError> {
+    let comment = get_comment(conn, comment_id)?;
+    let blog = readable_blog(conn, comment.blog_id, viewer_id, password)?;
+    if blog.effective_comment_mode() == CommentMode::Closed && viewer_id != Some(blog.author_id) {
+        return Err(AppError::NotFound);
+    }
+    Ok(comment)
+}
+
 #[utoipa::path(
     get,
     path = "/blogs/{id}",
     responses(
-        (status = 200, description = "Blog found", body = Blog),
-        (status = 404, description = "Blog not found")
+        (status = 200, description = "Blog found, in the first requested locale it is translated into, or else the original. `view_count` is left out, since every read changes it; listings carry it", body = BlogResponse),
+        (status = 304, description = "The cached copy is current"),
+        (status = 400, description = "Unsupported `lang`"),
+        (status = 403, description = "Password-protected blog and the password is missing or wrong"),
+        (status = 404, description = "Blog not found, or not visible to the viewer")
     ),
     params(
-        ("id" = Uuid, Path, description = "Blog ID")
+        ("id" = Uuid, Path, description = "Blog ID"),
+        LocaleQuery,
+        ("Accept-Language" = Option<String>, Header, description = "Preferred locales, used when `lang` is not given"),
+        ("X-Blog-Password" = Option<String>, Header, description = "Post password for password-protected blogs"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag of the cached copy"),
+        ("If-Modified-Since" = Option<String>, Header, description = "Last-Modified of the cached copy; only sent to anonymous readers")
     ),
     tag = "blogs"
 )]
-async fn get_blog_by_id(blog_id: web::Path<Uuid>, pool: web::Data<DbPool>) -> impl Responder {
+async fn get_blog_by_id(req: HttpRequest, blog_id: web::Path<Uuid>, query: web::Query<LocaleQuery>, viewer: Option<AuthUser>, pool: web::Data<DbPool>) -> impl Responder {
+    let preferred = match preferred_locales(&req, query.lang.as_deref()) {
+        Ok(preferred) => preferred,
+        Err(err) => return err.error_response(),
+    };
+    let password = blog_password(&req);
     let pool = pool.clone();
     let result = web::block(move || {
         let mut conn = pool.get().expect("couldn't get db connection from pool");
-        get_blog(&mut conn, blog_id.into_inner())
+        let viewer_id = viewer.map(|user| user.id);
+        let blog = readable_blog(&mut conn, blog_id.into_inner(), viewer_id, password.as_deref())?;
+        record_blog_view(&mut conn, blog.id)?;
+        let mut response = get_blog_response(&mut conn, blog.id, viewer_id)?;
+        localize_blog_responses(&mut conn, std::slice::from_mut(&mut response), &preferred)?;
+        // Signed-in readers also see whether they can comment, which blocks and
+        // follows decide without leaving a timestamp, so they only get the ETag.
+        let last_modified = match viewer_id {
+            Some(_) => None,
+            None => Some(blog_last_modified(&mut conn, &blog)?),
+        };
+        Ok::<_, AppError>((response, last_modified))
     }).await;
     
     match result {
-        Ok(blog_result) => handle_diesel_result(blog_result),
+        Ok(Ok((blog, last_modified))) => {
+            // Every read bumps the view count, so it is left out or no copy would ever be current
+            let mut body = serde_json::to_value(&blog).unwrap_or_default();
+            if let Some(fields) = body.as_object_mut() {
+                fields.remove("view_count");
+            }
+            let mut response = Validators::for_json(&body, last_modified).respond_json(&req, body);
+            response.headers_mut().insert(header::VARY, header::HeaderValue::from_static("Accept-Language, Authorization"));
+            response
+        }
+        Ok(Err(err)) => err.error_response(),
         Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
     }
 }
 
+/// The locales the client asked for, best first: `lang` if given, otherwise
+/// `Accept-Language` by quality. Unsupported languages in the header are skipped.
+fn preferred_locales(req: &HttpRequest, lang: Option<&str>) -> Result<Vec<Locale>, AppError> {
+    if let Some(lang) = lang {
+        return Locale::parse(lang)
+            .map(|locale| vec![locale])
+            .ok_or_else(|| AppError::BadRequest(format!("unsupported locale '{}'", lang)));
+    }
+
+    let header = req.headers()
+        .get("Accept-Language")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let mut ranges: Vec<(Locale, f32)> = header
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';');
+            let locale = Locale::parse(parts.next()?.trim())?;
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+            (quality > 0.0).then_some((locale, quality))
+        })
+        .collect();
+    ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let mut preferred = Vec::new();
+    for (locale, _) in ranges {
+        if !preferred.contains(&locale) {
+            preferred.push(locale);
+        }
+    }
+    Ok(preferred)
+}
+
 #[utoipa::path(
     put,
     path = "/blogs/{id}",
     request_body = Blog,
     responses(
         (status = 200, description = "Blog updated successfully", body = Blog),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an author or a publication editor, or account suspended or banned"),
         (status = 404, description = "Blog not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -143,15 +484,18 @@ async fn get_blog_by_id(blog_id: web::Path<Uuid>, pool: web::Data<DbPool>) -> im
     ),
     tag = "blogs"
 )]
-async fn update_blog_by_id(blog_id: web::Path<Uuid>, blog: web::Json<Blog>, pool: web::Data<DbPool>) -> impl Responder {
+async fn update_blog_by_id(blog_id: web::Path<Uuid>, blog: web::Json<Blog>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
     let pool = pool.clone();
     let result = web::block(move || {
         let mut conn = pool.get().expect("couldn't get db connection from pool");
-        update_blog(&mut conn, blog_id.into_inner(), &blog.title, &blog.content)
+        let existing = editable_blog(&mut conn, blog_id.into_inner(), user)?;
+        let updated = update_blog(&mut conn, existing.id, &blog.title, &blog.content)?;
+        notify_blog_authors(&mut conn, updated.id, user.id, NotificationKind::BlogEdited, serde_json::json!({ "title": updated.title }))?;
+        Ok(updated)
     }).await;
     
     match result {
-        Ok(blog_result) => handle_diesel_result(blog_result),
+        Ok(blog_result) => handle_app_result(blog_result),
         Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
     }
 }
@@ -161,6 +505,8 @@ async fn update_blog_by_id(blog_id: web::Path<Uuid>, blog: web::Json<Blog>, pool
     path = "/blogs/{id}",
     responses(
         (status = 200, description = "Blog deleted successfully"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the author, a publication editor or a moderator, or account suspended or banned"),
         (status = 404, description = "Blog not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -169,164 +515,3997 @@ async fn update_blog_by_id(blog_id: web::Path<Uuid>, blog: web::Json<Blog>, pool
     ),
     tag = "blogs"
 )]
-async fn delete_blog_by_id(blog_id: web::Path<Uuid>, pool: web::Data<DbPool>) -> impl Responder {
+async fn delete_blog_by_id(blog_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
     let pool = pool.clone();
     let result = web::block(move || {
         let mut conn = pool.get().expect("couldn't get db connection from pool");
-        delete_blog(&mut conn, blog_id.into_inner())
+        let blog = get_blog(&mut conn, blog_id.into_inner())?;
+        if blog.author_id != user.id && !manages_publication_blog(&mut conn, &blog, user.id)? && require_moderator(&mut conn, user).is_err() {
+            return Err(AppError::Forbidden);
+        }
+        remove_blog(&mut conn, &blog, user.id).map_err(AppError::from)
     }).await;
     
     match result {
-        Ok(_) => HttpResponse::Ok().json(ApiResponse::<()>::success(())),
+        Ok(delete_result) => handle_app_result(delete_result.map(|_| ())),
         Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
     }
 }
 
 #[utoipa::path(
-    post,
-    path = "/comments",
-    request_body = Comment,
+    get,
+    path = "/blogs/{id}/events",
     responses(
-        (status = 200, description = "Comment created successfully", body = Comment),
+        (status = 200, description = "Server-Sent Events stream of comment, reaction and like events for the blog", body = BlogEvent, content_type = "text/event-stream"),
+        (status = 404, description = "Blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID"),
+        ("Last-Event-ID" = Option<i64>, Header, description = "Replay events published after this event ID")
+    ),
+    tag = "blogs"
+)]
+async fn blog_events_stream(req: HttpRequest, blog_id: web::Path<Uuid>, viewer: Option<AuthUser>, pool: web::Data<DbPool>, broker: web::Data<EventBroker>) -> impl Responder {
+    let blog_id = blog_id.into_inner();
+    let last_event_id = req.headers()
+        .get("Last-Event-ID")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<i64>().ok());
+
+    // Subscribe before loading the backlog so nothing published in between is lost.
+    let receiver = broker.subscribe();
+    let password = blog_password(&req);
+
+    let viewer_id = viewer.map(|user| user.id);
+    let stream_pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = readable_blog(&mut conn, blog_id, viewer_id, password.as_deref())?;
+        // The password is checked once here; live events reuse the outcome.
+        let mut audience = Audience::load(&mut conn, viewer_id, password)?;
+        audience.admit(&blog);
+        let Some(event_id) = last_event_id else {
+            return Ok((audience, Vec::new()));
+        };
+        let blog = Some(Arc::new(blog));
+        let mut backlog = Vec::new();
+        let mut after = event_id;
+        loop {
+            let page = get_blog_events_since(&mut conn, blog_id, after, events::REPLAY_PAGE_SIZE)?;
+            let exhausted = (page.len() as i64) < events::REPLAY_PAGE_SIZE;
+            for event in page {
+                after = event.id;
+                let live = LiveEvent::with_blog(&mut conn, event, blog.clone())?;
+                if audience.check(&mut conn, &live, true)? {
+                    backlog.push(live.event);
+                }
+            }
+            if exhausted {
+                break;
+            }
+        }
+        Ok::<_, AppError>((audience, backlog))
+    }).await;
+
+    match result {
+        Ok(Ok((audience, backlog))) => HttpResponse::Ok()
+            .content_type("text/event-stream")
+            .insert_header(("Cache-Control", "no-cache"))
+            .streaming(events::sse_stream(blog_id, audience, stream_pool, backlog, receiver)),
+        Ok(Err(err)) => err.error_response(),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/blogs/{id}/like",
+    responses(
+        (status = 200, description = "Blog liked; liking again returns the existing like", body = Like),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Password-protected blog and the password is missing or wrong"),
+        (status = 404, description = "Blog not found, or not visible to the user")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID"),
+        ("X-Blog-Password" = Option<String>, Header, description = "Post password for password-protected blogs")
+    ),
+    tag = "likes"
+)]
+async fn like_blog_handler(req: HttpRequest, blog_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let password = blog_password(&req);
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = readable_blog(&mut conn, blog_id.into_inner(), Some(user.id), password.as_deref())?;
+        create_like(&mut conn, blog.id, user.id).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(like_result) => handle_app_result(like_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/blogs/{id}/like",
+    responses(
+        (status = 200, description = "Blog unliked; unliking a blog that is not liked is a no-op"),
+        (status = 401, description = "Not authenticated"),
         (status = 500, description = "Internal server error")
     ),
-    tag = "comments"
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "likes"
 )]
-async fn create_comment_handler(comment: web::Json<Comment>, pool: web::Data<DbPool>) -> impl Responder {
+async fn unlike_blog_handler(blog_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
     let pool = pool.clone();
     let result = web::block(move || {
         let mut conn = pool.get().expect("couldn't get db connection from pool");
-        create_comment(&mut conn, comment.blog_id, comment.user_id, &comment.content, comment.parent_comment_id)
+        delete_like_by_user(&mut conn, blog_id.into_inner(), user.id)
     }).await;
-    
+
     match result {
-        Ok(comment_result) => handle_diesel_result(comment_result),
+        Ok(Ok(_)) => HttpResponse::Ok().json(ApiResponse::<()>::success(())),
+        Ok(Err(_)) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Database error".to_string())),
         Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
     }
 }
 
 #[utoipa::path(
     get,
-    path = "/comments/{id}",
+    path = "/blogs/{id}/comments",
     responses(
-        (status = 200, description = "Comment found", body = Comment),
-        (status = 404, description = "Comment not found")
+        (status = 200, description = "Comment threads of the blog, nested at most 8 levels deep; empty when comments are closed, except for the author", body = [CommentThread]),
+        (status = 403, description = "Password-protected blog and the password is missing or wrong"),
+        (status = 404, description = "Blog not found, or not visible to the viewer")
     ),
     params(
-        ("id" = Uuid, Path, description = "Comment ID")
+        ("id" = Uuid, Path, description = "Blog ID"),
+        ("X-Blog-Password" = Option<String>, Header, description = "Post password for password-protected blogs"),
+        CommentListQuery
     ),
     tag = "comments"
 )]
-async fn get_comment_by_id(comment_id: web::Path<Uuid>, pool: web::Data<DbPool>) -> impl Responder {
+async fn list_blog_comments_handler(req: HttpRequest, blog_id: web::Path<Uuid>, query: web::Query<CommentListQuery>, viewer: Option<AuthUser>, pool: web::Data<DbPool>) -> impl Responder {
+    let password = blog_password(&req);
     let pool = pool.clone();
     let result = web::block(move || {
         let mut conn = pool.get().expect("couldn't get db connection from pool");
-        get_comment(&mut conn, comment_id.into_inner())
+        let viewer_id = viewer.map(|user| user.id);
+        let blog = readable_blog(&mut conn, blog_id.into_inner(), viewer_id, password.as_deref())?;
+        if blog.effective_comment_mode() == CommentMode::Closed && viewer_id != Some(blog.author_id) {
+            return Ok(Vec::new());
+        }
+        let comments = list_blog_comments(&mut conn, blog.id, viewer_id)?;
+        let responses = get_comment_responses(&mut conn, comments, viewer_id)?;
+        Ok::<_, AppError>(ranking::build_threads(responses, query.sort.unwrap_or_default()))
     }).await;
-    
+
     match result {
-        Ok(comment_result) => handle_diesel_result(comment_result),
+        Ok(threads_result) => handle_app_result(threads_result),
         Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
     }
 }
 
 #[utoipa::path(
     put,
-    path = "/comments/{id}",
-    request_body = Comment,
+    path = "/blogs/{id}/comment-settings",
+    request_body = CommentSettingsRequest,
     responses(
-        (status = 200, description = "Comment updated successfully", body = Comment),
-        (status = 404, description = "Comment not found"),
-        (status = 500, description = "Internal server error")
+        (status = 200, description = "Comment settings updated", body = Blog),
+        (status = 400, description = "auto_close_days is not between 1 and 3650"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the author or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Blog not found")
     ),
     params(
-        ("id" = Uuid, Path, description = "Comment ID")
+        ("id" = Uuid, Path, description = "Blog ID")
     ),
-    tag = "comments"
+    tag = "blogs"
 )]
-async fn update_comment_handler(comment_id: web::Path<Uuid>, comment: web::Json<Comment>, pool: web::Data<DbPool>) -> impl Responder {
+async fn update_comment_settings_handler(blog_id: web::Path<Uuid>, settings: web::Json<CommentSettingsRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    if settings.auto_close_days.is_some_and(|days| !(1..=MAX_AUTO_CLOSE_DAYS).contains(&days)) {
+        return AppError::BadRequest(format!("auto_close_days must be between 1 and {}", MAX_AUTO_CLOSE_DAYS)).error_response();
+    }
+
     let pool = pool.clone();
     let result = web::block(move || {
         let mut conn = pool.get().expect("couldn't get db connection from pool");
-        update_comment(&mut conn, comment_id.into_inner(), &comment.content)
+        let blog = authored_blog(&mut conn, blog_id.into_inner(), user)?;
+        update_comment_settings(&mut conn, blog.id, settings.mode, settings.auto_close_days).map_err(AppError::from)
     }).await;
-    
+
     match result {
-        Ok(comment_result) => handle_diesel_result(comment_result),
+        Ok(blog_result) => handle_app_result(blog_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+/// Whether `user_id` is an owner or editor of the publication the blog belongs to.
+fn manages_publication_blog(conn: &mut PgConnection, blog: &Blog, user_id: Uuid) -> Result<bool, AppError> {
+    match blog.publication_id {
+        Some(publication_id) => Ok(publication_role(conn, publication_id, user_id)?.is_some_and(|role| role.manages_blogs())),
+        None => Ok(false),
+    }
+}
+
+/// Loads a blog the user may manage: its author, or an owner or editor of its publication.
+fn authored_blog(conn: &mut PgConnection, blog_id: Uuid, user: ActiveUser) -> Result<Blog, AppError> {
+    let blog = get_blog(conn, blog_id)?;
+    if blog.author_id != user.id && !manages_publication_blog(conn, &blog, user.id)? {
+        return Err(AppError::Forbidden);
+    }
+    Ok(blog)
+}
+
+/// Whether `user_id` may edit the blog's content: anyone who may manage it, and its co-authors.
+fn can_edit_blog(conn: &mut PgConnection, blog: &Blog, user_id: Uuid) -> Result<bool, AppError> {
+    Ok(blog.author_id == user_id || is_blog_author(conn, blog.id, user_id)? || manages_publication_blog(conn, blog, user_id)?)
+}
+
+fn editable_blog(conn: &mut PgConnection, blog_id: Uuid, user: ActiveUser) -> Result<Blog, AppError> {
+    let blog = get_blog(conn, blog_id)?;
+    if !can_edit_blog(conn, &blog, user.id)? {
+        return Err(AppError::Forbidden);
+    }
+    Ok(blog)
+}
+
+#[utoipa::path(
+    put,
+    path = "/blogs/{id}/visibility",
+    request_body = VisibilityRequest,
+    responses(
+        (status = 200, description = "Visibility updated", body = Blog),
+        (status = 400, description = "Password visibility without a password"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the author or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "blogs"
+)]
+async fn update_visibility_handler(blog_id: web::Path<Uuid>, visibility: web::Json<VisibilityRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let password_hash = match (visibility.visibility, visibility.password.as_deref()) {
+        (BlogVisibility::Password, Some(password)) if !password.is_empty() => match bcrypt::hash(password, bcrypt::DEFAULT_COST) {
+            Ok(hash) => Some(hash),
+            Err(_) => return HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+        },
+        (BlogVisibility::Password, _) => return AppError::BadRequest("password visibility needs a password".to_string()).error_response(),
+        _ => None,
+    };
+
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = authored_blog(&mut conn, blog_id.into_inner(), user)?;
+        set_blog_visibility(&mut conn, blog.id, visibility.visibility, password_hash.as_deref()).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(blog_result) => handle_app_result(blog_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/blogs/{id}/shares",
+    responses(
+        (status = 200, description = "Users the blog is shared with", body = [UserSummary]),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the author or a publication editor"),
+        (status = 404, description = "Blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "blogs"
+)]
+async fn list_blog_shares_handler(blog_id: web::Path<Uuid>, user: AuthUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = get_blog(&mut conn, blog_id.into_inner())?;
+        if blog.author_id != user.id && !manages_publication_blog(&mut conn, &blog, user.id)? {
+            return Err(AppError::Forbidden);
+        }
+        list_blog_shares(&mut conn, blog.id).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(shares_result) => handle_app_result(shares_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/blogs/{id}/shares/{user_id}",
+    responses(
+        (status = 200, description = "Blog shared with the user; takes effect while visibility is `shared`"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the author or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Blog or user not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID"),
+        ("user_id" = Uuid, Path, description = "User to share with")
+    ),
+    tag = "blogs"
+)]
+async fn share_blog_handler(path: web::Path<(Uuid, Uuid)>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let (blog_id, user_id) = path.into_inner();
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = authored_blog(&mut conn, blog_id, user)?;
+        let reader = get_user(&mut conn, user_id)?;
+        share_blog(&mut conn, blog.id, reader.id).map(|_| ()).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(share_result) => handle_app_result(share_result),
         Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
     }
 }
 
 #[utoipa::path(
     delete,
-    path = "/comments/{id}",
+    path = "/blogs/{id}/shares/{user_id}",
     responses(
-        (status = 200, description = "Comment deleted successfully"),
-        (status = 404, description = "Comment not found"),
-        (status = 500, description = "Internal server error")
+        (status = 200, description = "Blog no longer shared with the user"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the author or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Blog not found, or not shared with the user")
     ),
     params(
-        ("id" = Uuid, Path, description = "Comment ID")
+        ("id" = Uuid, Path, description = "Blog ID"),
+        ("user_id" = Uuid, Path, description = "User to stop sharing with")
     ),
-    tag = "comments"
+    tag = "blogs"
 )]
-async fn delete_comment_handler(comment_id: web::Path<Uuid>, pool: web::Data<DbPool>) -> impl Responder {
+async fn unshare_blog_handler(path: web::Path<(Uuid, Uuid)>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let (blog_id, user_id) = path.into_inner();
     let pool = pool.clone();
     let result = web::block(move || {
         let mut conn = pool.get().expect("couldn't get db connection from pool");
-        delete_comment(&mut conn, comment_id.into_inner())
+        let blog = authored_blog(&mut conn, blog_id, user)?;
+        match unshare_blog(&mut conn, blog.id, user_id)? {
+            0 => Err(AppError::NotFound),
+            _ => Ok(()),
+        }
     }).await;
-    
+
     match result {
-        Ok(_) => HttpResponse::Ok().json(ApiResponse::<()>::success(())),
+        Ok(unshare_result) => handle_app_result(unshare_result),
         Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
     }
 }
 
+/// Applies a workflow `action` to the blog on behalf of `user`; the state
+/// machine decides whether their part in the blog allows it.
+fn workflow_step(conn: &mut PgConnection, blog_id: Uuid, user: ActiveUser, action: Action, comment: Option<&str>, publish_at: Option<DateTime<Utc>>) -> Result<Blog, AppError> {
+    let blog = get_blog(conn, blog_id)?;
+    transition_blog(conn, &blog, action, Some(user.id), comment, publish_at)
+}
+
+fn require_editor(conn: &mut PgConnection, user: ActiveUser) -> Result<User, AppError> {
+    let user = get_user(conn, user.id)?;
+    if !has_role(&user, &["editor", "admin"]) {
+        return Err(AppError::Forbidden);
+    }
+    Ok(user)
+}
+
+/// Whether `user_id` can review `blog`: a site editor or admin, or an owner or
+/// editor of the blog's publication.
+fn can_review(conn: &mut PgConnection, blog: &Blog, user_id: Uuid) -> Result<bool, AppError> {
+    let user = get_user(conn, user_id)?;
+    Ok(has_role(&user, &["editor", "admin"]) || manages_publication_blog(conn, blog, user_id)?)
+}
+
+/// Checks that `reviewer_id` can review `blog` and isn't its author.
+fn check_reviewer(conn: &mut PgConnection, blog: &Blog, reviewer_id: Uuid) -> Result<(), AppError> {
+    let reviewer = get_user(conn, reviewer_id)?;
+    if !can_review(conn, blog, reviewer.id)? {
+        return Err(AppError::BadRequest("reviewer must be an editor".to_string()));
+    }
+    if reviewer.id == blog.author_id {
+        return Err(AppError::BadRequest("authors cannot review their own blogs".to_string()));
+    }
+    Ok(())
+}
+
 #[utoipa::path(
     post,
-    path = "/likes",
-    request_body = Like,
+    path = "/blogs/{id}/publish",
     responses(
-        (status = 200, description = "Like created successfully", body = Like),
-        (status = 500, description = "Internal server error")
+        (status = 200, description = "Draft published without review", body = Blog),
+        (status = 400, description = "Blog is in review, scheduled or already published"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an author, or account suspended or banned"),
+        (status = 404, description = "Blog not found")
     ),
-    tag = "likes"
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "blogs"
 )]
-async fn create_like_handler(like: web::Json<Like>, pool: web::Data<DbPool>) -> impl Responder {
+async fn publish_blog_handler(blog_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
     let pool = pool.clone();
     let result = web::block(move || {
         let mut conn = pool.get().expect("couldn't get db connection from pool");
-        create_like(&mut conn, like.blog_id, like.user_id)
+        workflow_step(&mut conn, blog_id.into_inner(), user, Action::Publish, None, None)
     }).await;
-    
+
     match result {
-        Ok(like_result) => handle_diesel_result(like_result),
+        Ok(blog_result) => handle_app_result(blog_result),
         Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
     }
 }
 
 #[utoipa::path(
-    get,
-    path = "/likes/{id}",
+    post,
+    path = "/blogs/{id}/submit",
+    request_body = SubmitForReviewRequest,
     responses(
-        (status = 200, description = "Like found", body = Like),
-        (status = 404, description = "Like not found")
+        (status = 200, description = "Blog submitted for review", body = Blog),
+        (status = 400, description = "Blog is not a draft, or the reviewer is not an editor"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an author or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Blog or reviewer not found")
     ),
     params(
-        ("id" = Uuid, Path, description = "Like ID")
+        ("id" = Uuid, Path, description = "Blog ID")
     ),
-    tag = "likes"
+    tag = "reviews"
 )]
-async fn get_like_by_id(like_id: web::Path<Uuid>, pool: web::Data<DbPool>) -> impl Responder {
+async fn submit_blog_handler(blog_id: web::Path<Uuid>, request: web::Json<SubmitForReviewRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
     let pool = pool.clone();
     let result = web::block(move || {
         let mut conn = pool.get().expect("couldn't get db connection from pool");
-        get_like(&mut conn, like_id.into_inner())
+        let blog = editable_blog(&mut conn, blog_id.into_inner(), user)?;
+        if let Some(reviewer_id) = request.reviewer_id {
+            check_reviewer(&mut conn, &blog, reviewer_id)?;
+        }
+        // Publication editors submit on the owner's behalf; co-authors as themselves.
+        let actor_id = match is_blog_author(&mut conn, blog.id, user.id)? {
+            true => user.id,
+            false => blog.author_id,
+        };
+        submit_blog_for_review(&mut conn, &blog, actor_id, request.reviewer_id, request.comment.as_deref())
     }).await;
-    
+
     match result {
-        Ok(like_result) => handle_diesel_result(like_result),
+        Ok(blog_result) => handle_app_result(blog_result),
         Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
     }
-}
\ No newline at end of file
+}
+
+#[utoipa::path(
+    post,
+    path = "/blogs/{id}/withdraw",
+    request_body = ReviewCommentRequest,
+    responses(
+        (status = 200, description = "Blog taken out of review and back to draft", body = Blog),
+        (status = 400, description = "Blog is not in review"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an author, or account suspended or banned"),
+        (status = 404, description = "Blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "reviews"
+)]
+async fn withdraw_blog_handler(blog_id: web::Path<Uuid>, request: web::Json<ReviewCommentRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        workflow_step(&mut conn, blog_id.into_inner(), user, Action::Withdraw, request.comment.as_deref(), None)
+    }).await;
+
+    match result {
+        Ok(blog_result) => handle_app_result(blog_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/blogs/{id}/request-changes",
+    request_body = ReviewCommentRequest,
+    responses(
+        (status = 200, description = "Blog sent back to the author", body = Blog),
+        (status = 400, description = "Blog is not in review, or no comment given"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the assigned reviewer, or account suspended or banned"),
+        (status = 404, description = "Blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "reviews"
+)]
+async fn request_changes_handler(blog_id: web::Path<Uuid>, request: web::Json<ReviewCommentRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let comment = match request.into_inner().comment.filter(|comment| !comment.trim().is_empty()) {
+        Some(comment) => comment,
+        None => return AppError::BadRequest("a comment is required when requesting changes".to_string()).error_response(),
+    };
+
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        workflow_step(&mut conn, blog_id.into_inner(), user, Action::RequestChanges, Some(&comment), None)
+    }).await;
+
+    match result {
+        Ok(blog_result) => handle_app_result(blog_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/blogs/{id}/approve",
+    request_body = ApproveRequest,
+    responses(
+        (status = 200, description = "Blog published, or scheduled when `publish_at` is given", body = Blog),
+        (status = 400, description = "Blog is not in review, or `publish_at` is in the past"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the assigned reviewer, or account suspended or banned"),
+        (status = 404, description = "Blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "reviews"
+)]
+async fn approve_blog_handler(blog_id: web::Path<Uuid>, request: web::Json<ApproveRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let action = match request.publish_at {
+        Some(publish_at) if publish_at <= Utc::now() => {
+            return AppError::BadRequest("publish_at must be in the future".to_string()).error_response();
+        }
+        Some(_) => Action::Schedule,
+        None => Action::Approve,
+    };
+
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        workflow_step(&mut conn, blog_id.into_inner(), user, action, request.comment.as_deref(), request.publish_at)
+    }).await;
+
+    match result {
+        Ok(blog_result) => handle_app_result(blog_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/blogs/{id}/unschedule",
+    request_body = ReviewCommentRequest,
+    responses(
+        (status = 200, description = "Scheduled blog moved back into review", body = Blog),
+        (status = 400, description = "Blog is not scheduled"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the assigned reviewer, or account suspended or banned"),
+        (status = 404, description = "Blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "reviews"
+)]
+async fn unschedule_blog_handler(blog_id: web::Path<Uuid>, request: web::Json<ReviewCommentRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        workflow_step(&mut conn, blog_id.into_inner(), user, Action::Unschedule, request.comment.as_deref(), None)
+    }).await;
+
+    match result {
+        Ok(blog_result) => handle_app_result(blog_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/blogs/{id}/reviewer",
+    request_body = AssignReviewerRequest,
+    responses(
+        (status = 200, description = "Reviewer assigned", body = Blog),
+        (status = 400, description = "Reviewer is not an editor, or is the author"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an editor of the site or the blog's publication, or account suspended or banned"),
+        (status = 404, description = "Blog or reviewer not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "reviews"
+)]
+async fn assign_reviewer_handler(blog_id: web::Path<Uuid>, request: web::Json<AssignReviewerRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = get_blog(&mut conn, blog_id.into_inner())?;
+        if !can_review(&mut conn, &blog, user.id)? {
+            return Err(AppError::Forbidden);
+        }
+        check_reviewer(&mut conn, &blog, request.reviewer_id)?;
+        assign_reviewer(&mut conn, blog.id, Some(request.reviewer_id)).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(blog_result) => handle_app_result(blog_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/blogs/{id}/transitions",
+    responses(
+        (status = 200, description = "Workflow history of the blog, oldest first", body = [BlogTransition]),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the author, the reviewer or an editor"),
+        (status = 404, description = "Blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "reviews"
+)]
+async fn list_blog_transitions_handler(blog_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = get_blog(&mut conn, blog_id.into_inner())?;
+        if blog.author_id != user.id && blog.reviewer_id != Some(user.id) && !can_review(&mut conn, &blog, user.id)? {
+            return Err(AppError::Forbidden);
+        }
+        list_blog_transitions(&mut conn, blog.id).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(transitions_result) => handle_app_result(transitions_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/reviews",
+    responses(
+        (status = 200, description = "Blogs waiting in the review workflow, longest waiting first", body = [Blog]),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an editor of the site or the requested publication")
+    ),
+    params(ReviewQueueQuery),
+    tag = "reviews"
+)]
+async fn review_queue_handler(query: web::Query<ReviewQueueQuery>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let publication_editor = match query.publication_id {
+            Some(publication_id) => publication_role(&mut conn, publication_id, user.id)?.is_some_and(|role| role.manages_blogs()),
+            None => false,
+        };
+        if !publication_editor {
+            require_editor(&mut conn, user)?;
+        }
+        let limit = page_limit(query.limit);
+        let offset = query.offset.unwrap_or(0).max(0);
+        list_review_queue(&mut conn, query.status.unwrap_or(BlogStatus::InReview), query.reviewer_id, query.publication_id, limit, offset).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(blogs_result) => handle_app_result(blogs_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/blogs/{id}/revisions",
+    responses(
+        (status = 200, description = "Saved revisions of the blog, newest first", body = [BlogRevision]),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an author or a publication editor"),
+        (status = 404, description = "Blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "blogs"
+)]
+async fn list_blog_revisions_handler(blog_id: web::Path<Uuid>, user: AuthUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = get_blog(&mut conn, blog_id.into_inner())?;
+        if !can_edit_blog(&mut conn, &blog, user.id)? {
+            return Err(AppError::Forbidden);
+        }
+        list_blog_revisions(&mut conn, blog.id).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(revisions_result) => handle_app_result(revisions_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+const DEFAULT_PREVIEW_HOURS: i64 = 72;
+const MAX_PREVIEW_HOURS: i64 = 30 * 24;
+
+#[utoipa::path(
+    post,
+    path = "/blogs/{id}/previews",
+    request_body = PreviewTokenRequest,
+    responses(
+        (status = 200, description = "Preview link created", body = PreviewLink),
+        (status = 400, description = "Lifetime out of range"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an author or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Blog or revision not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "previews"
+)]
+async fn create_preview_handler(blog_id: web::Path<Uuid>, request: web::Json<PreviewTokenRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let hours = request.expires_in_hours.unwrap_or(DEFAULT_PREVIEW_HOURS);
+    if !(1..=MAX_PREVIEW_HOURS).contains(&hours) {
+        return AppError::BadRequest(format!("expires_in_hours must be between 1 and {}", MAX_PREVIEW_HOURS)).error_response();
+    }
+
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = editable_blog(&mut conn, blog_id.into_inner(), user)?;
+        if let Some(revision_id) = request.revision_id {
+            get_blog_revision(&mut conn, blog.id, revision_id)?;
+        }
+        let preview = create_preview_token(&mut conn, blog.id, request.revision_id, user.id, Utc::now() + Duration::hours(hours))?;
+        let token = issue_preview_token(&preview).map_err(|_| AppError::InternalServerError)?;
+        Ok(PreviewLink { preview, token })
+    }).await;
+
+    match result {
+        Ok(preview_result) => handle_app_result(preview_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/blogs/{id}/previews",
+    responses(
+        (status = 200, description = "Preview links that are neither revoked nor expired", body = [PreviewToken]),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an author or a publication editor"),
+        (status = 404, description = "Blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "previews"
+)]
+async fn list_previews_handler(blog_id: web::Path<Uuid>, user: AuthUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = get_blog(&mut conn, blog_id.into_inner())?;
+        if !can_edit_blog(&mut conn, &blog, user.id)? {
+            return Err(AppError::Forbidden);
+        }
+        list_active_preview_tokens(&mut conn, blog.id).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(previews_result) => handle_app_result(previews_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/blogs/{id}/previews/{preview_id}",
+    responses(
+        (status = 200, description = "Preview link revoked"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an author or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Blog not found, or no active preview link with this ID")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID"),
+        ("preview_id" = Uuid, Path, description = "Preview link ID")
+    ),
+    tag = "previews"
+)]
+async fn revoke_preview_handler(path: web::Path<(Uuid, Uuid)>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let (blog_id, preview_id) = path.into_inner();
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = editable_blog(&mut conn, blog_id, user)?;
+        match revoke_preview_token(&mut conn, blog.id, preview_id)? {
+            0 => Err(AppError::NotFound),
+            _ => Ok(()),
+        }
+    }).await;
+
+    match result {
+        Ok(revoke_result) => handle_app_result(revoke_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/preview/{token}",
+    responses(
+        (status = 200, description = "The draft, or the revision the link is pinned to", body = BlogPreview),
+        (status = 404, description = "Invalid, expired or revoked preview link")
+    ),
+    params(
+        ("token" = String, Path, description = "Signed preview token")
+    ),
+    tag = "previews"
+)]
+async fn get_preview_handler(token: web::Path<String>, pool: web::Data<DbPool>) -> impl Responder {
+    // Every kind of bad link gets the same answer, so links cannot be probed.
+    let Ok(claims) = verify_preview_token(&token) else {
+        return AppError::NotFound.error_response();
+    };
+
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let preview = get_preview_token(&mut conn, claims.sub)?;
+        if preview.blog_id != claims.blog_id || preview.revoked_at.is_some() || preview.expires_at <= Utc::now() {
+            return Err(AppError::NotFound);
+        }
+
+        let blog = get_blog(&mut conn, preview.blog_id)?;
+        let (title, content, updated_at) = match preview.revision_id {
+            Some(revision_id) => {
+                let revision = get_blog_revision(&mut conn, blog.id, revision_id)?;
+                (revision.title, revision.content, revision.created_at)
+            }
+            None => (blog.title, blog.content, blog.updated_at),
+        };
+        Ok(BlogPreview {
+            blog_id: blog.id,
+            author_id: blog.author_id,
+            status: blog.status,
+            revision_id: preview.revision_id,
+            title,
+            content,
+            updated_at,
+        })
+    }).await;
+
+    match result {
+        Ok(Ok(preview)) => HttpResponse::Ok()
+            .insert_header(("Cache-Control", "no-store"))
+            .insert_header(("X-Robots-Tag", "noindex"))
+            .json(ApiResponse::success(preview)),
+        Ok(Err(err)) => err.error_response(),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+/// Adds or removes the user's reaction of `kind` on a blog or comment and
+/// returns the target's updated reaction summary. Both directions are
+/// idempotent, and need the user to be able to read the target.
+async fn set_reaction(req: HttpRequest, pool: web::Data<DbPool>, target: ReactionTarget, target_id: Uuid, kind: String, user: ActiveUser, present: bool) -> HttpResponse {
+    if !reaction_kinds::is_enabled(&kind) {
+        return AppError::BadRequest(format!("Unknown reaction kind '{}'", kind)).error_response();
+    }
+
+    let password = blog_password(&req);
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        match target {
+            ReactionTarget::Blog => readable_blog(&mut conn, target_id, Some(user.id), password.as_deref()).map(|_| ())?,
+            ReactionTarget::Comment => readable_comment(&mut conn, target_id, Some(user.id), password.as_deref()).map(|_| ())?,
+        }
+        if present {
+            add_reaction(&mut conn, target, target_id, user.id, &kind)?;
+        } else {
+            remove_reaction(&mut conn, target, target_id, user.id, &kind)?;
+        }
+        let mut summaries = get_reaction_summaries(&mut conn, target, &[target_id], Some(user.id))?;
+        Ok::<_, AppError>(summaries.remove(&target_id).unwrap_or_default())
+    }).await;
+
+    match result {
+        Ok(summary_result) => handle_app_result(summary_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/blogs/{id}/reactions/{kind}",
+    responses(
+        (status = 200, description = "Reaction added; adding it again is a no-op", body = ReactionSummary),
+        (status = 400, description = "Reaction kind is not enabled"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Password-protected blog and the password is missing or wrong"),
+        (status = 404, description = "Blog not found, or not visible to the user")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID"),
+        ("kind" = String, Path, description = "Reaction kind, one of `GET /reactions`"),
+        ("X-Blog-Password" = Option<String>, Header, description = "Post password for password-protected blogs")
+    ),
+    tag = "reactions"
+)]
+async fn add_blog_reaction_handler(req: HttpRequest, path: web::Path<(Uuid, String)>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let (blog_id, kind) = path.into_inner();
+    set_reaction(req, pool, ReactionTarget::Blog, blog_id, kind, user, true).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/blogs/{id}/reactions/{kind}",
+    responses(
+        (status = 200, description = "Reaction removed; removing a missing reaction is a no-op", body = ReactionSummary),
+        (status = 400, description = "Reaction kind is not enabled"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Password-protected blog and the password is missing or wrong"),
+        (status = 404, description = "Blog not found, or not visible to the user")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID"),
+        ("kind" = String, Path, description = "Reaction kind, one of `GET /reactions`"),
+        ("X-Blog-Password" = Option<String>, Header, description = "Post password for password-protected blogs")
+    ),
+    tag = "reactions"
+)]
+async fn remove_blog_reaction_handler(req: HttpRequest, path: web::Path<(Uuid, String)>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let (blog_id, kind) = path.into_inner();
+    set_reaction(req, pool, ReactionTarget::Blog, blog_id, kind, user, false).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/comments/{id}/vote",
+    request_body = VoteRequest,
+    responses(
+        (status = 200, description = "Vote recorded, replacing any earlier vote", body = Comment),
+        (status = 400, description = "Vote value is not 1 or -1"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Comment on a password-protected blog and the password is missing or wrong"),
+        (status = 404, description = "Comment not found, or not visible to the user")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Comment ID"),
+        ("X-Blog-Password" = Option<String>, Header, description = "Post password when the comment is on a password-protected blog")
+    ),
+    tag = "comments"
+)]
+async fn vote_comment_handler(req: HttpRequest, comment_id: web::Path<Uuid>, vote: web::Json<VoteRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    if vote.value != 1 && vote.value != -1 {
+        return AppError::BadRequest("Vote value must be 1 or -1".to_string()).error_response();
+    }
+
+    let password = blog_password(&req);
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let comment = readable_comment(&mut conn, comment_id.into_inner(), Some(user.id), password.as_deref())?;
+        vote_comment(&mut conn, comment.id, user.id, vote.value).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(comment_result) => handle_app_result(comment_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/comments/{id}/vote",
+    responses(
+        (status = 200, description = "Vote removed; removing a missing vote is a no-op", body = Comment),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Comment on a password-protected blog and the password is missing or wrong"),
+        (status = 404, description = "Comment not found, or not visible to the user")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Comment ID"),
+        ("X-Blog-Password" = Option<String>, Header, description = "Post password when the comment is on a password-protected blog")
+    ),
+    tag = "comments"
+)]
+async fn clear_comment_vote_handler(req: HttpRequest, comment_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let password = blog_password(&req);
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let comment = readable_comment(&mut conn, comment_id.into_inner(), Some(user.id), password.as_deref())?;
+        clear_comment_vote(&mut conn, comment.id, user.id).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(comment_result) => handle_app_result(comment_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/comments/{id}/reactions/{kind}",
+    responses(
+        (status = 200, description = "Reaction added; adding it again is a no-op", body = ReactionSummary),
+        (status = 400, description = "Reaction kind is not enabled"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Comment on a password-protected blog and the password is missing or wrong"),
+        (status = 404, description = "Comment not found, or not visible to the user")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Comment ID"),
+        ("kind" = String, Path, description = "Reaction kind, one of `GET /reactions`"),
+        ("X-Blog-Password" = Option<String>, Header, description = "Post password when the comment is on a password-protected blog")
+    ),
+    tag = "reactions"
+)]
+async fn add_comment_reaction_handler(req: HttpRequest, path: web::Path<(Uuid, String)>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let (comment_id, kind) = path.into_inner();
+    set_reaction(req, pool, ReactionTarget::Comment, comment_id, kind, user, true).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/comments/{id}/reactions/{kind}",
+    responses(
+        (status = 200, description = "Reaction removed; removing a missing reaction is a no-op", body = ReactionSummary),
+        (status = 400, description = "Reaction kind is not enabled"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Comment on a password-protected blog and the password is missing or wrong"),
+        (status = 404, description = "Comment not found, or not visible to the user")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Comment ID"),
+        ("kind" = String, Path, description = "Reaction kind, one of `GET /reactions`"),
+        ("X-Blog-Password" = Option<String>, Header, description = "Post password when the comment is on a password-protected blog")
+    ),
+    tag = "reactions"
+)]
+async fn remove_comment_reaction_handler(req: HttpRequest, path: web::Path<(Uuid, String)>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let (comment_id, kind) = path.into_inner();
+    set_reaction(req, pool, ReactionTarget::Comment, comment_id, kind, user, false).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/reactions",
+    responses(
+        (status = 200, description = "Reaction kinds enabled on this site", body = [String])
+    ),
+    tag = "reactions"
+)]
+async fn list_reaction_kinds() -> impl Responder {
+    HttpResponse::Ok().json(ApiResponse::success(reaction_kinds::configured()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/comments",
+    request_body = Comment,
+    responses(
+        (status = 200, description = "Comment created successfully", body = Comment),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Post password missing or wrong, blog not published yet, comments closed, locked or limited to followers, blocked by the blog's author or by the author of the parent comment, or account suspended or banned"),
+        (status = 404, description = "Blog or parent comment not found, or the blog is not visible to the user"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("X-Blog-Password" = Option<String>, Header, description = "Post password for password-protected blogs")
+    ),
+    tag = "comments"
+)]
+async fn create_comment_handler(req: HttpRequest, comment: web::Json<Comment>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let password = blog_password(&req);
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        create_comment(&mut conn, comment.blog_id, user.id, &comment.content, comment.parent_comment_id, password.as_deref())
+    }).await;
+    
+    match result {
+        Ok(comment_result) => handle_app_result(comment_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/comments/{id}",
+    responses(
+        (status = 200, description = "Comment found", body = CommentResponse),
+        (status = 304, description = "The cached copy is current"),
+        (status = 403, description = "Comment on a password-protected blog and the password is missing or wrong"),
+        (status = 404, description = "Comment not found, its blog is not visible to the viewer, or comments on the blog are closed")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Comment ID"),
+        ("X-Blog-Password" = Option<String>, Header, description = "Post password when the comment is on a password-protected blog"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag of the cached copy"),
+        ("If-Modified-Since" = Option<String>, Header, description = "Last-Modified of the cached copy")
+    ),
+    tag = "comments"
+)]
+async fn get_comment_by_id(req: HttpRequest, comment_id: web::Path<Uuid>, viewer: Option<AuthUser>, pool: web::Data<DbPool>) -> impl Responder {
+    let password = blog_password(&req);
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let viewer_id = viewer.map(|user| user.id);
+        let comment = readable_comment(&mut conn, comment_id.into_inner(), viewer_id, password.as_deref())?;
+        get_comment_response(&mut conn, comment.id, viewer_id).map_err(AppError::from)
+    }).await;
+    
+    match result {
+        Ok(Ok(comment)) => {
+            // Votes and reactions touch `updated_at` too
+            let mut response = Validators::for_json(&comment, Some(comment.comment.updated_at)).respond_json(&req, comment);
+            response.headers_mut().insert(header::VARY, header::HeaderValue::from_static("Authorization"));
+            response
+        }
+        Ok(Err(err)) => err.error_response(),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/comments/{id}",
+    request_body = Comment,
+    responses(
+        (status = 200, description = "Comment updated successfully", body = Comment),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the author, or account suspended or banned"),
+        (status = 404, description = "Comment not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Comment ID")
+    ),
+    tag = "comments"
+)]
+async fn update_comment_handler(comment_id: web::Path<Uuid>, comment: web::Json<Comment>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let existing = get_comment(&mut conn, comment_id.into_inner())?;
+        if existing.user_id != user.id {
+            return Err(AppError::Forbidden);
+        }
+        update_comment(&mut conn, existing.id, &comment.content).map_err(AppError::from)
+    }).await;
+    
+    match result {
+        Ok(comment_result) => handle_app_result(comment_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/comments/{id}",
+    responses(
+        (status = 200, description = "Comment deleted successfully"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Neither the author nor a moderator, or account suspended or banned"),
+        (status = 404, description = "Comment not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Comment ID")
+    ),
+    tag = "comments"
+)]
+async fn delete_comment_handler(comment_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let comment = get_comment(&mut conn, comment_id.into_inner())?;
+        if comment.user_id != user.id && require_moderator(&mut conn, user).is_err() {
+            return Err(AppError::Forbidden);
+        }
+        remove_comment(&mut conn, &comment, user.id).map_err(AppError::from)
+    }).await;
+    
+    match result {
+        Ok(delete_result) => handle_app_result(delete_result.map(|_| ())),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/likes",
+    request_body = Like,
+    responses(
+        (status = 200, description = "Like created successfully", body = Like),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Account suspended or banned, or password-protected blog and the password is missing or wrong"),
+        (status = 404, description = "Blog not found, or not visible to the user"),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("X-Blog-Password" = Option<String>, Header, description = "Post password for password-protected blogs")
+    ),
+    tag = "likes"
+)]
+async fn create_like_handler(req: HttpRequest, like: web::Json<Like>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let password = blog_password(&req);
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = readable_blog(&mut conn, like.blog_id, Some(user.id), password.as_deref())?;
+        create_like(&mut conn, blog.id, user.id).map_err(AppError::from)
+    }).await;
+    
+    match result {
+        Ok(like_result) => handle_app_result(like_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/likes/{id}",
+    responses(
+        (status = 200, description = "Like found", body = Like),
+        (status = 404, description = "Like not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Like ID")
+    ),
+    tag = "likes"
+)]
+async fn get_like_by_id(like_id: web::Path<Uuid>, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        get_like(&mut conn, like_id.into_inner())
+    }).await;
+    
+    match result {
+        Ok(like_result) => handle_diesel_result(like_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/likes/{id}",
+    responses(
+        (status = 200, description = "Like deleted successfully"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Like belongs to another user"),
+        (status = 404, description = "Like not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Like ID")
+    ),
+    tag = "likes"
+)]
+async fn delete_like_handler(like_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let like = get_like(&mut conn, like_id.into_inner())?;
+        if like.user_id != user.id {
+            return Err(AppError::Forbidden);
+        }
+        delete_like(&mut conn, like.id).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(delete_result) => handle_app_result(delete_result.map(|_| ())),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+fn owned_reading_list(conn: &mut PgConnection, list_id: Uuid, user: ActiveUser) -> Result<ReadingList, AppError> {
+    let list = get_reading_list(conn, list_id)?;
+    if list.user_id != user.id {
+        return Err(AppError::Forbidden);
+    }
+    Ok(list)
+}
+
+#[utoipa::path(
+    get,
+    path = "/reading-lists",
+    responses(
+        (status = 200, description = "Reading lists of the authenticated user", body = [ReadingList]),
+        (status = 401, description = "Not authenticated")
+    ),
+    tag = "reading-lists"
+)]
+async fn list_my_reading_lists_handler(user: AuthUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        list_user_reading_lists(&mut conn, user.id, false)
+    }).await;
+
+    match result {
+        Ok(lists_result) => handle_diesel_result(lists_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/{id}/reading-lists",
+    responses(
+        (status = 200, description = "Public reading lists of the user, or all of them for the user themselves", body = [ReadingList]),
+        (status = 500, description = "Internal server error")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "User ID")
+    ),
+    tag = "reading-lists"
+)]
+async fn list_user_reading_lists_handler(user_id: web::Path<Uuid>, viewer: Option<AuthUser>, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let user_id = user_id.into_inner();
+        let public_only = viewer.map(|viewer| viewer.id) != Some(user_id);
+        list_user_reading_lists(&mut conn, user_id, public_only)
+    }).await;
+
+    match result {
+        Ok(lists_result) => handle_diesel_result(lists_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/reading-lists",
+    request_body = ReadingListRequest,
+    responses(
+        (status = 200, description = "Reading list created successfully", body = ReadingList),
+        (status = 401, description = "Not authenticated")
+    ),
+    tag = "reading-lists"
+)]
+async fn create_reading_list_handler(list: web::Json<ReadingListRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        create_reading_list(&mut conn, user.id, &list.name, list.description.as_deref(), list.is_public)
+    }).await;
+
+    match result {
+        Ok(list_result) => handle_diesel_result(list_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/reading-lists/{id}",
+    responses(
+        (status = 200, description = "Reading list with its items; public lists are readable by anyone", body = ReadingListDetail),
+        (status = 404, description = "Reading list not found or private")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Reading list ID")
+    ),
+    tag = "reading-lists"
+)]
+async fn get_reading_list_handler(list_id: web::Path<Uuid>, viewer: Option<AuthUser>, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let list = get_reading_list(&mut conn, list_id.into_inner())?;
+        if !list.is_public && viewer.map(|viewer| viewer.id) != Some(list.user_id) {
+            return Err(AppError::NotFound);
+        }
+        get_reading_list_detail(&mut conn, list, viewer.map(|viewer| viewer.id)).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(list_result) => handle_app_result(list_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/reading-lists/{id}",
+    request_body = ReadingListRequest,
+    responses(
+        (status = 200, description = "Reading list updated successfully", body = ReadingList),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Reading list belongs to another user"),
+        (status = 404, description = "Reading list not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Reading list ID")
+    ),
+    tag = "reading-lists"
+)]
+async fn update_reading_list_handler(list_id: web::Path<Uuid>, list: web::Json<ReadingListRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let existing = owned_reading_list(&mut conn, list_id.into_inner(), user)?;
+        update_reading_list(&mut conn, existing.id, &list.name, list.description.as_deref(), list.is_public).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(list_result) => handle_app_result(list_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/reading-lists/{id}",
+    responses(
+        (status = 200, description = "Reading list deleted successfully"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Reading list belongs to another user"),
+        (status = 404, description = "Reading list not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Reading list ID")
+    ),
+    tag = "reading-lists"
+)]
+async fn delete_reading_list_handler(list_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let list = owned_reading_list(&mut conn, list_id.into_inner(), user)?;
+        delete_reading_list(&mut conn, list.id).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(delete_result) => handle_app_result(delete_result.map(|_| ())),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/reading-lists/{id}/items",
+    request_body = ReadingListItemRequest,
+    responses(
+        (status = 200, description = "Blog saved at the end of the list; saving it again returns the existing item", body = ReadingListItem),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Reading list belongs to another user"),
+        (status = 404, description = "Reading list or blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Reading list ID")
+    ),
+    tag = "reading-lists"
+)]
+async fn add_reading_list_item_handler(list_id: web::Path<Uuid>, item: web::Json<ReadingListItemRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let list = owned_reading_list(&mut conn, list_id.into_inner(), user)?;
+        let blog = get_blog(&mut conn, item.blog_id)?;
+        if blog.visibility != BlogVisibility::Password.as_str() && !can_read_blog(&mut conn, &blog, Some(user.id), None)? {
+            return Err(AppError::NotFound);
+        }
+        add_reading_list_item(&mut conn, list.id, &blog).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(item_result) => handle_app_result(item_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/reading-lists/{id}/items/{item_id}",
+    responses(
+        (status = 200, description = "Item removed from the list"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Reading list belongs to another user"),
+        (status = 404, description = "Reading list or item not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Reading list ID"),
+        ("item_id" = Uuid, Path, description = "Reading list item ID")
+    ),
+    tag = "reading-lists"
+)]
+async fn remove_reading_list_item_handler(path: web::Path<(Uuid, Uuid)>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let (list_id, item_id) = path.into_inner();
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let list = owned_reading_list(&mut conn, list_id, user)?;
+        let item = get_reading_list_item(&mut conn, item_id)?;
+        if item.list_id != list.id {
+            return Err(AppError::NotFound);
+        }
+        delete_reading_list_item(&mut conn, item.id).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(delete_result) => handle_app_result(delete_result.map(|_| ())),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/reading-lists/{id}/items/order",
+    request_body = ReorderRequest,
+    responses(
+        (status = 200, description = "Items reordered", body = ReadingListDetail),
+        (status = 400, description = "`item_ids` is not exactly the items of the list"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Reading list belongs to another user"),
+        (status = 404, description = "Reading list not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Reading list ID")
+    ),
+    tag = "reading-lists"
+)]
+async fn reorder_reading_list_handler(list_id: web::Path<Uuid>, order: web::Json<ReorderRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let list = owned_reading_list(&mut conn, list_id.into_inner(), user)?;
+
+        let mut current: Vec<Uuid> = list_reading_list_items(&mut conn, list.id)?.into_iter().map(|item| item.id).collect();
+        let mut requested = order.item_ids.clone();
+        current.sort();
+        requested.sort();
+        if current != requested {
+            return Err(AppError::BadRequest("item_ids must list every item of the reading list exactly once".to_string()));
+        }
+
+        reorder_reading_list_items(&mut conn, list.id, &order.item_ids)?;
+        let list = get_reading_list(&mut conn, list.id)?;
+        get_reading_list_detail(&mut conn, list, Some(user.id)).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(list_result) => handle_app_result(list_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+#[utoipa::path(
+    put,
+    path = "/users/{id}/follow",
+    responses(
+        (status = 200, description = "Now following the user; repeating is a no-op", body = Follow),
+        (status = 400, description = "Users cannot follow themselves"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "One of the users has blocked the other"),
+        (status = 404, description = "User not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID of the user to follow")
+    ),
+    tag = "follows"
+)]
+async fn follow_user_handler(user_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let followee = get_user(&mut conn, user_id.into_inner())?;
+        if followee.id == user.id {
+            return Err(AppError::BadRequest("users cannot follow themselves".to_string()));
+        }
+        follow_user(&mut conn, user.id, followee.id)
+    }).await;
+
+    match result {
+        Ok(follow_result) => handle_app_result(follow_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/users/{id}/follow",
+    responses(
+        (status = 200, description = "No longer following the user"),
+        (status = 401, description = "Not authenticated"),
+        (status = 404, description = "Not following the user")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID of the user to unfollow")
+    ),
+    tag = "follows"
+)]
+async fn unfollow_user_handler(user_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        match unfollow_user(&mut conn, user.id, user_id.into_inner())? {
+            0 => Err(AppError::NotFound),
+            _ => Ok(()),
+        }
+    }).await;
+
+    match result {
+        Ok(unfollow_result) => handle_app_result(unfollow_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/{id}/followers",
+    responses(
+        (status = 200, description = "Users following this user, most recent first", body = FollowPage),
+        (status = 404, description = "User not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "User ID"),
+        PageQuery
+    ),
+    tag = "follows"
+)]
+async fn list_followers_handler(user_id: web::Path<Uuid>, query: web::Query<PageQuery>, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let user = get_user(&mut conn, user_id.into_inner())?;
+        let limit = page_limit(query.limit);
+        let offset = query.offset.unwrap_or(0).max(0);
+        Ok::<_, AppError>(FollowPage {
+            total: count_followers(&mut conn, user.id)?,
+            users: list_followers(&mut conn, user.id, limit, offset)?,
+        })
+    }).await;
+
+    match result {
+        Ok(page_result) => handle_app_result(page_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/{id}/following",
+    responses(
+        (status = 200, description = "Users this user follows, most recent first", body = FollowPage),
+        (status = 404, description = "User not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "User ID"),
+        PageQuery
+    ),
+    tag = "follows"
+)]
+async fn list_following_handler(user_id: web::Path<Uuid>, query: web::Query<PageQuery>, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let user = get_user(&mut conn, user_id.into_inner())?;
+        let limit = page_limit(query.limit);
+        let offset = query.offset.unwrap_or(0).max(0);
+        Ok::<_, AppError>(FollowPage {
+            total: count_following(&mut conn, user.id)?,
+            users: list_following(&mut conn, user.id, limit, offset)?,
+        })
+    }).await;
+
+    match result {
+        Ok(page_result) => handle_app_result(page_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/feed",
+    responses(
+        (status = 200, description = "Most recently published blogs by followed authors and co-authors", body = FeedPage),
+        (status = 400, description = "Malformed cursor"),
+        (status = 401, description = "Not authenticated")
+    ),
+    params(CursorQuery),
+    tag = "follows"
+)]
+async fn feed_handler(query: web::Query<CursorQuery>, user: AuthUser, pool: web::Data<DbPool>) -> impl Responder {
+    let cursor = match query.cursor.as_deref().map(Cursor::decode) {
+        Some(None) => return AppError::BadRequest("invalid cursor".to_string()).error_response(),
+        Some(cursor) => cursor,
+        None => None,
+    };
+    let limit = page_limit(query.limit);
+
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        // Fetch one extra row to learn whether there is a next page.
+        let mut blogs = get_feed(&mut conn, user.id, cursor, limit + 1)?;
+        let next_cursor = if blogs.len() as i64 > limit {
+            blogs.truncate(limit as usize);
+            blogs.last().and_then(|blog| Some(Cursor { at: blog.published_at?, id: blog.id }.encode()))
+        } else {
+            None
+        };
+        Ok(FeedPage {
+            blogs: get_blog_responses(&mut conn, blogs, Some(user.id))?,
+            next_cursor,
+        })
+    }).await;
+
+    match result {
+        Ok(feed_result) => handle_diesel_result(feed_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/users/{id}/block",
+    responses(
+        (status = 200, description = "User blocked; any follow between the two users is removed", body = UserBlock),
+        (status = 400, description = "Users cannot block themselves"),
+        (status = 401, description = "Not authenticated"),
+        (status = 404, description = "User not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID of the user to block")
+    ),
+    tag = "blocks"
+)]
+async fn block_user_handler(user_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let target = get_user(&mut conn, user_id.into_inner())?;
+        if target.id == user.id {
+            return Err(AppError::BadRequest("users cannot block themselves".to_string()));
+        }
+        block_user(&mut conn, user.id, target.id).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(block_result) => handle_app_result(block_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/users/{id}/block",
+    responses(
+        (status = 200, description = "User unblocked"),
+        (status = 401, description = "Not authenticated"),
+        (status = 404, description = "User was not blocked")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID of the user to unblock")
+    ),
+    tag = "blocks"
+)]
+async fn unblock_user_handler(user_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        match unblock_user(&mut conn, user.id, user_id.into_inner())? {
+            0 => Err(AppError::NotFound),
+            _ => Ok(()),
+        }
+    }).await;
+
+    match result {
+        Ok(unblock_result) => handle_app_result(unblock_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/users/{id}/mute",
+    responses(
+        (status = 200, description = "User muted; their blogs no longer appear in the feed", body = UserMute),
+        (status = 400, description = "Users cannot mute themselves"),
+        (status = 401, description = "Not authenticated"),
+        (status = 404, description = "User not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID of the user to mute")
+    ),
+    tag = "blocks"
+)]
+async fn mute_user_handler(user_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let target = get_user(&mut conn, user_id.into_inner())?;
+        if target.id == user.id {
+            return Err(AppError::BadRequest("users cannot mute themselves".to_string()));
+        }
+        mute_user(&mut conn, user.id, target.id).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(mute_result) => handle_app_result(mute_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/users/{id}/mute",
+    responses(
+        (status = 200, description = "User unmuted"),
+        (status = 401, description = "Not authenticated"),
+        (status = 404, description = "User was not muted")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "ID of the user to unmute")
+    ),
+    tag = "blocks"
+)]
+async fn unmute_user_handler(user_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        match unmute_user(&mut conn, user.id, user_id.into_inner())? {
+            0 => Err(AppError::NotFound),
+            _ => Ok(()),
+        }
+    }).await;
+
+    match result {
+        Ok(unmute_result) => handle_app_result(unmute_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/blocks",
+    responses(
+        (status = 200, description = "Users blocked by the authenticated user", body = [UserSummary]),
+        (status = 401, description = "Not authenticated")
+    ),
+    tag = "blocks"
+)]
+async fn list_blocks_handler(user: AuthUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        list_blocked_users(&mut conn, user.id)
+    }).await;
+
+    match result {
+        Ok(users_result) => handle_diesel_result(users_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/mutes",
+    responses(
+        (status = 200, description = "Users muted by the authenticated user", body = [UserSummary]),
+        (status = 401, description = "Not authenticated")
+    ),
+    tag = "blocks"
+)]
+async fn list_mutes_handler(user: AuthUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        list_muted_users(&mut conn, user.id)
+    }).await;
+
+    match result {
+        Ok(users_result) => handle_diesel_result(users_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+fn has_role(user: &User, roles: &[&str]) -> bool {
+    roles.contains(&user.role.as_str())
+}
+
+fn require_moderator(conn: &mut PgConnection, user: ActiveUser) -> Result<User, AppError> {
+    let user = get_user(conn, user.id)?;
+    if !has_role(&user, &["moderator", "admin"]) {
+        return Err(AppError::Forbidden);
+    }
+    Ok(user)
+}
+
+fn require_admin(conn: &mut PgConnection, user: ActiveUser) -> Result<User, AppError> {
+    let user = get_user(conn, user.id)?;
+    if !has_role(&user, &["admin"]) {
+        return Err(AppError::Forbidden);
+    }
+    Ok(user)
+}
+
+#[utoipa::path(
+    post,
+    path = "/reports",
+    request_body = ReportRequest,
+    responses(
+        (status = 200, description = "Report filed", body = Report),
+        (status = 401, description = "Not authenticated"),
+        (status = 404, description = "Reported blog, comment or user not found")
+    ),
+    tag = "moderation"
+)]
+async fn create_report_handler(report: web::Json<ReportRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        match report.target_type {
+            ReportTarget::Blog => get_blog(&mut conn, report.target_id).map(|_| ())?,
+            ReportTarget::Comment => get_comment(&mut conn, report.target_id).map(|_| ())?,
+            ReportTarget::User => get_user(&mut conn, report.target_id).map(|_| ())?,
+        }
+        create_report(&mut conn, user.id, report.target_type, report.target_id, report.reason, report.details.as_deref()).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(report_result) => handle_app_result(report_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/moderation/reports",
+    responses(
+        (status = 200, description = "Reports in the queue, oldest first", body = [Report]),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a moderator")
+    ),
+    params(ReportQueueQuery),
+    tag = "moderation"
+)]
+async fn list_reports_handler(query: web::Query<ReportQueueQuery>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        require_moderator(&mut conn, user)?;
+        let limit = page_limit(query.limit);
+        let offset = query.offset.unwrap_or(0).max(0);
+        list_reports(&mut conn, query.status.unwrap_or(ReportStatus::Open), query.assignee_id, limit, offset).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(reports_result) => handle_app_result(reports_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/moderation/reports/{id}",
+    responses(
+        (status = 200, description = "Report with the actions taken on it", body = ReportDetail),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a moderator"),
+        (status = 404, description = "Report not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Report ID")
+    ),
+    tag = "moderation"
+)]
+async fn get_report_handler(report_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        require_moderator(&mut conn, user)?;
+        let report = get_report(&mut conn, report_id.into_inner())?;
+        get_report_detail(&mut conn, report).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(report_result) => handle_app_result(report_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/moderation/reports/{id}/assignee",
+    request_body = AssignReportRequest,
+    responses(
+        (status = 200, description = "Report assigned or unassigned", body = Report),
+        (status = 400, description = "Assignee is not a moderator"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a moderator"),
+        (status = 404, description = "Report not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Report ID")
+    ),
+    tag = "moderation"
+)]
+async fn assign_report_handler(report_id: web::Path<Uuid>, assignment: web::Json<AssignReportRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let moderator = require_moderator(&mut conn, user)?;
+        let report = get_report(&mut conn, report_id.into_inner())?;
+        if let Some(assignee_id) = assignment.assignee_id {
+            let assignee = get_user(&mut conn, assignee_id)?;
+            if !has_role(&assignee, &["moderator", "admin"]) {
+                return Err(AppError::BadRequest("reports can only be assigned to moderators".to_string()));
+            }
+        }
+        assign_report(&mut conn, report.id, assignment.assignee_id, moderator.id).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(report_result) => handle_app_result(report_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/moderation/reports/{id}/resolve",
+    request_body = ResolveReportRequest,
+    responses(
+        (status = 200, description = "Report resolved, with the action taken on its target", body = ReportDetail),
+        (status = 400, description = "Report already resolved, or the action does not fit the status or target"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a moderator, or the action targets a user whose role is not below the moderator's"),
+        (status = 404, description = "Report not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Report ID")
+    ),
+    tag = "moderation"
+)]
+async fn resolve_report_handler(report_id: web::Path<Uuid>, resolution: web::Json<ResolveReportRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let moderator = require_moderator(&mut conn, user)?;
+        let report = get_report(&mut conn, report_id.into_inner())?;
+        if report.status != ReportStatus::Open.as_str() {
+            return Err(AppError::BadRequest("report is already resolved".to_string()));
+        }
+        match (resolution.status, resolution.action) {
+            (ReportStatus::Open, _) => {
+                return Err(AppError::BadRequest("status must be actioned or dismissed".to_string()));
+            }
+            (ReportStatus::Dismissed, Some(_)) => {
+                return Err(AppError::BadRequest("a dismissed report cannot carry an action".to_string()));
+            }
+            (_, Some(ModerationAction::RemoveContent)) if report.target_type == ReportTarget::User.as_str() => {
+                return Err(AppError::BadRequest("remove_content does not apply to reported users".to_string()));
+            }
+            (_, Some(ModerationAction::SuspendUser)) if resolution.suspended_until.is_none_or(|until| until <= Utc::now()) => {
+                return Err(AppError::BadRequest("suspend_user needs a suspended_until in the future".to_string()));
+            }
+            _ => {}
+        }
+        // Moderators only act on users ranked below them; content that is
+        // already gone has no one left to protect.
+        if resolution.action.is_some() {
+            match report_target_user(&mut conn, &report) {
+                Ok(target_id) => {
+                    if get_user(&mut conn, target_id)?.role() >= moderator.role() {
+                        return Err(AppError::Forbidden);
+                    }
+                }
+                Err(diesel::result::Error::NotFound) => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        let report = resolve_report(&mut conn, &report, moderator.id, resolution.status, resolution.notes.as_deref(), resolution.action, resolution.suspended_until)?;
+        get_report_detail(&mut conn, report).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(report_result) => handle_app_result(report_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/admin/users/{id}/role",
+    request_body = RoleRequest,
+    responses(
+        (status = 200, description = "Role changed", body = AdminUser),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin"),
+        (status = 404, description = "User not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "User ID")
+    ),
+    tag = "admin"
+)]
+async fn set_user_role_handler(user_id: web::Path<Uuid>, role: web::Json<RoleRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let admin = require_admin(&mut conn, user)?;
+        set_user_role(&mut conn, user_id.into_inner(), role.role, admin.id).map(AdminUser::from).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(user_result) => handle_app_result(user_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/admin/users/{id}/status",
+    request_body = AccountStatusRequest,
+    responses(
+        (status = 200, description = "Account status changed", body = AdminUser),
+        (status = 400, description = "Suspension without a future expiry, or an admin changing their own status"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an admin"),
+        (status = 404, description = "User not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "User ID")
+    ),
+    tag = "admin"
+)]
+async fn set_account_status_handler(user_id: web::Path<Uuid>, status: web::Json<AccountStatusRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let admin = require_admin(&mut conn, user)?;
+        let target = get_user(&mut conn, user_id.into_inner())?;
+        if target.id == admin.id {
+            return Err(AppError::BadRequest("admins cannot change their own account status".to_string()));
+        }
+        if status.expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+            return Err(AppError::BadRequest("expires_at must be in the future".to_string()));
+        }
+        if status.status == AccountStatus::Suspended && status.expires_at.is_none() {
+            return Err(AppError::BadRequest("a suspension needs an expires_at".to_string()));
+        }
+        let expires_at = match status.status {
+            AccountStatus::Active => None,
+            _ => status.expires_at,
+        };
+        set_account_status(&mut conn, target.id, status.status, status.reason.as_deref(), expires_at, admin.id, None).map(AdminUser::from).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(user_result) => handle_app_result(user_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+fn validate_slug(slug: &str) -> Result<(), AppError> {
+    let slug_ok = (1..=64).contains(&slug.len())
+        && slug.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !slug.starts_with('-')
+        && !slug.ends_with('-');
+    if !slug_ok {
+        return Err(AppError::BadRequest("slug must be 1-64 lowercase letters, digits or dashes".to_string()));
+    }
+    Ok(())
+}
+
+/// Checks the slug and brand colour of a publication request.
+fn validate_publication(request: &PublicationRequest) -> Result<(), AppError> {
+    if request.name.trim().is_empty() {
+        return Err(AppError::BadRequest("name must not be empty".to_string()));
+    }
+    validate_slug(&request.slug)?;
+    if let Some(color) = &request.accent_color {
+        let hex = color.strip_prefix('#').unwrap_or_default();
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(AppError::BadRequest("accent_color must be a hex colour like #1a73e8".to_string()));
+        }
+    }
+    Ok(())
+}
+
+fn require_publication_owner(conn: &mut PgConnection, publication_id: Uuid, user: ActiveUser) -> Result<Publication, AppError> {
+    let publication = get_publication(conn, publication_id)?;
+    if publication_role(conn, publication.id, user.id)? != Some(PublicationRole::Owner) {
+        return Err(AppError::Forbidden);
+    }
+    Ok(publication)
+}
+
+#[utoipa::path(
+    post,
+    path = "/publications",
+    request_body = PublicationRequest,
+    responses(
+        (status = 200, description = "Publication created with the authenticated user as owner", body = Publication),
+        (status = 400, description = "Invalid name, slug or colour, or slug already taken"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Account suspended or banned")
+    ),
+    tag = "publications"
+)]
+async fn create_publication_handler(request: web::Json<PublicationRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    if let Err(err) = validate_publication(&request) {
+        return err.error_response();
+    }
+
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        create_publication(&mut conn, &request, user.id)
+    }).await;
+
+    match result {
+        Ok(publication_result) => handle_app_result(publication_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/publications/{id}",
+    responses(
+        (status = 200, description = "Publication settings and branding", body = Publication),
+        (status = 404, description = "Publication not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Publication ID")
+    ),
+    tag = "publications"
+)]
+async fn get_publication_handler(publication_id: web::Path<Uuid>, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        get_publication(&mut conn, publication_id.into_inner()).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(publication_result) => handle_app_result(publication_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/publications/{id}",
+    request_body = PublicationRequest,
+    responses(
+        (status = 200, description = "Publication updated", body = Publication),
+        (status = 400, description = "Invalid name, slug or colour, or slug already taken"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an owner, or account suspended or banned"),
+        (status = 404, description = "Publication not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Publication ID")
+    ),
+    tag = "publications"
+)]
+async fn update_publication_handler(publication_id: web::Path<Uuid>, request: web::Json<PublicationRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    if let Err(err) = validate_publication(&request) {
+        return err.error_response();
+    }
+
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let publication = require_publication_owner(&mut conn, publication_id.into_inner(), user)?;
+        update_publication(&mut conn, &publication, &request)
+    }).await;
+
+    match result {
+        Ok(publication_result) => handle_app_result(publication_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/publications/{id}",
+    responses(
+        (status = 200, description = "Publication deleted; its blogs stay with their authors"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an owner, or account suspended or banned"),
+        (status = 404, description = "Publication not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Publication ID")
+    ),
+    tag = "publications"
+)]
+async fn delete_publication_handler(publication_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let publication = require_publication_owner(&mut conn, publication_id.into_inner(), user)?;
+        delete_publication(&mut conn, publication.id).map(|_| ()).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(delete_result) => handle_app_result(delete_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/publications/{id}/blogs",
+    responses(
+        (status = 200, description = "Page of the publication's blogs visible to the viewer", body = [BlogResponse]),
+        (status = 404, description = "Publication not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Publication ID"),
+        BlogListQuery
+    ),
+    tag = "publications"
+)]
+async fn list_publication_blogs_handler(publication_id: web::Path<Uuid>, query: web::Query<BlogListQuery>, viewer: Option<AuthUser>, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let publication = get_publication(&mut conn, publication_id.into_inner())?;
+        let limit = page_limit(query.limit);
+        let offset = query.offset.unwrap_or(0).max(0);
+        let blogs = list_blogs(&mut conn, query.sort.unwrap_or_default(), limit, offset, viewer.map(|user| user.id), Some(publication.id), None)?;
+        get_blog_responses(&mut conn, blogs, viewer.map(|user| user.id)).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(blogs_result) => handle_app_result(blogs_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/publications/{id}/members",
+    responses(
+        (status = 200, description = "Members of the publication with their roles", body = [PublicationMember]),
+        (status = 404, description = "Publication not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Publication ID")
+    ),
+    tag = "publications"
+)]
+async fn list_publication_members_handler(publication_id: web::Path<Uuid>, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let publication = get_publication(&mut conn, publication_id.into_inner())?;
+        list_publication_members(&mut conn, publication.id).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(members_result) => handle_app_result(members_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/publications/{id}/members/{user_id}",
+    request_body = MemberRoleRequest,
+    responses(
+        (status = 200, description = "Member added or role changed"),
+        (status = 400, description = "Would leave the publication without an owner"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an owner, or account suspended or banned"),
+        (status = 404, description = "Publication or user not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Publication ID"),
+        ("user_id" = Uuid, Path, description = "User to add or update")
+    ),
+    tag = "publications"
+)]
+async fn set_publication_member_handler(path: web::Path<(Uuid, Uuid)>, request: web::Json<MemberRoleRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let (publication_id, member_id) = path.into_inner();
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let publication = require_publication_owner(&mut conn, publication_id, user)?;
+        get_user(&mut conn, member_id)?;
+        set_publication_member(&mut conn, publication.id, member_id, request.role)
+    }).await;
+
+    match result {
+        Ok(member_result) => handle_app_result(member_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/publications/{id}/members/{user_id}",
+    responses(
+        (status = 200, description = "Member removed; their blogs stay in the publication"),
+        (status = 400, description = "Would leave the publication without an owner"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Neither an owner nor the member themselves, or account suspended or banned"),
+        (status = 404, description = "Publication not found, or the user is not a member")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Publication ID"),
+        ("user_id" = Uuid, Path, description = "Member to remove")
+    ),
+    tag = "publications"
+)]
+async fn remove_publication_member_handler(path: web::Path<(Uuid, Uuid)>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let (publication_id, member_id) = path.into_inner();
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let publication = match member_id == user.id {
+            true => get_publication(&mut conn, publication_id)?,
+            false => require_publication_owner(&mut conn, publication_id, user)?,
+        };
+        match remove_publication_member(&mut conn, publication.id, member_id)? {
+            0 => Err(AppError::NotFound),
+            _ => Ok(()),
+        }
+    }).await;
+
+    match result {
+        Ok(remove_result) => handle_app_result(remove_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/blogs/{id}/publication",
+    request_body = BlogPublicationRequest,
+    responses(
+        (status = 200, description = "Blog moved into or out of a publication", body = Blog),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Cannot manage the blog, or the caller or author is not a member of the target publication"),
+        (status = 404, description = "Blog or publication not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "publications"
+)]
+async fn set_blog_publication_handler(blog_id: web::Path<Uuid>, request: web::Json<BlogPublicationRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = authored_blog(&mut conn, blog_id.into_inner(), user)?;
+        if let Some(publication_id) = request.publication_id {
+            get_publication(&mut conn, publication_id)?;
+            if publication_role(&mut conn, publication_id, user.id)?.is_none()
+                || publication_role(&mut conn, publication_id, blog.author_id)?.is_none()
+            {
+                return Err(AppError::Forbidden);
+            }
+        }
+        set_blog_publication(&mut conn, blog.id, request.publication_id).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(blog_result) => handle_app_result(blog_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/blogs/{id}/authors",
+    responses(
+        (status = 200, description = "Everyone credited on the blog, in byline order", body = [BlogAuthor]),
+        (status = 403, description = "Password-protected blog and the password is missing or wrong"),
+        (status = 404, description = "Blog not found, or not visible to the viewer")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID"),
+        ("X-Blog-Password" = Option<String>, Header, description = "Post password for password-protected blogs")
+    ),
+    tag = "co-authors"
+)]
+async fn list_blog_authors_handler(req: HttpRequest, blog_id: web::Path<Uuid>, viewer: Option<AuthUser>, pool: web::Data<DbPool>) -> impl Responder {
+    let password = blog_password(&req);
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = readable_blog(&mut conn, blog_id.into_inner(), viewer.map(|user| user.id), password.as_deref())?;
+        let mut authors = list_blog_authors(&mut conn, &[blog.id])?;
+        Ok::<_, AppError>(authors.remove(&blog.id).unwrap_or_default())
+    }).await;
+
+    match result {
+        Ok(authors_result) => handle_app_result(authors_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/blogs/{id}/authors",
+    request_body = AuthorOrderRequest,
+    responses(
+        (status = 200, description = "Byline reordered"),
+        (status = 400, description = "The list is not exactly the blog's authors"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the author or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "co-authors"
+)]
+async fn reorder_blog_authors_handler(blog_id: web::Path<Uuid>, order: web::Json<AuthorOrderRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = authored_blog(&mut conn, blog_id.into_inner(), user)?;
+        let mut current: Vec<Uuid> = list_blog_authors(&mut conn, &[blog.id])?
+            .remove(&blog.id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|author| author.user.id)
+            .collect();
+        let mut requested = order.user_ids.clone();
+        current.sort();
+        requested.sort();
+        if current != requested {
+            return Err(AppError::BadRequest("user_ids must list every author of the blog exactly once".to_string()));
+        }
+        reorder_blog_authors(&mut conn, blog.id, &order.user_ids).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(reorder_result) => handle_app_result(reorder_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/blogs/{id}/authors/{user_id}",
+    responses(
+        (status = 200, description = "Co-author removed"),
+        (status = 400, description = "The blog's owner cannot be removed"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Neither able to manage the blog nor removing yourself, or account suspended or banned"),
+        (status = 404, description = "Blog not found, or the user is not an author")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID"),
+        ("user_id" = Uuid, Path, description = "Co-author to remove")
+    ),
+    tag = "co-authors"
+)]
+async fn remove_blog_author_handler(path: web::Path<(Uuid, Uuid)>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let (blog_id, author_id) = path.into_inner();
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = match author_id == user.id {
+            true => get_blog(&mut conn, blog_id)?,
+            false => authored_blog(&mut conn, blog_id, user)?,
+        };
+        match remove_blog_author(&mut conn, &blog, author_id)? {
+            0 => Err(AppError::NotFound),
+            _ => Ok(()),
+        }
+    }).await;
+
+    match result {
+        Ok(remove_result) => handle_app_result(remove_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/blogs/{id}/invitations",
+    responses(
+        (status = 200, description = "Pending co-author invitations for the blog", body = [AuthorInvitation]),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the author or a publication editor"),
+        (status = 404, description = "Blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "co-authors"
+)]
+async fn list_blog_invitations_handler(blog_id: web::Path<Uuid>, user: AuthUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = get_blog(&mut conn, blog_id.into_inner())?;
+        if blog.author_id != user.id && !manages_publication_blog(&mut conn, &blog, user.id)? {
+            return Err(AppError::Forbidden);
+        }
+        list_blog_invitations(&mut conn, blog.id).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(invitations_result) => handle_app_result(invitations_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/blogs/{id}/invitations",
+    request_body = InvitationRequest,
+    responses(
+        (status = 200, description = "Invitation sent; the invitee is notified", body = AuthorInvitation),
+        (status = 400, description = "Already an author, or already invited"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the author or a publication editor, a block exists, or account suspended or banned"),
+        (status = 404, description = "Blog or user not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "co-authors"
+)]
+async fn invite_author_handler(blog_id: web::Path<Uuid>, request: web::Json<InvitationRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = authored_blog(&mut conn, blog_id.into_inner(), user)?;
+        let invitee = get_user(&mut conn, request.user_id)?;
+        create_author_invitation(&mut conn, blog.id, invitee.id, user.id, request.role)
+    }).await;
+
+    match result {
+        Ok(invitation_result) => handle_app_result(invitation_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/blogs/{id}/invitations/{invitation_id}",
+    responses(
+        (status = 200, description = "Invitation revoked"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the author or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Blog not found, or no pending invitation with this ID")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID"),
+        ("invitation_id" = Uuid, Path, description = "Invitation ID")
+    ),
+    tag = "co-authors"
+)]
+async fn revoke_invitation_handler(path: web::Path<(Uuid, Uuid)>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let (blog_id, invitation_id) = path.into_inner();
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = authored_blog(&mut conn, blog_id, user)?;
+        match revoke_invitation(&mut conn, blog.id, invitation_id)? {
+            0 => Err(AppError::NotFound),
+            _ => Ok(()),
+        }
+    }).await;
+
+    match result {
+        Ok(revoke_result) => handle_app_result(revoke_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/invitations",
+    responses(
+        (status = 200, description = "Co-author invitations waiting for the authenticated user", body = [AuthorInvitation]),
+        (status = 401, description = "Not authenticated")
+    ),
+    tag = "co-authors"
+)]
+async fn list_my_invitations_handler(user: AuthUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        list_pending_invitations(&mut conn, user.id)
+    }).await;
+
+    match result {
+        Ok(invitations_result) => handle_diesel_result(invitations_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+/// Answers one of the user's own invitations; anyone else's is reported as missing.
+fn answer_invitation(conn: &mut PgConnection, invitation_id: Uuid, user: ActiveUser, accept: bool) -> Result<AuthorInvitation, AppError> {
+    let invitation = get_author_invitation(conn, invitation_id)?;
+    if invitation.invitee_id != user.id {
+        return Err(AppError::NotFound);
+    }
+    respond_to_invitation(conn, &invitation, accept)
+}
+
+#[utoipa::path(
+    post,
+    path = "/invitations/{id}/accept",
+    responses(
+        (status = 200, description = "Invitation accepted; you are now credited on the blog", body = AuthorInvitation),
+        (status = 400, description = "Invitation is no longer pending"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Account suspended or banned"),
+        (status = 404, description = "Invitation not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Invitation ID")
+    ),
+    tag = "co-authors"
+)]
+async fn accept_invitation_handler(invitation_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        answer_invitation(&mut conn, invitation_id.into_inner(), user, true)
+    }).await;
+
+    match result {
+        Ok(invitation_result) => handle_app_result(invitation_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/invitations/{id}/decline",
+    responses(
+        (status = 200, description = "Invitation declined", body = AuthorInvitation),
+        (status = 400, description = "Invitation is no longer pending"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Account suspended or banned"),
+        (status = 404, description = "Invitation not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Invitation ID")
+    ),
+    tag = "co-authors"
+)]
+async fn decline_invitation_handler(invitation_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        answer_invitation(&mut conn, invitation_id.into_inner(), user, false)
+    }).await;
+
+    match result {
+        Ok(invitation_result) => handle_app_result(invitation_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/notifications",
+    responses(
+        (status = 200, description = "Page of the authenticated user's notifications, newest first", body = NotificationPage),
+        (status = 400, description = "Invalid cursor"),
+        (status = 401, description = "Not authenticated")
+    ),
+    params(NotificationQuery),
+    tag = "notifications"
+)]
+async fn list_notifications_handler(query: web::Query<NotificationQuery>, user: AuthUser, pool: web::Data<DbPool>) -> impl Responder {
+    let cursor = match query.cursor.as_deref().map(Cursor::decode) {
+        Some(None) => return AppError::BadRequest("invalid cursor".to_string()).error_response(),
+        Some(cursor) => cursor,
+        None => None,
+    };
+    let limit = page_limit(query.limit);
+
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        list_notifications(&mut conn, user.id, query.unread, cursor, limit)
+    }).await;
+
+    match result {
+        Ok(page_result) => handle_diesel_result(page_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/notifications/{id}/read",
+    responses(
+        (status = 200, description = "Notification marked read"),
+        (status = 401, description = "Not authenticated"),
+        (status = 404, description = "No unread notification with this ID")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Notification ID")
+    ),
+    tag = "notifications"
+)]
+async fn mark_notification_read_handler(notification_id: web::Path<Uuid>, user: AuthUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        match mark_notifications_read(&mut conn, user.id, Some(notification_id.into_inner()))? {
+            0 => Err(AppError::NotFound),
+            _ => Ok(()),
+        }
+    }).await;
+
+    match result {
+        Ok(read_result) => handle_app_result(read_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/notifications/read",
+    responses(
+        (status = 200, description = "All notifications marked read; returns how many changed", body = usize),
+        (status = 401, description = "Not authenticated")
+    ),
+    tag = "notifications"
+)]
+async fn mark_all_notifications_read_handler(user: AuthUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        mark_notifications_read(&mut conn, user.id, None)
+    }).await;
+
+    match result {
+        Ok(read_result) => handle_diesel_result(read_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+/// Checks where blogs are being transferred to. Admins may transfer anywhere;
+/// anyone else needs to be a member of the target publication, and can't hand
+/// blogs to someone who has blocked them.
+fn check_transfer_target(conn: &mut PgConnection, request: &TransferRequest, actor: &User) -> Result<(), AppError> {
+    if request.to_user_id.is_none() && request.to_publication_id.is_none() {
+        return Err(AppError::BadRequest("to_user_id or to_publication_id is required".to_string()));
+    }
+    let is_admin = has_role(actor, &["admin"]);
+    if let Some(to_user_id) = request.to_user_id {
+        get_user(conn, to_user_id)?;
+        if !is_admin && is_blocked(conn, to_user_id, actor.id)? {
+            return Err(AppError::Forbidden);
+        }
+    }
+    if let Some(to_publication_id) = request.to_publication_id {
+        get_publication(conn, to_publication_id)?;
+        if !is_admin && publication_role(conn, to_publication_id, actor.id)?.is_none() {
+            return Err(AppError::Forbidden);
+        }
+    }
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/blogs/{id}/transfer",
+    request_body = TransferRequest,
+    responses(
+        (status = 200, description = "Blog transferred; the previous and new owners are notified", body = Blog),
+        (status = 400, description = "No target, or the new owner is not a member of the target publication"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the author, a publication editor or an admin, not a member of the target publication, or blocked by the new owner"),
+        (status = 404, description = "Blog, user or publication not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "blogs"
+)]
+async fn transfer_blog_handler(blog_id: web::Path<Uuid>, request: web::Json<TransferRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let blog_id = blog_id.into_inner();
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let actor = get_user(&mut conn, user.id)?;
+        let blog = match authored_blog(&mut conn, blog_id, user) {
+            Err(AppError::Forbidden) if has_role(&actor, &["admin"]) => get_blog(&mut conn, blog_id)?,
+            other => other?,
+        };
+        check_transfer_target(&mut conn, &request, &actor)?;
+        let mut transferred = transfer_blogs(&mut conn, &[blog.id], request.to_user_id, request.to_publication_id, actor.id)?;
+        transferred.pop().ok_or(AppError::NotFound)
+    }).await;
+
+    match result {
+        Ok(blog_result) => handle_app_result(blog_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/{id}/transfer-blogs",
+    request_body = TransferRequest,
+    responses(
+        (status = 200, description = "All of the user's blogs transferred in one transaction", body = [Blog]),
+        (status = 400, description = "No target, or the new owner is not a member of the target publication"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Neither the user themselves nor an admin, not a member of the target publication, or blocked by the new owner"),
+        (status = 404, description = "User or publication not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "User whose blogs are transferred")
+    ),
+    tag = "blogs"
+)]
+async fn transfer_user_blogs_handler(owner_id: web::Path<Uuid>, request: web::Json<TransferRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let actor = get_user(&mut conn, user.id)?;
+        let owner = get_user(&mut conn, owner_id.into_inner())?;
+        if owner.id != actor.id && !has_role(&actor, &["admin"]) {
+            return Err(AppError::Forbidden);
+        }
+        check_transfer_target(&mut conn, &request, &actor)?;
+        let blog_ids = list_owned_blog_ids(&mut conn, owner.id)?;
+        transfer_blogs(&mut conn, &blog_ids, request.to_user_id, request.to_publication_id, actor.id)
+    }).await;
+
+    match result {
+        Ok(blogs_result) => handle_app_result(blogs_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+/// Loads a series the user may manage: its owner, or an owner or editor of its publication.
+fn managed_series(conn: &mut PgConnection, series_id: Uuid, user: ActiveUser) -> Result<Series, AppError> {
+    let series = get_series(conn, series_id)?;
+    if series.owner_id == user.id {
+        return Ok(series);
+    }
+    let publication_editor = match series.publication_id {
+        Some(publication_id) => publication_role(conn, publication_id, user.id)?.is_some_and(|role| role.manages_blogs()),
+        None => false,
+    };
+    if !publication_editor {
+        return Err(AppError::Forbidden);
+    }
+    Ok(series)
+}
+
+#[utoipa::path(
+    get,
+    path = "/series",
+    responses(
+        (status = 200, description = "Page of series, newest first", body = [Series])
+    ),
+    params(SeriesListQuery),
+    tag = "series"
+)]
+async fn list_series_handler(query: web::Query<SeriesListQuery>, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let limit = page_limit(query.limit);
+        let offset = query.offset.unwrap_or(0).max(0);
+        list_series(&mut conn, query.owner_id, query.publication_id, limit, offset)
+    }).await;
+
+    match result {
+        Ok(series_result) => handle_diesel_result(series_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/series",
+    request_body = SeriesRequest,
+    responses(
+        (status = 200, description = "Series created with the authenticated user as owner", body = Series),
+        (status = 400, description = "Empty title"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not a member of the publication, or account suspended or banned"),
+        (status = 404, description = "Publication not found")
+    ),
+    tag = "series"
+)]
+async fn create_series_handler(request: web::Json<SeriesRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    if request.title.trim().is_empty() {
+        return AppError::BadRequest("title must not be empty".to_string()).error_response();
+    }
+
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        if let Some(publication_id) = request.publication_id {
+            get_publication(&mut conn, publication_id)?;
+            if publication_role(&mut conn, publication_id, user.id)?.is_none() {
+                return Err(AppError::Forbidden);
+            }
+        }
+        create_series(&mut conn, user.id, &request).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(series_result) => handle_app_result(series_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/series/{id}",
+    responses(
+        (status = 200, description = "Series with the parts visible to the viewer, in order", body = SeriesDetail),
+        (status = 404, description = "Series not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Series ID")
+    ),
+    tag = "series"
+)]
+async fn get_series_handler(series_id: web::Path<Uuid>, viewer: Option<AuthUser>, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let series = get_series(&mut conn, series_id.into_inner())?;
+        get_series_detail(&mut conn, series, viewer.map(|user| user.id)).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(series_result) => handle_app_result(series_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/series/{id}",
+    request_body = SeriesRequest,
+    responses(
+        (status = 200, description = "Series updated; `publication_id` is ignored", body = Series),
+        (status = 400, description = "Empty title"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the owner or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Series not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Series ID")
+    ),
+    tag = "series"
+)]
+async fn update_series_handler(series_id: web::Path<Uuid>, request: web::Json<SeriesRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    if request.title.trim().is_empty() {
+        return AppError::BadRequest("title must not be empty".to_string()).error_response();
+    }
+
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let series = managed_series(&mut conn, series_id.into_inner(), user)?;
+        update_series(&mut conn, series.id, request.title.trim(), request.description.as_deref()).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(series_result) => handle_app_result(series_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/series/{id}",
+    responses(
+        (status = 200, description = "Series deleted; its blogs are kept"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the owner or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Series not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Series ID")
+    ),
+    tag = "series"
+)]
+async fn delete_series_handler(series_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let series = managed_series(&mut conn, series_id.into_inner(), user)?;
+        delete_series(&mut conn, series.id).map(|_| ()).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(delete_result) => handle_app_result(delete_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/series/{id}/order",
+    request_body = SeriesOrderRequest,
+    responses(
+        (status = 200, description = "Series reordered"),
+        (status = 400, description = "The list is not exactly the series' blogs"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the owner or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Series not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Series ID")
+    ),
+    tag = "series"
+)]
+async fn reorder_series_handler(series_id: web::Path<Uuid>, order: web::Json<SeriesOrderRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let series = managed_series(&mut conn, series_id.into_inner(), user)?;
+
+        let mut current = list_series_blog_ids(&mut conn, series.id)?;
+        let mut requested = order.blog_ids.clone();
+        current.sort();
+        requested.sort();
+        if current != requested {
+            return Err(AppError::BadRequest("blog_ids must list every blog of the series exactly once".to_string()));
+        }
+
+        reorder_series(&mut conn, series.id, &order.blog_ids).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(reorder_result) => handle_app_result(reorder_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/series/{id}/blogs/{blog_id}",
+    responses(
+        (status = 200, description = "Blog added as the last part of the series"),
+        (status = 400, description = "Blog is already in a series"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Cannot manage the series or the blog, or account suspended or banned"),
+        (status = 404, description = "Series or blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Series ID"),
+        ("blog_id" = Uuid, Path, description = "Blog to add")
+    ),
+    tag = "series"
+)]
+async fn add_series_blog_handler(path: web::Path<(Uuid, Uuid)>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let (series_id, blog_id) = path.into_inner();
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let series = managed_series(&mut conn, series_id, user)?;
+        let blog = authored_blog(&mut conn, blog_id, user)?;
+        add_blog_to_series(&mut conn, series.id, blog.id)
+    }).await;
+
+    match result {
+        Ok(add_result) => handle_app_result(add_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/series/{id}/blogs/{blog_id}",
+    responses(
+        (status = 200, description = "Blog removed from the series; the blog itself is kept"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not the owner or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Series not found, or the blog is not in it")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Series ID"),
+        ("blog_id" = Uuid, Path, description = "Blog to remove")
+    ),
+    tag = "series"
+)]
+async fn remove_series_blog_handler(path: web::Path<(Uuid, Uuid)>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let (series_id, blog_id) = path.into_inner();
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let series = managed_series(&mut conn, series_id, user)?;
+        match remove_blog_from_series(&mut conn, series.id, blog_id)? {
+            0 => Err(AppError::NotFound),
+            _ => Ok(()),
+        }
+    }).await;
+
+    match result {
+        Ok(remove_result) => handle_app_result(remove_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/blogs/search",
+    responses(
+        (status = 200, description = "Matching blogs, best match first", body = [BlogResponse]),
+        (status = 400, description = "Empty query or unsupported `lang`")
+    ),
+    params(
+        BlogSearchQuery,
+        ("Accept-Language" = Option<String>, Header, description = "Preferred locales for the results, used when `lang` is not given")
+    ),
+    tag = "blogs"
+)]
+async fn search_blogs_handler(req: HttpRequest, query: web::Query<BlogSearchQuery>, viewer: Option<AuthUser>, pool: web::Data<DbPool>) -> impl Responder {
+    if query.q.trim().is_empty() {
+        return AppError::BadRequest("q must not be empty".to_string()).error_response();
+    }
+    let preferred = match preferred_locales(&req, query.lang.as_deref()) {
+        Ok(preferred) => preferred,
+        Err(err) => return err.error_response(),
+    };
+    // Only an explicit `lang` narrows the search; Accept-Language just picks the translation shown
+    let lang = query.lang.as_ref().and(preferred.first().copied());
+
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let viewer_id = viewer.map(|user| user.id);
+        let limit = page_limit(query.limit);
+        let offset = query.offset.unwrap_or(0).max(0);
+        let blogs = search_blogs(&mut conn, query.q.trim(), lang, viewer_id, limit, offset)?;
+        let mut responses = get_blog_responses(&mut conn, blogs, viewer_id)?;
+        localize_blog_responses(&mut conn, &mut responses, &preferred)?;
+        Ok::<_, AppError>(responses)
+    }).await;
+
+    match result {
+        Ok(Ok(blogs)) => HttpResponse::Ok()
+            .insert_header(("Vary", "Accept-Language"))
+            .json(ApiResponse::success(blogs)),
+        Ok(Err(err)) => err.error_response(),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/blogs/{id}/translations",
+    responses(
+        (status = 200, description = "Every translation of the blog", body = [BlogTranslation]),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an author or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "blogs"
+)]
+async fn list_blog_translations_handler(blog_id: web::Path<Uuid>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = editable_blog(&mut conn, blog_id.into_inner(), user)?;
+        list_blog_translations(&mut conn, blog.id).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(translations_result) => handle_app_result(translations_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/blogs/{id}/translations/{locale}",
+    request_body = TranslationRequest,
+    responses(
+        (status = 200, description = "Translation created or replaced", body = BlogTranslation),
+        (status = 400, description = "Unsupported locale, the blog's own locale, invalid slug, or slug already taken"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an author or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID"),
+        ("locale" = Locale, Path, description = "Locale of the translation")
+    ),
+    tag = "blogs"
+)]
+async fn upsert_blog_translation_handler(path: web::Path<(Uuid, String)>, request: web::Json<TranslationRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let (blog_id, locale) = path.into_inner();
+    let Some(locale) = Locale::parse(&locale) else {
+        return AppError::BadRequest(format!("unsupported locale '{}'", locale)).error_response();
+    };
+    if request.title.trim().is_empty() {
+        return AppError::BadRequest("title must not be empty".to_string()).error_response();
+    }
+    if let Err(err) = validate_slug(&request.slug) {
+        return err.error_response();
+    }
+
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = editable_blog(&mut conn, blog_id, user)?;
+        if blog.locale == locale.as_str() {
+            return Err(AppError::BadRequest("the blog is already written in this locale".to_string()));
+        }
+        upsert_blog_translation(&mut conn, blog.id, locale, &request)
+    }).await;
+
+    match result {
+        Ok(translation_result) => handle_app_result(translation_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/blogs/{id}/translations/{locale}",
+    responses(
+        (status = 200, description = "Translation deleted"),
+        (status = 400, description = "Unsupported locale"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an author or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Blog or translation not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID"),
+        ("locale" = Locale, Path, description = "Locale of the translation")
+    ),
+    tag = "blogs"
+)]
+async fn delete_blog_translation_handler(path: web::Path<(Uuid, String)>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let (blog_id, locale) = path.into_inner();
+    let Some(locale) = Locale::parse(&locale) else {
+        return AppError::BadRequest(format!("unsupported locale '{}'", locale)).error_response();
+    };
+
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = editable_blog(&mut conn, blog_id, user)?;
+        match delete_blog_translation(&mut conn, blog.id, locale)? {
+            0 => Err(AppError::NotFound),
+            _ => Ok(()),
+        }
+    }).await;
+
+    match result {
+        Ok(delete_result) => handle_app_result(delete_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/blogs/{id}/excerpt",
+    request_body = ExcerptRequest,
+    responses(
+        (status = 200, description = "Excerpt set, or reset to the derived one", body = Blog),
+        (status = 400, description = "Empty excerpt, or longer than 500 characters"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an author or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "blogs"
+)]
+async fn set_blog_excerpt_handler(blog_id: web::Path<Uuid>, request: web::Json<ExcerptRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let excerpt = request.into_inner().excerpt.map(|excerpt| excerpt.trim().to_string());
+    if let Some(excerpt) = &excerpt {
+        if excerpt.is_empty() || excerpt.chars().count() > 500 {
+            return AppError::BadRequest("excerpt must be 1-500 characters".to_string()).error_response();
+        }
+    }
+
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = editable_blog(&mut conn, blog_id.into_inner(), user)?;
+        set_blog_excerpt(&mut conn, &blog, excerpt.as_deref()).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(blog_result) => handle_app_result(blog_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+/// Trims the SEO overrides, dropping empty ones, and checks their lengths and URLs.
+fn normalize_seo(request: SeoRequest) -> Result<SeoRequest, AppError> {
+    let clean = |value: Option<String>| value.map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+    let request = SeoRequest {
+        meta_title: clean(request.meta_title),
+        meta_description: clean(request.meta_description),
+        canonical_url: clean(request.canonical_url),
+        og_image_url: clean(request.og_image_url),
+        noindex: request.noindex,
+    };
+
+    if request.meta_title.as_ref().is_some_and(|title| title.chars().count() > 120) {
+        return Err(AppError::BadRequest("meta_title must be at most 120 characters".to_string()));
+    }
+    if request.meta_description.as_ref().is_some_and(|description| description.chars().count() > 300) {
+        return Err(AppError::BadRequest("meta_description must be at most 300 characters".to_string()));
+    }
+    for (field, url) in [("canonical_url", &request.canonical_url), ("og_image_url", &request.og_image_url)] {
+        let absolute = |url: &String| {
+            url.strip_prefix("https://").or_else(|| url.strip_prefix("http://"))
+                .is_some_and(|rest| !rest.is_empty() && !rest.contains(char::is_whitespace))
+        };
+        if url.as_ref().is_some_and(|url| !absolute(url)) {
+            return Err(AppError::BadRequest(format!("{} must be an absolute http(s) URL", field)));
+        }
+    }
+    Ok(request)
+}
+
+#[utoipa::path(
+    put,
+    path = "/blogs/{id}/seo",
+    request_body = SeoRequest,
+    responses(
+        (status = 200, description = "SEO overrides replaced; empty fields fall back to the defaults", body = Blog),
+        (status = 400, description = "Override too long, or URL not absolute http(s)"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an author or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "blogs"
+)]
+async fn set_blog_seo_handler(blog_id: web::Path<Uuid>, request: web::Json<SeoRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let request = match normalize_seo(request.into_inner()) {
+        Ok(request) => request,
+        Err(err) => return err.error_response(),
+    };
+
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = editable_blog(&mut conn, blog_id.into_inner(), user)?;
+        set_blog_seo(&mut conn, blog.id, &request).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(blog_result) => handle_app_result(blog_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/blogs/{id}/tags",
+    request_body = TagsRequest,
+    responses(
+        (status = 200, description = "Tags replaced; the blog's tags in alphabetical order", body = [String]),
+        (status = 400, description = "More than 10 tags, or a tag that isn't 1-50 letters, digits and dashes"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not an author or a publication editor, or account suspended or banned"),
+        (status = 404, description = "Blog not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID")
+    ),
+    tag = "blogs"
+)]
+async fn set_blog_tags_handler(blog_id: web::Path<Uuid>, request: web::Json<TagsRequest>, user: ActiveUser, pool: web::Data<DbPool>) -> impl Responder {
+    let tags = match tags::normalize_all(request.into_inner().tags) {
+        Ok(tags) => tags,
+        Err(err) => return err.error_response(),
+    };
+
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog = editable_blog(&mut conn, blog_id.into_inner(), user)?;
+        set_blog_tags(&mut conn, blog.id, &tags).map_err(AppError::from)
+    }).await;
+
+    match result {
+        Ok(tags_result) => handle_app_result(tags_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/tags/{tag}",
+    responses(
+        (status = 200, description = "Page of the blogs carrying the tag that are visible to the viewer", body = [BlogResponse]),
+        (status = 404, description = "Tag not in slug form")
+    ),
+    params(
+        ("tag" = String, Path, description = "Tag, e.g. `web-dev`"),
+        BlogListQuery
+    ),
+    tag = "blogs"
+)]
+async fn list_tag_blogs_handler(tag: web::Path<String>, query: web::Query<BlogListQuery>, viewer: Option<AuthUser>, pool: web::Data<DbPool>) -> impl Responder {
+    let tag = tag.into_inner();
+    if !tags::is_normalized(&tag) {
+        return AppError::NotFound.error_response();
+    }
+
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let limit = page_limit(query.limit);
+        let offset = query.offset.unwrap_or(0).max(0);
+        let blogs = list_blogs(&mut conn, query.sort.unwrap_or_default(), limit, offset, viewer.map(|user| user.id), None, Some(&tag))?;
+        get_blog_responses(&mut conn, blogs, viewer.map(|user| user.id))
+    }).await;
+
+    match result {
+        Ok(blogs_result) => handle_diesel_result(blogs_result),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/blogs/{id}/meta",
+    responses(
+        (status = 200, description = "SEO, OpenGraph, Twitter card and JSON-LD metadata for the blog in the negotiated locale", body = BlogMeta),
+        (status = 400, description = "Unsupported `lang`"),
+        (status = 403, description = "Password-protected blog and the password is missing or wrong"),
+        (status = 404, description = "Blog not found, or not visible to the viewer")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Blog ID"),
+        LocaleQuery,
+        ("Accept-Language" = Option<String>, Header, description = "Preferred locales, used when `lang` is not given"),
+        ("X-Blog-Password" = Option<String>, Header, description = "Post password for password-protected blogs")
+    ),
+    tag = "blogs"
+)]
+async fn get_blog_meta_handler(req: HttpRequest, blog_id: web::Path<Uuid>, query: web::Query<LocaleQuery>, viewer: Option<AuthUser>, pool: web::Data<DbPool>) -> impl Responder {
+    let preferred = match preferred_locales(&req, query.lang.as_deref()) {
+        Ok(preferred) => preferred,
+        Err(err) => return err.error_response(),
+    };
+    let password = blog_password(&req);
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let viewer_id = viewer.map(|user| user.id);
+        let blog = readable_blog(&mut conn, blog_id.into_inner(), viewer_id, password.as_deref())?;
+        let publication = blog.publication_id.map(|publication_id| get_publication(&mut conn, publication_id)).transpose()?;
+        let mut responses = get_blog_responses(&mut conn, vec![blog], viewer_id)?;
+        localize_blog_responses(&mut conn, &mut responses, &preferred)?;
+        Ok::<_, AppError>(seo::blog_meta(&responses[0], publication.as_ref()))
+    }).await;
+
+    match result {
+        Ok(Ok(meta)) => HttpResponse::Ok()
+            .insert_header(("Vary", "Accept-Language"))
+            .json(ApiResponse::success(meta)),
+        Ok(Err(err)) => err.error_response(),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+/// Renders a feed of the latest published blogs, site-wide, for one author
+/// or for one tag, answering with 304 when the reader's copy is current.
+async fn serve_feed(req: HttpRequest, pool: web::Data<DbPool>, format: FeedFormat, scope: FeedScope, content: FeedContent) -> HttpResponse {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let (title, path, blogs) = match scope {
+            FeedScope::Site => (site::name().to_string(), String::new(), list_syndicated_blogs(&mut conn, None, None, feeds::FEED_SIZE)?),
+            FeedScope::Author(author_id) => {
+                let author = get_user(&mut conn, author_id)?;
+                let blogs = list_syndicated_blogs(&mut conn, Some(author.id), None, feeds::FEED_SIZE)?;
+                (format!("{} on {}", author.username, site::name()), format!("/users/{}", author.id), blogs)
+            }
+            FeedScope::Tag(tag) => {
+                if !tags::is_normalized(&tag) {
+                    return Err(AppError::NotFound);
+                }
+                let blogs = list_syndicated_blogs(&mut conn, None, Some(&tag), feeds::FEED_SIZE)?;
+                (format!("{} on {}", tag, site::name()), format!("/tags/{}", tag), blogs)
+            }
+        };
+        let blogs = get_blog_responses(&mut conn, blogs, None)?;
+        Ok::<_, AppError>(Feed { title, path, blogs, full_content: content == FeedContent::Full })
+    }).await;
+
+    match result {
+        Ok(Ok(feed)) => {
+            let body = feed.render(format).into_bytes();
+            // No Last-Modified: a blog leaving the feed doesn't move its newest update
+            Validators::for_body(&body, None).respond(&req, format.content_type(), body)
+        }
+        Ok(Err(err)) => err.error_response(),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/feed.xml",
+    responses(
+        (status = 200, description = "RSS 2.0 feed of the latest published blogs", body = String, content_type = "application/rss+xml"),
+        (status = 304, description = "The cached copy is current"),
+    ),
+    params(
+        FeedQuery,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of the cached copy")
+    ),
+    tag = "feeds"
+)]
+async fn rss_feed_handler(req: HttpRequest, query: web::Query<FeedQuery>, pool: web::Data<DbPool>) -> impl Responder {
+    serve_feed(req, pool, FeedFormat::Rss, FeedScope::Site, query.content.unwrap_or_default()).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/atom.xml",
+    responses(
+        (status = 200, description = "Atom feed of the latest published blogs", body = String, content_type = "application/atom+xml"),
+        (status = 304, description = "The cached copy is current"),
+    ),
+    params(
+        FeedQuery,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of the cached copy")
+    ),
+    tag = "feeds"
+)]
+async fn atom_feed_handler(req: HttpRequest, query: web::Query<FeedQuery>, pool: web::Data<DbPool>) -> impl Responder {
+    serve_feed(req, pool, FeedFormat::Atom, FeedScope::Site, query.content.unwrap_or_default()).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/feed.json",
+    responses(
+        (status = 200, description = "JSON Feed 1.1 of the latest published blogs", body = String, content_type = "application/feed+json"),
+        (status = 304, description = "The cached copy is current"),
+    ),
+    params(
+        FeedQuery,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of the cached copy")
+    ),
+    tag = "feeds"
+)]
+async fn json_feed_handler(req: HttpRequest, query: web::Query<FeedQuery>, pool: web::Data<DbPool>) -> impl Responder {
+    serve_feed(req, pool, FeedFormat::Json, FeedScope::Site, query.content.unwrap_or_default()).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/{id}/feed.xml",
+    responses(
+        (status = 200, description = "RSS 2.0 feed of the latest published blogs the user is credited on", body = String, content_type = "application/rss+xml"),
+        (status = 304, description = "The cached copy is current"),
+        (status = 404, description = "User not found"),
+    ),
+    params(
+        ("id" = Uuid, Path, description = "User ID"),
+        FeedQuery,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of the cached copy")
+    ),
+    tag = "feeds"
+)]
+async fn user_rss_feed_handler(req: HttpRequest, user_id: web::Path<Uuid>, query: web::Query<FeedQuery>, pool: web::Data<DbPool>) -> impl Responder {
+    serve_feed(req, pool, FeedFormat::Rss, FeedScope::Author(user_id.into_inner()), query.content.unwrap_or_default()).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/{id}/atom.xml",
+    responses(
+        (status = 200, description = "Atom feed of the latest published blogs the user is credited on", body = String, content_type = "application/atom+xml"),
+        (status = 304, description = "The cached copy is current"),
+        (status = 404, description = "User not found"),
+    ),
+    params(
+        ("id" = Uuid, Path, description = "User ID"),
+        FeedQuery,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of the cached copy")
+    ),
+    tag = "feeds"
+)]
+async fn user_atom_feed_handler(req: HttpRequest, user_id: web::Path<Uuid>, query: web::Query<FeedQuery>, pool: web::Data<DbPool>) -> impl Responder {
+    serve_feed(req, pool, FeedFormat::Atom, FeedScope::Author(user_id.into_inner()), query.content.unwrap_or_default()).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/{id}/feed.json",
+    responses(
+        (status = 200, description = "JSON Feed 1.1 of the latest published blogs the user is credited on", body = String, content_type = "application/feed+json"),
+        (status = 304, description = "The cached copy is current"),
+        (status = 404, description = "User not found"),
+    ),
+    params(
+        ("id" = Uuid, Path, description = "User ID"),
+        FeedQuery,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of the cached copy")
+    ),
+    tag = "feeds"
+)]
+async fn user_json_feed_handler(req: HttpRequest, user_id: web::Path<Uuid>, query: web::Query<FeedQuery>, pool: web::Data<DbPool>) -> impl Responder {
+    serve_feed(req, pool, FeedFormat::Json, FeedScope::Author(user_id.into_inner()), query.content.unwrap_or_default()).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/tags/{tag}/feed.xml",
+    responses(
+        (status = 200, description = "RSS 2.0 feed of the latest published blogs carrying the tag", body = String, content_type = "application/rss+xml"),
+        (status = 304, description = "The cached copy is current"),
+        (status = 404, description = "Tag not in slug form"),
+    ),
+    params(
+        ("tag" = String, Path, description = "Tag, e.g. `web-dev`"),
+        FeedQuery,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of the cached copy")
+    ),
+    tag = "feeds"
+)]
+async fn tag_rss_feed_handler(req: HttpRequest, tag: web::Path<String>, query: web::Query<FeedQuery>, pool: web::Data<DbPool>) -> impl Responder {
+    serve_feed(req, pool, FeedFormat::Rss, FeedScope::Tag(tag.into_inner()), query.content.unwrap_or_default()).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/tags/{tag}/atom.xml",
+    responses(
+        (status = 200, description = "Atom feed of the latest published blogs carrying the tag", body = String, content_type = "application/atom+xml"),
+        (status = 304, description = "The cached copy is current"),
+        (status = 404, description = "Tag not in slug form"),
+    ),
+    params(
+        ("tag" = String, Path, description = "Tag, e.g. `web-dev`"),
+        FeedQuery,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of the cached copy")
+    ),
+    tag = "feeds"
+)]
+async fn tag_atom_feed_handler(req: HttpRequest, tag: web::Path<String>, query: web::Query<FeedQuery>, pool: web::Data<DbPool>) -> impl Responder {
+    serve_feed(req, pool, FeedFormat::Atom, FeedScope::Tag(tag.into_inner()), query.content.unwrap_or_default()).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/tags/{tag}/feed.json",
+    responses(
+        (status = 200, description = "JSON Feed 1.1 of the latest published blogs carrying the tag", body = String, content_type = "application/feed+json"),
+        (status = 304, description = "The cached copy is current"),
+        (status = 404, description = "Tag not in slug form"),
+    ),
+    params(
+        ("tag" = String, Path, description = "Tag, e.g. `web-dev`"),
+        FeedQuery,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of the cached copy")
+    ),
+    tag = "feeds"
+)]
+async fn tag_json_feed_handler(req: HttpRequest, tag: web::Path<String>, query: web::Query<FeedQuery>, pool: web::Data<DbPool>) -> impl Responder {
+    serve_feed(req, pool, FeedFormat::Json, FeedScope::Tag(tag.into_inner()), query.content.unwrap_or_default()).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/robots.txt",
+    responses(
+        (status = 200, description = "Crawler rules, configured with `ROBOTS_DISALLOW` and `ROBOTS_ALLOW_INDEXING`", body = String, content_type = "text/plain")
+    ),
+    tag = "seo"
+)]
+async fn robots_txt_handler() -> impl Responder {
+    HttpResponse::Ok()
+        .content_type("text/plain; charset=utf-8")
+        .body(sitemap::robots_txt())
+}
+
+/// Where page `page` of a sitemap starts; pages past the end are not found.
+fn sitemap_offset(page: i64) -> Result<i64, AppError> {
+    page.checked_sub(1)
+        .filter(|page| *page >= 0)
+        .and_then(|page| page.checked_mul(sitemap::MAX_URLS))
+        .ok_or(AppError::NotFound)
+}
+
+fn blog_sitemap_urls(conn: &mut PgConnection, page: i64) -> Result<Vec<SitemapUrl>, AppError> {
+    Ok(list_sitemap_blogs(conn, sitemap::MAX_URLS, sitemap_offset(page)?)?
+        .into_iter()
+        .map(|(blog_id, updated_at)| SitemapUrl { loc: site::blog_url(blog_id), lastmod: Some(updated_at) })
+        .collect())
+}
+
+fn author_sitemap_urls(conn: &mut PgConnection, page: i64) -> Result<Vec<SitemapUrl>, AppError> {
+    Ok(list_sitemap_authors(conn, sitemap::MAX_URLS, sitemap_offset(page)?)?
+        .into_iter()
+        .map(|(user_id, updated_at)| SitemapUrl { loc: site::user_url(user_id), lastmod: Some(updated_at) })
+        .collect())
+}
+
+fn tag_sitemap_urls(conn: &mut PgConnection, page: i64) -> Result<Vec<SitemapUrl>, AppError> {
+    Ok(list_sitemap_tags(conn, sitemap::MAX_URLS, sitemap_offset(page)?)?
+        .into_iter()
+        .map(|(tag, updated_at)| SitemapUrl { loc: site::tag_url(&tag), lastmod: Some(updated_at) })
+        .collect())
+}
+
+/// Sends a rendered sitemap with validators taken from its newest `lastmod`,
+/// if any.
+fn sitemap_response(req: &HttpRequest, xml: String, urls: &[SitemapUrl]) -> HttpResponse {
+    let last_modified = urls.iter().filter_map(|url| url.lastmod).max();
+    let body = xml.into_bytes();
+    Validators::for_body(&body, last_modified).respond(req, "application/xml; charset=utf-8", body)
+}
+
+#[utoipa::path(
+    get,
+    path = "/sitemap.xml",
+    responses(
+        (status = 200, description = "Sitemap of indexable blogs and their author and tag pages, or a sitemap index over `/sitemaps/*` once there are more than 50,000 URLs", body = String, content_type = "application/xml"),
+        (status = 304, description = "The cached copy is current")
+    ),
+    tag = "seo"
+)]
+async fn sitemap_handler(req: HttpRequest, pool: web::Data<DbPool>) -> impl Responder {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        let blog_count = count_sitemap_blogs(&mut conn)?;
+        let author_count = count_sitemap_authors(&mut conn)?;
+        let tag_count = count_sitemap_tags(&mut conn)?;
+
+        if blog_count + author_count + tag_count <= sitemap::MAX_URLS {
+            let mut urls = blog_sitemap_urls(&mut conn, 1)?;
+            urls.extend(author_sitemap_urls(&mut conn, 1)?);
+            urls.extend(tag_sitemap_urls(&mut conn, 1)?);
+            return Ok::<_, AppError>((sitemap::urlset(&urls), urls));
+        }
+
+        let pages = |kind: &'static str, dates: Vec<DateTime<Utc>>| {
+            dates.into_iter().zip(1..).map(move |(lastmod, page)| SitemapUrl {
+                loc: format!("{}/sitemaps/{}-{}.xml", site::base_url(), kind, page),
+                lastmod: Some(lastmod),
+            })
+        };
+        let sitemaps: Vec<SitemapUrl> = pages("blogs", list_sitemap_blog_pages(&mut conn, sitemap::MAX_URLS)?)
+            .chain(pages("authors", list_sitemap_author_pages(&mut conn, sitemap::MAX_URLS)?))
+            .chain(pages("tags", list_sitemap_tag_pages(&mut conn, sitemap::MAX_URLS)?))
+            .collect();
+        Ok((sitemap::index(&sitemaps), sitemaps))
+    }).await;
+
+    match result {
+        Ok(Ok((xml, urls))) => sitemap_response(&req, xml, &urls),
+        Ok(Err(err)) => err.error_response(),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/sitemaps/blogs-{page}.xml",
+    responses(
+        (status = 200, description = "One page of up to 50,000 indexable blogs", body = String, content_type = "application/xml"),
+        (status = 304, description = "The cached copy is current"),
+        (status = 404, description = "No such page")
+    ),
+    params(
+        ("page" = i64, Path, description = "1-based page number")
+    ),
+    tag = "seo"
+)]
+async fn blog_sitemap_handler(req: HttpRequest, page: web::Path<i64>, pool: web::Data<DbPool>) -> impl Responder {
+    let page = page.into_inner();
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        blog_sitemap_urls(&mut conn, page)
+    }).await;
+
+    match result {
+        Ok(Ok(urls)) if page == 1 || !urls.is_empty() => sitemap_response(&req, sitemap::urlset(&urls), &urls),
+        Ok(Ok(_)) => AppError::NotFound.error_response(),
+        Ok(Err(err)) => err.error_response(),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/sitemaps/authors-{page}.xml",
+    responses(
+        (status = 200, description = "One page of up to 50,000 author pages", body = String, content_type = "application/xml"),
+        (status = 304, description = "The cached copy is current"),
+        (status = 404, description = "No such page")
+    ),
+    params(
+        ("page" = i64, Path, description = "1-based page number")
+    ),
+    tag = "seo"
+)]
+async fn author_sitemap_handler(req: HttpRequest, page: web::Path<i64>, pool: web::Data<DbPool>) -> impl Responder {
+    let page = page.into_inner();
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        author_sitemap_urls(&mut conn, page)
+    }).await;
+
+    match result {
+        Ok(Ok(urls)) if page == 1 || !urls.is_empty() => sitemap_response(&req, sitemap::urlset(&urls), &urls),
+        Ok(Ok(_)) => AppError::NotFound.error_response(),
+        Ok(Err(err)) => err.error_response(),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/sitemaps/tags-{page}.xml",
+    responses(
+        (status = 200, description = "One page of up to 50,000 tag pages", body = String, content_type = "application/xml"),
+        (status = 304, description = "The cached copy is current"),
+        (status = 404, description = "No such page")
+    ),
+    params(
+        ("page" = i64, Path, description = "1-based page number")
+    ),
+    tag = "seo"
+)]
+async fn tag_sitemap_handler(req: HttpRequest, page: web::Path<i64>, pool: web::Data<DbPool>) -> impl Responder {
+    let page = page.into_inner();
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        tag_sitemap_urls(&mut conn, page)
+    }).await;
+
+    match result {
+        Ok(Ok(urls)) if page == 1 || !urls.is_empty() => sitemap_response(&req, sitemap::urlset(&urls), &urls),
+        Ok(Ok(_)) => AppError::NotFound.error_response(),
+        Ok(Err(err)) => err.error_response(),
+        Err(_) => HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string())),
+    }
+}
@@ -8,6 +8,11 @@ table! {
         email -> Varchar,
         password_hash -> Varchar,
         created_at -> Timestamptz,
+        role -> Varchar,
+        status -> Varchar,
+        status_reason -> Nullable<Text>,
+        status_expires_at -> Nullable<Timestamptz>,
+        updated_at -> Timestamptz,
     }
 }
 
@@ -19,6 +24,29 @@ table! {
         author_id -> Uuid,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        like_count -> Int8,
+        comment_count -> Int8,
+        view_count -> Int8,
+        comment_mode -> Varchar,
+        comment_auto_close_days -> Nullable<Int4>,
+        visibility -> Varchar,
+        password_hash -> Nullable<Varchar>,
+        status -> Varchar,
+        published_at -> Nullable<Timestamptz>,
+        reviewer_id -> Nullable<Uuid>,
+        publication_id -> Nullable<Uuid>,
+        locale -> Varchar,
+        word_count -> Int4,
+        reading_time_minutes -> Int4,
+        excerpt -> Text,
+        excerpt_is_manual -> Bool,
+        toc -> Jsonb,
+        meta_title -> Nullable<Varchar>,
+        meta_description -> Nullable<Text>,
+        canonical_url -> Nullable<Varchar>,
+        og_image_url -> Nullable<Varchar>,
+        noindex -> Bool,
+        changed_at -> Timestamptz,
     }
 }
 
@@ -30,14 +58,263 @@ table! {
         content -> Text,
         parent_comment_id -> Nullable<Uuid>,
         created_at -> Timestamptz,
+        upvotes -> Int8,
+        downvotes -> Int8,
+        score -> Int8,
+        updated_at -> Timestamptz,
+    }
+}
+
+table! {
+    comment_votes (comment_id, user_id) {
+        comment_id -> Uuid,
+        user_id -> Uuid,
+        value -> Int2,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
     }
 }
 
 table! {
-    likes (id) {
+    reactions (id) {
         id -> Uuid,
+        target_type -> Varchar,
+        target_id -> Uuid,
+        user_id -> Uuid,
+        kind -> Varchar,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    blog_events (id) {
+        id -> Int8,
         blog_id -> Uuid,
+        event_type -> Varchar,
+        payload -> Jsonb,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    reading_lists (id) {
+        id -> Uuid,
         user_id -> Uuid,
+        name -> Varchar,
+        description -> Nullable<Text>,
+        is_public -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+table! {
+    reading_list_items (id) {
+        id -> Uuid,
+        list_id -> Uuid,
+        blog_id -> Nullable<Uuid>,
+        blog_title -> Varchar,
+        position -> Int4,
+        added_at -> Timestamptz,
+    }
+}
+
+table! {
+    follows (follower_id, followee_id) {
+        follower_id -> Uuid,
+        followee_id -> Uuid,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    user_blocks (blocker_id, blocked_id) {
+        blocker_id -> Uuid,
+        blocked_id -> Uuid,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    user_mutes (muter_id, muted_id) {
+        muter_id -> Uuid,
+        muted_id -> Uuid,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    reports (id) {
+        id -> Uuid,
+        reporter_id -> Uuid,
+        target_type -> Varchar,
+        target_id -> Uuid,
+        reason -> Varchar,
+        details -> Nullable<Text>,
+        status -> Varchar,
+        assignee_id -> Nullable<Uuid>,
+        resolution_notes -> Nullable<Text>,
+        resolved_by -> Nullable<Uuid>,
+        resolved_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+table! {
+    audit_log (id) {
+        id -> Uuid,
+        actor_id -> Nullable<Uuid>,
+        action -> Varchar,
+        target_type -> Varchar,
+        target_id -> Uuid,
+        report_id -> Nullable<Uuid>,
+        notes -> Nullable<Text>,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    blog_shares (blog_id, user_id) {
+        blog_id -> Uuid,
+        user_id -> Uuid,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    blog_revisions (id) {
+        id -> Uuid,
+        blog_id -> Uuid,
+        revision_number -> Int4,
+        title -> Varchar,
+        content -> Text,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    blog_preview_tokens (id) {
+        id -> Uuid,
+        blog_id -> Uuid,
+        revision_id -> Nullable<Uuid>,
+        created_by -> Nullable<Uuid>,
+        expires_at -> Timestamptz,
+        revoked_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    blog_transitions (id) {
+        id -> Uuid,
+        blog_id -> Uuid,
+        from_status -> Varchar,
+        to_status -> Varchar,
+        action -> Varchar,
+        actor_id -> Nullable<Uuid>,
+        comment -> Nullable<Text>,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    publications (id) {
+        id -> Uuid,
+        name -> Varchar,
+        slug -> Varchar,
+        description -> Nullable<Text>,
+        logo_url -> Nullable<Varchar>,
+        accent_color -> Nullable<Varchar>,
+        require_review -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+table! {
+    publication_members (publication_id, user_id) {
+        publication_id -> Uuid,
+        user_id -> Uuid,
+        role -> Varchar,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    blog_authors (blog_id, user_id) {
+        blog_id -> Uuid,
+        user_id -> Uuid,
+        position -> Int4,
+        role -> Varchar,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    blog_author_invitations (id) {
+        id -> Uuid,
+        blog_id -> Uuid,
+        invitee_id -> Uuid,
+        inviter_id -> Nullable<Uuid>,
+        role -> Varchar,
+        status -> Varchar,
+        created_at -> Timestamptz,
+        responded_at -> Nullable<Timestamptz>,
+    }
+}
+
+table! {
+    notifications (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        actor_id -> Nullable<Uuid>,
+        kind -> Varchar,
+        blog_id -> Nullable<Uuid>,
+        data -> Jsonb,
+        read_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    series (id) {
+        id -> Uuid,
+        owner_id -> Uuid,
+        publication_id -> Nullable<Uuid>,
+        title -> Varchar,
+        description -> Nullable<Text>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+table! {
+    series_blogs (series_id, blog_id) {
+        series_id -> Uuid,
+        blog_id -> Uuid,
+        position -> Int4,
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    blog_translations (id) {
+        id -> Uuid,
+        blog_id -> Uuid,
+        locale -> Varchar,
+        title -> Varchar,
+        content -> Text,
+        slug -> Varchar,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+table! {
+    blog_tags (blog_id, tag) {
+        blog_id -> Uuid,
+        tag -> Varchar,
         created_at -> Timestamptz,
     }
 }
@@ -46,5 +323,27 @@ allow_tables_to_appear_in_same_query!(
     users,
     blogs,
     comments,
-    likes,
+    comment_votes,
+    reactions,
+    blog_events,
+    reading_lists,
+    reading_list_items,
+    follows,
+    user_blocks,
+    user_mutes,
+    reports,
+    audit_log,
+    blog_shares,
+    blog_revisions,
+    blog_preview_tokens,
+    blog_transitions,
+    publications,
+    publication_members,
+    blog_authors,
+    blog_author_invitations,
+    notifications,
+    series,
+    series_blogs,
+    blog_translations,
+    blog_tags,
 );
\ No newline at end of file
@@ -0,0 +1,86 @@
+use serde_json::json;
+
+use crate::content;
+use crate::models::{BlogMeta, BlogResponse, BlogStatus, BlogVisibility, Locale, OpenGraph, Publication, TwitterCard};
+use crate::site;
+
+/// Search engines cut descriptions at about this length.
+const DESCRIPTION_CHARS: usize = 160;
+
+/// Metadata for the blog as served to the reader. The overrides are written
+/// for the original, so a served translation uses its own title and excerpt
+/// and is canonical at its `?lang=` address.
+pub fn blog_meta(response: &BlogResponse, publication: Option<&Publication>) -> BlogMeta {
+    let blog = &response.blog;
+    let translated = response.slug.is_some();
+
+    let (title, description, url) = if translated {
+        (
+            blog.title.clone(),
+            content::truncate(&blog.excerpt, DESCRIPTION_CHARS),
+            format!("{}?lang={}", site::blog_url(blog.id), blog.locale),
+        )
+    } else {
+        (
+            blog.meta_title.clone().unwrap_or_else(|| blog.title.clone()),
+            blog.meta_description.clone().unwrap_or_else(|| content::truncate(&blog.excerpt, DESCRIPTION_CHARS)),
+            blog.canonical_url.clone().unwrap_or_else(|| site::blog_url(blog.id)),
+        )
+    };
+    let image = blog.og_image_url.clone().or_else(|| publication.and_then(|publication| publication.logo_url.clone()));
+    let authors: Vec<String> = response.authors.iter().map(|author| author.user.username.clone()).collect();
+
+    let indexable = !blog.noindex
+        && blog.status == BlogStatus::Published.as_str()
+        && blog.visibility == BlogVisibility::Public.as_str();
+
+    let mut json_ld = json!({
+        "@context": "https://schema.org",
+        "@type": "BlogPosting",
+        "headline": title,
+        "description": description,
+        "url": url,
+        "mainEntityOfPage": { "@type": "WebPage", "@id": url },
+        "inLanguage": blog.locale,
+        "wordCount": blog.word_count,
+        "datePublished": blog.published_at,
+        "dateModified": blog.updated_at,
+        "author": authors.iter().map(|name| json!({ "@type": "Person", "name": name })).collect::<Vec<_>>(),
+    });
+    if let Some(image) = &image {
+        json_ld["image"] = json!(image);
+    }
+    if let Some(publication) = publication {
+        let mut publisher = json!({ "@type": "Organization", "name": publication.name });
+        if let Some(logo_url) = &publication.logo_url {
+            publisher["logo"] = json!({ "@type": "ImageObject", "url": logo_url });
+        }
+        json_ld["publisher"] = publisher;
+    }
+
+    BlogMeta {
+        open_graph: OpenGraph {
+            kind: "article".to_string(),
+            title: title.clone(),
+            description: description.clone(),
+            url: url.clone(),
+            image: image.clone(),
+            site_name: publication.map_or_else(|| site::name().to_string(), |publication| publication.name.clone()),
+            locale: Locale::parse(&blog.locale).unwrap_or_default().open_graph_tag().to_string(),
+            published_time: blog.published_at,
+            modified_time: blog.updated_at,
+            authors,
+        },
+        twitter: TwitterCard {
+            card: if image.is_some() { "summary_large_image" } else { "summary" }.to_string(),
+            title: title.clone(),
+            description: description.clone(),
+            image,
+        },
+        robots: if indexable { "index, follow" } else { "noindex, nofollow" }.to_string(),
+        title,
+        description,
+        canonical_url: url,
+        json_ld,
+    }
+}
@@ -0,0 +1,37 @@
+use std::env;
+use std::sync::OnceLock;
+
+use uuid::Uuid;
+
+const DEFAULT_URL: &str = "http://localhost:8080";
+const DEFAULT_NAME: &str = "Blog";
+
+/// Public address of the site without a trailing slash, read once from
+/// `SITE_URL`. Absolute links in metadata are built on it.
+pub fn base_url() -> &'static str {
+    static URL: OnceLock<String> = OnceLock::new();
+
+    URL.get_or_init(|| {
+        let url = env::var("SITE_URL").unwrap_or_else(|_| DEFAULT_URL.to_string());
+        url.trim().trim_end_matches('/').to_string()
+    })
+}
+
+/// Name of the site, read once from `SITE_NAME`.
+pub fn name() -> &'static str {
+    static NAME: OnceLock<String> = OnceLock::new();
+
+    NAME.get_or_init(|| env::var("SITE_NAME").unwrap_or_else(|_| DEFAULT_NAME.to_string()))
+}
+
+pub fn blog_url(blog_id: Uuid) -> String {
+    format!("{}/blogs/{}", base_url(), blog_id)
+}
+
+pub fn user_url(user_id: Uuid) -> String {
+    format!("{}/users/{}", base_url(), user_id)
+}
+
+pub fn tag_url(tag: &str) -> String {
+    format!("{}/tags/{}", base_url(), tag)
+}
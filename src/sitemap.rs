@@ -0,0 +1,73 @@
+use std::env;
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Utc};
+
+use crate::site;
+
+/// Most URLs a single sitemap may list.
+pub const MAX_URLS: i64 = 50_000;
+
+const DEFAULT_DISALLOW: &str = "/preview/,/admin/,/moderation/";
+
+pub struct SitemapUrl {
+    pub loc: String,
+    pub lastmod: Option<DateTime<Utc>>,
+}
+
+/// A `<urlset>` sitemap of pages.
+pub fn urlset(urls: &[SitemapUrl]) -> String {
+    render("urlset", "url", urls)
+}
+
+/// A `<sitemapindex>` pointing at further sitemaps.
+pub fn index(sitemaps: &[SitemapUrl]) -> String {
+    render("sitemapindex", "sitemap", sitemaps)
+}
+
+fn render(root: &str, element: &str, urls: &[SitemapUrl]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!("<{} xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n", root));
+    for url in urls {
+        xml.push_str(&format!("<{}><loc>{}</loc>", element, escape(&url.loc)));
+        if let Some(lastmod) = url.lastmod {
+            xml.push_str(&format!("<lastmod>{}</lastmod>", lastmod.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)));
+        }
+        xml.push_str(&format!("</{}>\n", element));
+    }
+    xml.push_str(&format!("</{}>\n", root));
+    xml
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// The site's robots.txt, built once. `ROBOTS_DISALLOW` lists the paths
+/// crawlers are kept out of, comma-separated; `ROBOTS_ALLOW_INDEXING=false`
+/// shuts them out entirely, e.g. on staging.
+pub fn robots_txt() -> &'static str {
+    static ROBOTS: OnceLock<String> = OnceLock::new();
+
+    ROBOTS.get_or_init(|| {
+        let allow_indexing = env::var("ROBOTS_ALLOW_INDEXING")
+            .map(|value| value.trim() != "false")
+            .unwrap_or(true);
+        let disallow = env::var("ROBOTS_DISALLOW").unwrap_or_else(|_| DEFAULT_DISALLOW.to_string());
+
+        let mut robots = String::from("User-agent: *\n");
+        if allow_indexing {
+            for path in disallow.split(',').map(str::trim).filter(|path| !path.is_empty()) {
+                robots.push_str(&format!("Disallow: {}\n", path));
+            }
+            robots.push_str(&format!("\nSitemap: {}/sitemap.xml\n", site::base_url()));
+        } else {
+            robots.push_str("Disallow: /\n");
+        }
+        robots
+    })
+}
@@ -0,0 +1,92 @@
+use crate::error_handler::AppError;
+use crate::models::MAX_TAGS;
+
+/// Longest tag, in characters.
+pub const MAX_LENGTH: usize = 50;
+
+/// The slug form of `tag`: lowercased, with runs of spaces, dashes and
+/// underscores turned into single dashes. `None` if nothing is left or it
+/// has characters other than ASCII letters and digits.
+pub fn normalize(tag: &str) -> Option<String> {
+    let lowered = tag.trim().to_lowercase();
+    let words: Vec<&str> = lowered
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    let tag = words.join("-");
+    let valid = !tag.is_empty()
+        && tag.len() <= MAX_LENGTH
+        && words.iter().all(|word| word.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()));
+    valid.then_some(tag)
+}
+
+/// Normalizes the tags of a `TagsRequest`, dropping duplicates and keeping
+/// the first-seen order.
+pub fn normalize_all(tags: Vec<String>) -> Result<Vec<String>, AppError> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let Some(tag) = normalize(&tag) else {
+            return Err(AppError::BadRequest(format!(
+                "tag {:?} must be 1-{} letters, digits and dashes",
+                tag, MAX_LENGTH
+            )));
+        };
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+
+    if normalized.len() > MAX_TAGS {
+        return Err(AppError::BadRequest(format!("a blog can have at most {} tags", MAX_TAGS)));
+    }
+    Ok(normalized)
+}
+
+/// Whether `tag` is already in slug form, as tags in URLs must be.
+pub fn is_normalized(tag: &str) -> bool {
+    normalize(tag).as_deref() == Some(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_lowercases_and_dashes_separators() {
+        assert_eq!(normalize("Rust").as_deref(), Some("rust"));
+        assert_eq!(normalize("  Web   Dev ").as_deref(), Some("web-dev"));
+        assert_eq!(normalize("web_dev--tips").as_deref(), Some("web-dev-tips"));
+        assert_eq!(normalize("-edge-").as_deref(), Some("edge"));
+    }
+
+    #[test]
+    fn normalize_rejects_empty_long_and_non_ascii_tags() {
+        assert_eq!(normalize(""), None);
+        assert_eq!(normalize(" - "), None);
+        assert_eq!(normalize("c++"), None);
+        assert_eq!(normalize("café"), None);
+        assert_eq!(normalize(&"a".repeat(MAX_LENGTH)).map(|tag| tag.len()), Some(MAX_LENGTH));
+        assert_eq!(normalize(&"a".repeat(MAX_LENGTH + 1)), None);
+    }
+
+    #[test]
+    fn normalize_all_drops_duplicates_in_order() {
+        let tags = normalize_all(vec!["Rust".to_string(), "web dev".to_string(), "rust".to_string()]).unwrap();
+        assert_eq!(tags, ["rust", "web-dev"]);
+    }
+
+    #[test]
+    fn normalize_all_limits_the_count() {
+        let tags = (0..=MAX_TAGS).map(|i| format!("tag{}", i)).collect();
+        assert!(matches!(normalize_all(tags), Err(AppError::BadRequest(_))));
+        assert!(matches!(normalize_all(vec!["ok".to_string(), "not ok!".to_string()]), Err(AppError::BadRequest(_))));
+    }
+
+    #[test]
+    fn only_slugs_are_normalized() {
+        assert!(is_normalized("web-dev"));
+        assert!(!is_normalized("Web-Dev"));
+        assert!(!is_normalized("web--dev"));
+    }
+}
@@ -0,0 +1,194 @@
+use uuid::Uuid;
+
+use crate::error_handler::AppError;
+use crate::models::{Blog, BlogStatus};
+
+/// Something a participant asks the editorial workflow to do with a blog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Submit,
+    Withdraw,
+    RequestChanges,
+    Approve,
+    Schedule,
+    Unschedule,
+    Publish,
+}
+
+impl Action {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Action::Submit => "submit",
+            Action::Withdraw => "withdraw",
+            Action::RequestChanges => "request_changes",
+            Action::Approve => "approve",
+            Action::Schedule => "schedule",
+            Action::Unschedule => "unschedule",
+            Action::Publish => "publish",
+        }
+    }
+}
+
+/// The part someone plays for a particular blog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Actor {
+    Author,
+    /// The editor assigned in `blogs.reviewer_id`
+    Reviewer,
+    /// The background job that publishes scheduled blogs
+    Scheduler,
+}
+
+impl Actor {
+    /// The part `user_id` plays for `blog`, if any. `is_author` says whether
+    /// they are credited in `blog_authors`, which always includes the owner.
+    pub fn of(blog: &Blog, user_id: Uuid, is_author: bool) -> Option<Self> {
+        if is_author || blog.author_id == user_id {
+            Some(Actor::Author)
+        } else if blog.reviewer_id == Some(user_id) {
+            Some(Actor::Reviewer)
+        } else {
+            None
+        }
+    }
+}
+
+use Action::*;
+use BlogStatus::*;
+
+/// Every allowed move: from status, action, who may take it, resulting status.
+/// Authors may publish their own drafts directly unless review is required.
+const TRANSITIONS: &[(BlogStatus, Action, Actor, BlogStatus)] = &[
+    (Draft, Submit, Actor::Author, InReview),
+    (ChangesRequested, Submit, Actor::Author, InReview),
+    (InReview, Withdraw, Actor::Author, Draft),
+    (InReview, RequestChanges, Actor::Reviewer, ChangesRequested),
+    (InReview, Approve, Actor::Reviewer, Published),
+    (InReview, Schedule, Actor::Reviewer, Scheduled),
+    (Scheduled, Unschedule, Actor::Reviewer, InReview),
+    (Scheduled, Publish, Actor::Scheduler, Published),
+    (Draft, Publish, Actor::Author, Published),
+    (ChangesRequested, Publish, Actor::Author, Published),
+];
+
+/// The status a blog in `from` moves to when `actor` takes `action`.
+/// Moves that don't exist from `from` are a bad request; moves that exist but
+/// belong to someone else are forbidden. `review_required` takes away the
+/// author's direct publish.
+pub fn transition(from: BlogStatus, action: Action, actor: Option<Actor>, review_required: bool) -> Result<BlogStatus, AppError> {
+    if review_required && action == Publish && actor == Some(Actor::Author) {
+        return Err(AppError::BadRequest("this blog must be approved by an editor before it is published".to_string()));
+    }
+
+    let mut allowed = TRANSITIONS
+        .iter()
+        .filter(|(state, candidate, _, _)| *state == from && *candidate == action)
+        .peekable();
+
+    if allowed.peek().is_none() {
+        return Err(AppError::BadRequest(format!(
+            "cannot {} a blog that is {}",
+            action.as_str().replace('_', " "),
+            from.as_str().replace('_', " "),
+        )));
+    }
+
+    allowed
+        .find(|(_, _, who, _)| Some(*who) == actor)
+        .map(|(_, _, _, to)| *to)
+        .ok_or(AppError::Forbidden)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STATUSES: [BlogStatus; 5] = [Draft, InReview, ChangesRequested, Scheduled, Published];
+    const ACTIONS: [Action; 7] = [Submit, Withdraw, RequestChanges, Approve, Schedule, Unschedule, Publish];
+
+    #[test]
+    fn every_listed_move_is_allowed_for_its_actor() {
+        for &(from, action, actor, to) in TRANSITIONS {
+            assert_eq!(transition(from, action, Some(actor), false).ok(), Some(to), "{:?} {:?} by {:?}", from, action, actor);
+        }
+    }
+
+    #[test]
+    fn review_flow() {
+        assert_eq!(transition(Draft, Submit, Some(Actor::Author), true).ok(), Some(InReview));
+        assert_eq!(transition(InReview, RequestChanges, Some(Actor::Reviewer), true).ok(), Some(ChangesRequested));
+        assert_eq!(transition(ChangesRequested, Submit, Some(Actor::Author), true).ok(), Some(InReview));
+        assert_eq!(transition(InReview, Schedule, Some(Actor::Reviewer), true).ok(), Some(Scheduled));
+        assert_eq!(transition(Scheduled, Publish, Some(Actor::Scheduler), true).ok(), Some(Published));
+    }
+
+    #[test]
+    fn moves_missing_from_the_status_are_bad_requests() {
+        for from in STATUSES {
+            for action in ACTIONS {
+                if TRANSITIONS.iter().any(|&(state, candidate, _, _)| state == from && candidate == action) {
+                    continue;
+                }
+                for actor in [Some(Actor::Author), Some(Actor::Reviewer), Some(Actor::Scheduler), None] {
+                    assert!(
+                        matches!(transition(from, action, actor, false), Err(AppError::BadRequest(_))),
+                        "{:?} {:?} by {:?}", from, action, actor,
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bad_request_names_the_move() {
+        match transition(Published, Withdraw, Some(Actor::Author), false) {
+            Err(AppError::BadRequest(message)) => assert_eq!(message, "cannot withdraw a blog that is published"),
+            other => panic!("unexpected {:?}", other.map(|status| status.as_str())),
+        }
+    }
+
+    #[test]
+    fn moves_belonging_to_someone_else_are_forbidden() {
+        let cases = [
+            (Draft, Submit, Some(Actor::Reviewer)),
+            (InReview, Withdraw, Some(Actor::Reviewer)),
+            (InReview, Approve, Some(Actor::Author)),
+            (InReview, RequestChanges, Some(Actor::Author)),
+            (InReview, Schedule, Some(Actor::Author)),
+            (Scheduled, Unschedule, Some(Actor::Author)),
+            (Scheduled, Publish, Some(Actor::Author)),
+            (Scheduled, Publish, Some(Actor::Reviewer)),
+            (Draft, Publish, Some(Actor::Reviewer)),
+            (Draft, Publish, Some(Actor::Scheduler)),
+        ];
+        for (from, action, actor) in cases {
+            assert!(matches!(transition(from, action, actor, false), Err(AppError::Forbidden)), "{:?} {:?} by {:?}", from, action, actor);
+        }
+    }
+
+    #[test]
+    fn strangers_are_forbidden_from_every_existing_move() {
+        for &(from, action, _, _) in TRANSITIONS {
+            assert!(matches!(transition(from, action, None, false), Err(AppError::Forbidden)), "{:?} {:?}", from, action);
+        }
+    }
+
+    #[test]
+    fn review_required_blocks_the_authors_direct_publish() {
+        for from in [Draft, ChangesRequested] {
+            assert!(matches!(transition(from, Publish, Some(Actor::Author), true), Err(AppError::BadRequest(_))));
+            assert_eq!(transition(from, Publish, Some(Actor::Author), false).ok(), Some(Published));
+        }
+        // Even from a status with no direct publish, the review requirement is what's reported.
+        match transition(InReview, Publish, Some(Actor::Author), true) {
+            Err(AppError::BadRequest(message)) => assert!(message.contains("approved by an editor")),
+            other => panic!("unexpected {:?}", other.map(|status| status.as_str())),
+        }
+    }
+
+    #[test]
+    fn review_required_leaves_the_scheduler_alone() {
+        assert_eq!(transition(Scheduled, Publish, Some(Actor::Scheduler), true).ok(), Some(Published));
+        assert_eq!(transition(InReview, Approve, Some(Actor::Reviewer), true).ok(), Some(Published));
+    }
+}
@@ -0,0 +1,405 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use actix_ws::{CloseCode, Closed, Message, MessageStream, Session};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::api_response::ApiResponse;
+use crate::auth::AuthUser;
+use crate::db::DbPool;
+use crate::events::{self, Audience, EventBroker, LiveEvent};
+use crate::models::BlogEvent;
+use crate::orm::{can_read_blog, get_blog, get_user};
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(10);
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(30);
+const TYPING_TIMEOUT: Duration = Duration::from_secs(6);
+const PRESENCE_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TopicKind {
+    Blog,
+    User,
+}
+
+/// A blog or user whose events a socket is subscribed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub struct Topic {
+    pub kind: TopicKind,
+    pub id: Uuid,
+}
+
+impl Topic {
+    /// Blog topics receive every event of the blog; user topics receive
+    /// events for comments and blogs written by that user. Whether the
+    /// subscriber may see the event is checked separately.
+    fn matches(&self, event: &BlogEvent) -> bool {
+        match self.kind {
+            TopicKind::Blog => event.blog_id == self.id,
+            TopicKind::User => ["user_id", "author_id"].iter().any(|key| {
+                event.payload.get(key)
+                    .and_then(|value| value.as_str())
+                    .and_then(|value| Uuid::parse_str(value).ok())
+                    == Some(self.id)
+            }),
+        }
+    }
+}
+
+/// Messages sent by the client as JSON text frames.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Start receiving events for a topic. Subscribing to a blog also counts the user as reading it.
+    Subscribe { topic: Topic },
+    /// Stop receiving events for a topic.
+    Unsubscribe { topic: Topic },
+    /// Show the user as typing on a subscribed blog, optionally replying to a comment.
+    Typing { blog_id: Uuid, parent_comment_id: Option<Uuid> },
+    /// Clear the typing indicator before it expires on its own.
+    StopTyping { blog_id: Uuid },
+}
+
+/// Messages pushed by the server as JSON text frames.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    Subscribed { topic: Topic },
+    Unsubscribed { topic: Topic },
+    Event { topic: Topic, event: BlogEvent },
+    /// Who is currently on a blog; sent to its subscribers whenever it changes.
+    Presence { blog_id: Uuid, readers: usize, typing: Vec<TypingUser> },
+    Error { message: String },
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TypingUser {
+    pub user_id: Uuid,
+    pub username: String,
+    pub parent_comment_id: Option<Uuid>,
+}
+
+struct Reader {
+    user_id: Uuid,
+    username: String,
+    typing: Option<(Option<Uuid>, Instant)>,
+}
+
+/// Tracks who is reading and typing on each blog. Presence lives in memory,
+/// so it only covers sockets connected to this server process.
+pub struct Presence {
+    blogs: Mutex<HashMap<Uuid, HashMap<Uuid, Reader>>>,
+    updates: broadcast::Sender<ServerMessage>,
+}
+
+impl Presence {
+    pub fn new() -> Self {
+        let (updates, _) = broadcast::channel(PRESENCE_CAPACITY);
+        Self {
+            blogs: Mutex::new(HashMap::new()),
+            updates,
+        }
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<ServerMessage> {
+        self.updates.subscribe()
+    }
+
+    fn join(&self, blog_id: Uuid, session_id: Uuid, user_id: Uuid, username: &str) {
+        let mut blogs = self.blogs.lock().expect("presence lock poisoned");
+        let readers = blogs.entry(blog_id).or_default();
+        readers.insert(session_id, Reader {
+            user_id,
+            username: username.to_string(),
+            typing: None,
+        });
+        self.announce(blog_id, readers);
+    }
+
+    fn leave(&self, blog_id: Uuid, session_id: Uuid) {
+        let mut blogs = self.blogs.lock().expect("presence lock poisoned");
+        let Some(readers) = blogs.get_mut(&blog_id) else {
+            return;
+        };
+        if readers.remove(&session_id).is_some() {
+            self.announce(blog_id, readers);
+        }
+        if readers.is_empty() {
+            blogs.remove(&blog_id);
+        }
+    }
+
+    fn set_typing(&self, blog_id: Uuid, session_id: Uuid, typing: Option<Option<Uuid>>) {
+        let mut blogs = self.blogs.lock().expect("presence lock poisoned");
+        let Some(readers) = blogs.get_mut(&blog_id) else {
+            return;
+        };
+        let Some(reader) = readers.get_mut(&session_id) else {
+            return;
+        };
+        let was_typing = reader.typing.map(|(parent, _)| parent);
+        reader.typing = typing.map(|parent| (parent, Instant::now()));
+        if was_typing != typing {
+            self.announce(blog_id, readers);
+        }
+    }
+
+    fn expire_typing(&self) {
+        let mut blogs = self.blogs.lock().expect("presence lock poisoned");
+        for (blog_id, readers) in blogs.iter_mut() {
+            let mut changed = false;
+            for reader in readers.values_mut() {
+                if reader.typing.is_some_and(|(_, since)| since.elapsed() >= TYPING_TIMEOUT) {
+                    reader.typing = None;
+                    changed = true;
+                }
+            }
+            if changed {
+                self.announce(*blog_id, readers);
+            }
+        }
+    }
+
+    fn announce(&self, blog_id: Uuid, readers: &HashMap<Uuid, Reader>) {
+        let distinct_readers: HashSet<Uuid> = readers.values().map(|reader| reader.user_id).collect();
+        let typing = readers.values()
+            .filter_map(|reader| reader.typing.map(|(parent_comment_id, _)| TypingUser {
+                user_id: reader.user_id,
+                username: reader.username.clone(),
+                parent_comment_id,
+            }))
+            .collect();
+
+        // Sending only fails when no socket is connected.
+        let _ = self.updates.send(ServerMessage::Presence {
+            blog_id,
+            readers: distinct_readers.len(),
+            typing,
+        });
+    }
+}
+
+/// Opens the real-time WebSocket gateway.
+///
+/// Authenticate with `Authorization: Bearer <token>`, or with `?access_token=<token>` from
+/// browsers. Every frame is a JSON text message: the client sends `ClientMessage` and the
+/// server pushes `ServerMessage`, both tagged by their `type` field.
+///
+/// Subscribing to a `blog` topic delivers that blog's events (`comment_created`,
+/// `comment_updated`, `comment_deleted`, `like_count`, `blog_updated`, ...) and its presence,
+/// and counts the user as reading it. A `user` topic delivers events for comments and blogs
+/// written by that user. Either way, events are only delivered while the subscriber can read
+/// the blog they belong to. New blocks, mutes, shares and co-authors take up to a minute to
+/// apply to a connected socket. Typing indicators are only accepted for subscribed blogs and expire
+/// after 6 seconds unless repeated.
+///
+/// The server pings every 10 seconds and closes sockets that have been silent for 30 seconds.
+#[utoipa::path(
+    get,
+    path = "/ws",
+    responses(
+        (status = 101, description = "Switching to the WebSocket protocol"),
+        (status = 401, description = "Missing or invalid access token")
+    ),
+    params(
+        ("access_token" = Option<String>, Query, description = "Access token, for clients that cannot set headers")
+    ),
+    tag = "realtime"
+)]
+pub async fn websocket_handler(req: HttpRequest, body: web::Payload, user: AuthUser, pool: web::Data<DbPool>, broker: web::Data<EventBroker>, presence: web::Data<Presence>) -> actix_web::Result<HttpResponse> {
+    let user_pool = pool.clone();
+    let account = web::block(move || {
+        let mut conn = user_pool.get().expect("couldn't get db connection from pool");
+        let account = get_user(&mut conn, user.id)?;
+        Audience::load(&mut conn, Some(user.id), None).map(|audience| (account, audience))
+    }).await;
+
+    let (username, audience) = match account {
+        Ok(Ok((account, audience))) => (account.username, audience),
+        Ok(Err(diesel::result::Error::NotFound)) => return Ok(HttpResponse::Unauthorized().json(ApiResponse::<()>::error("Unknown user".to_string()))),
+        Ok(Err(_)) => return Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Database error".to_string()))),
+        Err(_) => return Ok(HttpResponse::InternalServerError().json(ApiResponse::<()>::error("Server error".to_string()))),
+    };
+
+    let (response, session, stream) = actix_ws::handle(&req, body)?;
+    let connection = Connection {
+        session,
+        session_id: Uuid::new_v4(),
+        user_id: user.id,
+        username,
+        topics: HashSet::new(),
+        audience: Arc::new(Mutex::new(audience)),
+    };
+    actix_web::rt::spawn(run(connection, stream, pool, broker.subscribe(), presence));
+
+    Ok(response)
+}
+
+struct Connection {
+    session: Session,
+    session_id: Uuid,
+    user_id: Uuid,
+    username: String,
+    topics: HashSet<Topic>,
+    audience: Arc<Mutex<Audience>>,
+}
+
+async fn run(mut connection: Connection, mut stream: MessageStream, pool: web::Data<DbPool>, mut events: broadcast::Receiver<LiveEvent>, presence: web::Data<Presence>) {
+    let mut presence_updates = presence.subscribe();
+    let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+    let mut last_seen = Instant::now();
+
+    let close_reason = loop {
+        let sent = tokio::select! {
+            message = stream.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    last_seen = Instant::now();
+                    connection.handle_text(&text, &pool, &presence).await
+                }
+                Some(Ok(Message::Ping(bytes))) => {
+                    last_seen = Instant::now();
+                    connection.session.pong(&bytes).await
+                }
+                Some(Ok(Message::Pong(_))) => {
+                    last_seen = Instant::now();
+                    Ok(())
+                }
+                Some(Ok(Message::Close(reason))) => break reason,
+                Some(Ok(_)) => Ok(()),
+                Some(Err(_)) | None => break None,
+            },
+            event = events.recv() => match event {
+                Ok(event) => connection.forward_event(event, &pool).await,
+                Err(RecvError::Lagged(skipped)) => connection.send(&ServerMessage::Error {
+                    message: format!("{} events were dropped because the connection fell behind", skipped),
+                }).await,
+                Err(RecvError::Closed) => break None,
+            },
+            update = presence_updates.recv() => match update {
+                Ok(update) => connection.forward_presence(update).await,
+                Err(RecvError::Lagged(_)) => Ok(()),
+                Err(RecvError::Closed) => break None,
+            },
+            _ = heartbeat.tick() => {
+                if last_seen.elapsed() > CLIENT_TIMEOUT {
+                    break Some((CloseCode::Away, "heartbeat timeout").into());
+                }
+                presence.expire_typing();
+                connection.session.ping(b"").await
+            }
+        };
+
+        if sent.is_err() {
+            break None;
+        }
+    };
+
+    for topic in &connection.topics {
+        if topic.kind == TopicKind::Blog {
+            presence.leave(topic.id, connection.session_id);
+        }
+    }
+    let _ = connection.session.close(close_reason).await;
+}
+
+impl Connection {
+    async fn send(&mut self, message: &ServerMessage) -> Result<(), Closed> {
+        let text = serde_json::to_string(message).expect("server messages always serialize");
+        self.session.text(text).await
+    }
+
+    async fn handle_text(&mut self, text: &str, pool: &web::Data<DbPool>, presence: &Presence) -> Result<(), Closed> {
+        let message = match serde_json::from_str::<ClientMessage>(text) {
+            Ok(message) => message,
+            Err(err) => return self.send(&ServerMessage::Error { message: format!("Invalid message: {}", err) }).await,
+        };
+
+        match message {
+            ClientMessage::Subscribe { topic } => {
+                if !self.topics.contains(&topic) {
+                    match topic_exists(pool, topic, self.user_id).await {
+                        Some(true) => {}
+                        Some(false) => return self.send(&ServerMessage::Error { message: "Topic not found".to_string() }).await,
+                        None => return self.send(&ServerMessage::Error { message: "Server error".to_string() }).await,
+                    }
+                    self.topics.insert(topic);
+                    if topic.kind == TopicKind::Blog {
+                        presence.join(topic.id, self.session_id, self.user_id, &self.username);
+                    }
+                }
+                self.send(&ServerMessage::Subscribed { topic }).await
+            }
+            ClientMessage::Unsubscribe { topic } => {
+                if self.topics.remove(&topic) && topic.kind == TopicKind::Blog {
+                    presence.leave(topic.id, self.session_id);
+                }
+                self.send(&ServerMessage::Unsubscribed { topic }).await
+            }
+            ClientMessage::Typing { blog_id, parent_comment_id } => {
+                presence.set_typing(blog_id, self.session_id, Some(parent_comment_id));
+                Ok(())
+            }
+            ClientMessage::StopTyping { blog_id } => {
+                presence.set_typing(blog_id, self.session_id, None);
+                Ok(())
+            }
+        }
+    }
+
+    async fn forward_event(&mut self, live: LiveEvent, pool: &web::Data<DbPool>) -> Result<(), Closed> {
+        let topics: Vec<Topic> = self.topics.iter().filter(|topic| topic.matches(&live.event)).copied().collect();
+        if topics.is_empty() {
+            return Ok(());
+        }
+
+        let blog_subscriber = self.topics.contains(&Topic { kind: TopicKind::Blog, id: live.event.blog_id });
+        match events::admits(&self.audience, pool, &live, blog_subscriber).await {
+            Some(true) => {}
+            Some(false) => return Ok(()),
+            None => return self.send(&ServerMessage::Error { message: format!("Event {} could not be delivered", live.event.id) }).await,
+        }
+        for topic in topics {
+            self.send(&ServerMessage::Event { topic, event: live.event.clone() }).await?;
+        }
+        Ok(())
+    }
+
+    async fn forward_presence(&mut self, update: ServerMessage) -> Result<(), Closed> {
+        match &update {
+            ServerMessage::Presence { blog_id, .. } if self.topics.contains(&Topic { kind: TopicKind::Blog, id: *blog_id }) => {
+                self.send(&update).await
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Blogs only count as existing when `user_id` may read them without a
+/// password. Returns `None` when the lookup itself failed.
+async fn topic_exists(pool: &web::Data<DbPool>, topic: Topic, user_id: Uuid) -> Option<bool> {
+    let pool = pool.clone();
+    let result = web::block(move || {
+        let mut conn = pool.get().expect("couldn't get db connection from pool");
+        match topic.kind {
+            TopicKind::Blog => {
+                let blog = get_blog(&mut conn, topic.id)?;
+                can_read_blog(&mut conn, &blog, Some(user_id), None)
+            }
+            TopicKind::User => get_user(&mut conn, topic.id).map(|_| true),
+        }
+    }).await;
+
+    match result {
+        Ok(Ok(readable)) => Some(readable),
+        Ok(Err(diesel::result::Error::NotFound)) => Some(false),
+        _ => None,
+    }
+}